@@ -0,0 +1,40 @@
+//! Collateral baskets grouping several items under one security
+
+use soroban_sdk::{contracttype, Address, Vec};
+
+/// Basket member: a collateral entry and the share of it pledged to the basket
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BasketMember {
+    pub collateral_id: u64,
+    /// Pledged share of the collateral in basis points (10000 = whole item)
+    pub share_bps: u32,
+}
+
+/// Collateral basket
+#[contracttype]
+#[derive(Clone)]
+pub struct CollateralBasket {
+    pub id: u64,
+    pub owner: Address,
+    pub members: Vec<BasketMember>,
+    pub locked: bool,
+    pub locked_by_escrow: u64,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+/// Aggregate basket valuation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BasketValuation {
+    pub basket_id: u64,
+    /// Sum of pledged shares at current valuation
+    pub gross_value: i128,
+    /// Gross value after per-asset-type haircuts
+    pub adjusted_value: i128,
+    /// Whether every member is verified
+    pub verified: bool,
+    /// Valuation timestamp of the member valued longest ago
+    pub oldest_valuation: u64,
+}
@@ -50,6 +50,16 @@ pub enum RiskRating {
     D = 9,
 }
 
+/// Haircut and eligibility rule for an asset class and risk rating
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HaircutRule {
+    /// Valuation haircut in basis points
+    pub haircut_bps: u32,
+    /// Whether collateral in this bucket may secure loans at all
+    pub eligible: bool,
+}
+
 /// Real estate classification
 #[contracttype]
 #[derive(Clone)]
@@ -0,0 +1,27 @@
+//! Insurance policies covering registered collateral
+
+use soroban_sdk::{contracttype, Address, BytesN};
+
+/// Insurance policy attached to a collateral entry
+#[contracttype]
+#[derive(Clone)]
+pub struct InsurancePolicy {
+    pub collateral_id: u64,
+    pub insurer: Address,
+    pub policy_hash: BytesN<32>,
+    pub coverage_amount: i128,
+    pub beneficiary: Address,
+    pub expiry: u64,
+    pub state: PolicyState,
+    pub registered_at: u64,
+    pub updated_at: u64,
+}
+
+/// Policy lifecycle state, driven by the insurer
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PolicyState {
+    Pending = 0,
+    Confirmed = 1,
+    Cancelled = 2,
+}
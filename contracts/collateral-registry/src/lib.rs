@@ -8,26 +8,40 @@
 //! - Authenticity verification and classification
 //! - Efficient on-chain storage with IPFS integration
 //! - Comprehensive audit trails via events
+//! - SEP-41 token representation of verified collateral and its fractions
+//! - Collateral baskets locked and valued as a single security
+//! - Insurance policy tracking with lapse and under-coverage flags
+//! - Electronic warehouse receipts for commodity collateral
 
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, String, Vec,
+    contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, IntoVal, String,
+    Symbol, Val, Vec,
 };
 
-mod classification;
-mod collateral;
-mod locking;
-mod ownership;
-mod valuation;
-mod verification;
-
+pub mod basket;
+pub mod classification;
+pub mod collateral;
+pub mod insurance;
+pub mod locking;
+pub mod ownership;
+pub mod tokenization;
+pub mod valuation;
+pub mod verification;
+pub mod warehouse;
+
+use basket::*;
 use classification::*;
 use collateral::*;
+use insurance::*;
 use locking::*;
 use ownership::*;
+use tokenization::*;
 use valuation::*;
 use verification::*;
+use warehouse::*;
 
 /// Contract errors
 #[contracttype]
@@ -58,6 +72,20 @@ pub enum ContractError {
     InvalidLockingEscrow = 23,
     LockingFailed = 24,
     UnlockingFailed = 25,
+    CollateralTokenized = 26,
+    CollateralNotTokenized = 27,
+    CollateralNotVerified = 28,
+    BasketNotFound = 29,
+    InvalidBasket = 30,
+    CollateralInBasket = 31,
+    BasketValueDecreased = 32,
+    InsuranceNotFound = 33,
+    InvalidInsurance = 34,
+    CustodianNotApproved = 35,
+    ReceiptNotFound = 36,
+    InvalidQuantity = 37,
+    CollateralIneligible = 38,
+    TokenWasmNotSet = 39,
 }
 
 impl From<soroban_sdk::Error> for ContractError {
@@ -132,70 +160,14 @@ impl CollateralRegistry {
     ) -> Result<u64, ContractError> {
         owner.require_auth();
 
-        // Validate inputs
-        if initial_valuation <= 0 {
-            return Err(ContractError::InvalidValuation);
-        }
-
-        // Check for duplicate asset hash
-        let hash_key = format_asset_hash_key(&asset_hash);
-        if env.storage().persistent().has(&hash_key) {
-            return Err(ContractError::DuplicateCollateral);
-        }
-
-        // Get next collateral ID
-        let collateral_id: u64 = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("next_id"))
-            .unwrap_or(1u64);
-
-        // Create collateral record
-        let collateral = Collateral {
-            id: collateral_id,
-            owner: owner.clone(),
+        store_new_collateral(
+            &env,
+            owner,
             asset_hash,
-            metadata_uri: metadata_uri.clone(),
+            metadata_uri,
             asset_type,
-            current_valuation: initial_valuation,
-            previous_valuation: 0,
-            valuation_timestamp: env.ledger().timestamp(),
-            status: CollateralStatus::Active,
-            locked: false,
-            locked_by_escrow: 0,
-            verification_status: collateral::VerificationStatus::Pending,
-            verified_by: None,
-            verified_at: 0,
-            created_at: env.ledger().timestamp(),
-            updated_at: env.ledger().timestamp(),
-            expiry_date: 0,
-            fractionalized: false,
-            fraction_count: 0,
-        };
-
-        // Store collateral
-        let storage_key = format_collateral_storage_key(collateral_id);
-        env.storage().persistent().set(&storage_key, &collateral);
-
-        // Store asset hash mapping
-        env.storage().persistent().set(&hash_key, &collateral_id);
-
-        // Store owner mapping
-        let owner_key = format_owner_collateral_key(&owner, collateral_id);
-        env.storage().persistent().set(&owner_key, &collateral_id);
-
-        // Update next ID
-        env.storage()
-            .instance()
-            .set(&symbol_short!("next_id"), &(collateral_id + 1));
-
-        // Emit event
-        env.events().publish(
-            (symbol_short!("col_regd"),),
-            (collateral_id, owner, initial_valuation),
-        );
-
-        Ok(collateral_id)
+            initial_valuation,
+        )
     }
 
     /// Update collateral valuation with oracle verification
@@ -269,6 +241,9 @@ impl CollateralRegistry {
         env.events()
             .publish((symbol_short!("val_updt"),), (collateral_id, new_valuation));
 
+        // A higher valuation may leave the policy under-covered
+        refresh_insurance_flag(&env, &collateral);
+
         Ok(())
     }
 
@@ -296,6 +271,12 @@ impl CollateralRegistry {
         // Verify current owner
         collateral.owner.require_auth();
 
+        // Tokenized collateral changes hands through its token contract
+        let token_key = format_token_binding_key(collateral_id);
+        if env.storage().persistent().has(&token_key) {
+            return Err(ContractError::CollateralTokenized);
+        }
+
         // Check if collateral is locked
         if collateral.locked {
             return Err(ContractError::CollateralLocked);
@@ -306,41 +287,7 @@ impl CollateralRegistry {
             return Err(ContractError::InvalidTransfer);
         }
 
-        // Update ownership
-        let previous_owner = collateral.owner.clone();
-        collateral.owner = new_owner.clone();
-        collateral.updated_at = env.ledger().timestamp();
-
-        // Store updated collateral
-        env.storage().persistent().set(&storage_key, &collateral);
-
-        // Update owner mappings
-        let old_owner_key = format_owner_collateral_key(&previous_owner, collateral_id);
-        env.storage().persistent().remove(&old_owner_key);
-
-        let new_owner_key = format_owner_collateral_key(&new_owner, collateral_id);
-        env.storage()
-            .persistent()
-            .set(&new_owner_key, &collateral_id);
-
-        // Record transfer
-        let transfer_record = OwnershipTransfer {
-            collateral_id,
-            from: previous_owner.clone(),
-            to: new_owner.clone(),
-            timestamp: env.ledger().timestamp(),
-        };
-
-        let transfer_key = format_transfer_history_key(collateral_id, env.ledger().timestamp());
-        env.storage()
-            .persistent()
-            .set(&transfer_key, &transfer_record);
-
-        // Emit event
-        env.events().publish(
-            (symbol_short!("col_xfer"),),
-            (collateral_id, previous_owner, new_owner),
-        );
+        apply_ownership_transfer(&env, &mut collateral, new_owner);
 
         Ok(())
     }
@@ -358,6 +305,62 @@ impl CollateralRegistry {
         collateral_id: u64,
         escrow_id: u64,
     ) -> Result<(), ContractError> {
+        lock_for(&env, collateral_id, escrow_id, None)
+    }
+
+    /// Lock collateral on behalf of a secured party (lender)
+    ///
+    /// Unlike `lock_collateral`, only the secured party can release the lock,
+    /// and its consent is required for partial releases while locked.
+    /// Tokenized collateral and basket members can't be seized or liquidated
+    /// on their own, so they can't be locked this way either.
+    ///
+    /// # Arguments
+    /// * `collateral_id` - ID of collateral
+    /// * `escrow_id` - ID of escrow/loan
+    /// * `secured_party` - Lender or contract holding the security interest
+    ///
+    /// # Events
+    /// Emits `CollateralLocked` event
+    pub fn lock_collateral_for(
+        env: Env,
+        collateral_id: u64,
+        escrow_id: u64,
+        secured_party: Address,
+    ) -> Result<(), ContractError> {
+        if env
+            .storage()
+            .persistent()
+            .has(&format_token_binding_key(collateral_id))
+        {
+            return Err(ContractError::CollateralTokenized);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&format_basket_member_key(collateral_id))
+        {
+            return Err(ContractError::CollateralInBasket);
+        }
+
+        lock_for(&env, collateral_id, escrow_id, Some(secured_party))
+    }
+
+    /// Get the secured party holding a lock on collateral, if any
+    pub fn get_secured_party(env: Env, collateral_id: u64) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&format_secured_party_key(collateral_id))
+    }
+
+    /// Unlock collateral from loan
+    ///
+    /// # Arguments
+    /// * `collateral_id` - ID of collateral
+    ///
+    /// # Events
+    /// Emits `CollateralUnlocked` event
+    pub fn unlock_collateral(env: Env, collateral_id: u64) -> Result<(), ContractError> {
         // Get collateral
         let storage_key = format_collateral_storage_key(collateral_id);
         let mut collateral: Collateral = env
@@ -366,48 +369,71 @@ impl CollateralRegistry {
             .get(&storage_key)
             .ok_or(ContractError::CollateralNotFound)?;
 
-        // Verify owner
-        collateral.owner.require_auth();
+        // Verify secured party, or owner for plain locks
+        let secured_party_key = format_secured_party_key(collateral_id);
+        match env
+            .storage()
+            .persistent()
+            .get::<_, Address>(&secured_party_key)
+        {
+            Some(secured_party) => secured_party.require_auth(),
+            None => collateral.owner.require_auth(),
+        }
 
-        // Check if already locked
-        if collateral.locked {
-            return Err(ContractError::CollateralLocked);
+        // Check if locked
+        if !collateral.locked {
+            return Err(ContractError::CollateralNotLocked);
+        }
+
+        // Members of a locked basket are released with the basket
+        if let Some(basket_id) = env
+            .storage()
+            .persistent()
+            .get::<_, u64>(&format_basket_member_key(collateral_id))
+        {
+            let basket: CollateralBasket = env
+                .storage()
+                .persistent()
+                .get(&format_basket_key(basket_id))
+                .ok_or(ContractError::BasketNotFound)?;
+            if basket.locked {
+                return Err(ContractError::CollateralInBasket);
+            }
         }
 
-        // Lock collateral
-        collateral.locked = true;
-        collateral.locked_by_escrow = escrow_id;
+        // Unlock collateral
+        let escrow_id = collateral.locked_by_escrow;
+        collateral.locked = false;
+        collateral.locked_by_escrow = 0;
         collateral.updated_at = env.ledger().timestamp();
 
         // Store updated collateral
         env.storage().persistent().set(&storage_key, &collateral);
-
-        // Record locking
-        let lock_record = CollateralLock {
-            collateral_id,
-            escrow_id,
-            locked_at: env.ledger().timestamp(),
-            locked_by: collateral.owner.clone(),
-        };
-
-        let lock_key = format_lock_history_key(collateral_id, env.ledger().timestamp());
-        env.storage().persistent().set(&lock_key, &lock_record);
+        env.storage().persistent().remove(&secured_party_key);
 
         // Emit event
         env.events()
-            .publish((symbol_short!("col_lock"),), (collateral_id, escrow_id));
+            .publish((symbol_short!("col_unlk"),), (collateral_id, escrow_id));
 
         Ok(())
     }
 
-    /// Unlock collateral from loan
+    /// Seize locked collateral on default (secured party only)
+    ///
+    /// Releases the lock and transfers ownership to `recipient`, marking the
+    /// collateral `Seized`.
     ///
     /// # Arguments
     /// * `collateral_id` - ID of collateral
+    /// * `recipient` - Address taking ownership (typically the lender)
     ///
     /// # Events
-    /// Emits `CollateralUnlocked` event
-    pub fn unlock_collateral(env: Env, collateral_id: u64) -> Result<(), ContractError> {
+    /// Emits `CollateralSeized` event
+    pub fn seize_collateral(
+        env: Env,
+        collateral_id: u64,
+        recipient: Address,
+    ) -> Result<(), ContractError> {
         // Get collateral
         let storage_key = format_collateral_storage_key(collateral_id);
         let mut collateral: Collateral = env
@@ -416,30 +442,190 @@ impl CollateralRegistry {
             .get(&storage_key)
             .ok_or(ContractError::CollateralNotFound)?;
 
-        // Verify owner
-        collateral.owner.require_auth();
+        // Only the secured party holding the lock can seize
+        let secured_party_key = format_secured_party_key(collateral_id);
+        let secured_party: Address = env
+            .storage()
+            .persistent()
+            .get(&secured_party_key)
+            .ok_or(ContractError::Unauthorized)?;
+        secured_party.require_auth();
 
-        // Check if locked
         if !collateral.locked {
             return Err(ContractError::CollateralNotLocked);
         }
 
-        // Unlock collateral
+        // Tokenized collateral changes hands through its token contract
+        if env
+            .storage()
+            .persistent()
+            .has(&format_token_binding_key(collateral_id))
+        {
+            return Err(ContractError::CollateralTokenized);
+        }
+
+        // Members of a basket are seized with the basket
+        if env
+            .storage()
+            .persistent()
+            .has(&format_basket_member_key(collateral_id))
+        {
+            return Err(ContractError::CollateralInBasket);
+        }
+
         let escrow_id = collateral.locked_by_escrow;
         collateral.locked = false;
         collateral.locked_by_escrow = 0;
-        collateral.updated_at = env.ledger().timestamp();
-
-        // Store updated collateral
-        env.storage().persistent().set(&storage_key, &collateral);
+        collateral.status = CollateralStatus::Seized;
+        env.storage().persistent().remove(&secured_party_key);
+
+        if recipient != collateral.owner {
+            apply_ownership_transfer(&env, &mut collateral, recipient.clone());
+        } else {
+            collateral.updated_at = env.ledger().timestamp();
+            env.storage().persistent().set(&storage_key, &collateral);
+        }
 
         // Emit event
-        env.events()
-            .publish((symbol_short!("col_unlk"),), (collateral_id, escrow_id));
+        env.events().publish(
+            (symbol_short!("col_seiz"),),
+            (collateral_id, escrow_id, recipient),
+        );
 
         Ok(())
     }
 
+    /// Hand a share of locked collateral to a liquidator (secured party only)
+    ///
+    /// The share is recorded as the liquidator's claim on the collateral. Once
+    /// claims reach the whole item the lock is released and the collateral is
+    /// marked `Liquidated`; a liquidator holding the entire claim also takes
+    /// ownership.
+    ///
+    /// # Arguments
+    /// * `collateral_id` - ID of collateral
+    /// * `liquidator` - Address receiving the share
+    /// * `share_bps` - Share of the collateral in basis points (1-10000)
+    ///
+    /// # Returns
+    /// Total share of the collateral liquidated so far
+    ///
+    /// # Events
+    /// Emits `CollateralLiquidated` event
+    pub fn liquidate_collateral(
+        env: Env,
+        collateral_id: u64,
+        liquidator: Address,
+        share_bps: u32,
+    ) -> Result<u32, ContractError> {
+        // Get collateral
+        let storage_key = format_collateral_storage_key(collateral_id);
+        let mut collateral: Collateral = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::CollateralNotFound)?;
+
+        // Only the secured party holding the lock can liquidate
+        let secured_party_key = format_secured_party_key(collateral_id);
+        let secured_party: Address = env
+            .storage()
+            .persistent()
+            .get(&secured_party_key)
+            .ok_or(ContractError::Unauthorized)?;
+        secured_party.require_auth();
+
+        if !collateral.locked {
+            return Err(ContractError::CollateralNotLocked);
+        }
+
+        if share_bps == 0 || share_bps > 10000 {
+            return Err(ContractError::InvalidQuantity);
+        }
+
+        // Tokenized collateral changes hands through its token contract
+        if env
+            .storage()
+            .persistent()
+            .has(&format_token_binding_key(collateral_id))
+        {
+            return Err(ContractError::CollateralTokenized);
+        }
+
+        // Members of a basket are liquidated with the basket
+        if env
+            .storage()
+            .persistent()
+            .has(&format_basket_member_key(collateral_id))
+        {
+            return Err(ContractError::CollateralInBasket);
+        }
+
+        // Claims never exceed the whole item
+        let total_key = format_liquidated_share_key(collateral_id);
+        let liquidated: u32 = env.storage().persistent().get(&total_key).unwrap_or(0);
+        let share = share_bps.min(10000 - liquidated);
+        let liquidated = liquidated + share;
+        env.storage().persistent().set(&total_key, &liquidated);
+
+        let now = env.ledger().timestamp();
+        let claim_key = format_liquidation_claim_key(collateral_id, &liquidator);
+        let mut claim: LiquidationClaim =
+            env.storage()
+                .persistent()
+                .get(&claim_key)
+                .unwrap_or(LiquidationClaim {
+                    collateral_id,
+                    holder: liquidator.clone(),
+                    share_bps: 0,
+                    updated_at: now,
+                });
+        claim.share_bps += share;
+        claim.updated_at = now;
+        env.storage().persistent().set(&claim_key, &claim);
+
+        if liquidated == 10000 {
+            collateral.locked = false;
+            collateral.locked_by_escrow = 0;
+            collateral.status = CollateralStatus::Liquidated;
+            env.storage().persistent().remove(&secured_party_key);
+
+            if claim.share_bps == 10000 && liquidator != collateral.owner {
+                apply_ownership_transfer(&env, &mut collateral, liquidator.clone());
+            } else {
+                collateral.updated_at = now;
+                env.storage().persistent().set(&storage_key, &collateral);
+            }
+        }
+
+        // Emit event
+        env.events().publish(
+            (symbol_short!("col_liq"),),
+            (collateral_id, liquidator, share, liquidated),
+        );
+
+        Ok(liquidated)
+    }
+
+    /// Get a liquidator's claim on collateral, if any
+    pub fn get_liquidation_claim(
+        env: Env,
+        collateral_id: u64,
+        holder: Address,
+    ) -> Option<LiquidationClaim> {
+        env.storage()
+            .persistent()
+            .get(&format_liquidation_claim_key(collateral_id, &holder))
+    }
+
+    /// Get the share of collateral liquidated so far, in basis points
+    pub fn get_liquidated_share(env: Env, collateral_id: u64) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&format_liquidated_share_key(collateral_id))
+            .unwrap_or(0)
+    }
+
     /// Verify collateral authenticity
     ///
     /// # Arguments
@@ -503,37 +689,250 @@ impl CollateralRegistry {
         Ok(())
     }
 
-    /// Get collateral details
+    /// Represent collateral as a SEP-41 token
+    ///
+    /// Deploys a collateral token contract from the admin-approved wasm hash,
+    /// binds it to a verified collateral entry and mints `supply` units to the
+    /// owner. A supply of 1 represents the whole item; a larger supply splits
+    /// it into fractions. Locked collateral and basket members can't be
+    /// tokenized.
     ///
     /// # Arguments
     /// * `collateral_id` - ID of collateral
+    /// * `supply` - Number of units to mint
+    /// * `name` - Token name
+    /// * `symbol` - Token symbol
     ///
     /// # Returns
-    /// Collateral details
-    pub fn get_collateral(env: Env, collateral_id: u64) -> Result<Collateral, ContractError> {
+    /// Address of the deployed token contract
+    ///
+    /// # Events
+    /// Emits `CollateralTokenized` event
+    pub fn tokenize_collateral(
+        env: Env,
+        collateral_id: u64,
+        supply: i128,
+        name: String,
+        symbol: String,
+    ) -> Result<Address, ContractError> {
+        // Get collateral
         let storage_key = format_collateral_storage_key(collateral_id);
-        env.storage()
+        let mut collateral: Collateral = env
+            .storage()
             .persistent()
             .get(&storage_key)
-            .ok_or(ContractError::CollateralNotFound)
-    }
+            .ok_or(ContractError::CollateralNotFound)?;
 
-    /// Get collateral by asset hash
-    ///
-    /// # Arguments
-    /// * `asset_hash` - SHA-256 hash of asset
-    ///
-    /// # Returns
-    /// Collateral ID if found
-    pub fn get_collateral_by_hash(env: Env, asset_hash: BytesN<32>) -> Result<u64, ContractError> {
-        let hash_key = format_asset_hash_key(&asset_hash);
-        env.storage()
+        // Verify owner
+        collateral.owner.require_auth();
+
+        // Only verified, active collateral can be tokenized
+        if collateral.verification_status != collateral::VerificationStatus::Verified {
+            return Err(ContractError::CollateralNotVerified);
+        }
+
+        if collateral.status != CollateralStatus::Active {
+            return Err(ContractError::InvalidCollateralData);
+        }
+
+        // Locked collateral stays with its secured party, and basket members
+        // are pledged through the basket
+        if collateral.locked {
+            return Err(ContractError::CollateralLocked);
+        }
+
+        if env
+            .storage()
             .persistent()
-            .get(&hash_key)
-            .ok_or(ContractError::CollateralNotFound)
+            .has(&format_basket_member_key(collateral_id))
+        {
+            return Err(ContractError::CollateralInBasket);
+        }
+
+        let token_key = format_token_binding_key(collateral_id);
+        if env.storage().persistent().has(&token_key) {
+            return Err(ContractError::CollateralTokenized);
+        }
+
+        if supply <= 0 || supply > u32::MAX as i128 {
+            return Err(ContractError::InvalidCollateralData);
+        }
+
+        let wasm_hash: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("tok_wasm"))
+            .ok_or(ContractError::TokenWasmNotSet)?;
+
+        // Deploy the token; the collateral ID salts its address
+        let mut salt = [0u8; 32];
+        salt[24..].copy_from_slice(&collateral_id.to_be_bytes());
+        let token = env
+            .deployer()
+            .with_current_contract(BytesN::from_array(&env, &salt))
+            .deploy_v2(wasm_hash, ());
+
+        // Initialize the token; it mints the full supply to the owner
+        let init_args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                env.current_contract_address().into_val(&env),
+                collateral_id.into_val(&env),
+                collateral.owner.into_val(&env),
+                supply.into_val(&env),
+                name.into_val(&env),
+                symbol.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<Val>(&token, &Symbol::new(&env, "initialize"), init_args);
+
+        // Record fractionalization on the collateral
+        collateral.fractionalized = supply > 1;
+        collateral.fraction_count = supply as u32;
+        collateral.updated_at = env.ledger().timestamp();
+        env.storage().persistent().set(&storage_key, &collateral);
+
+        let binding = CollateralTokenBinding {
+            collateral_id,
+            token: token.clone(),
+            supply,
+            tokenized_by: collateral.owner.clone(),
+            tokenized_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&token_key, &binding);
+
+        // Emit event
+        env.events().publish(
+            (symbol_short!("col_tokn"),),
+            (collateral_id, token.clone(), supply),
+        );
+
+        Ok(token)
     }
 
-    /// Get collateral valuation history
+    /// Set the collateral token wasm hash deployed by `tokenize_collateral`
+    /// (admin only)
+    ///
+    /// # Arguments
+    /// * `wasm_hash` - Hash of the uploaded `collateral-token` wasm
+    pub fn set_token_wasm(env: Env, wasm_hash: BytesN<32>) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("tok_wasm"), &wasm_hash);
+
+        Ok(())
+    }
+
+    /// Get the collateral token wasm hash
+    pub fn get_token_wasm(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&symbol_short!("tok_wasm"))
+    }
+
+    /// Validate a token transfer reported by a collateral token contract
+    ///
+    /// Only the token bound to `collateral_id` may call this. Transfers are
+    /// rejected while the collateral is locked or no longer active. For whole
+    /// (non-fractionalized) items, registry ownership follows the token.
+    ///
+    /// # Arguments
+    /// * `collateral_id` - ID of collateral
+    /// * `from` - Sending holder
+    /// * `to` - Receiving holder
+    /// * `amount` - Number of units transferred
+    pub fn on_token_transfer(
+        env: Env,
+        collateral_id: u64,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        let binding: CollateralTokenBinding = env
+            .storage()
+            .persistent()
+            .get(&format_token_binding_key(collateral_id))
+            .ok_or(ContractError::CollateralNotTokenized)?;
+
+        binding.token.require_auth();
+
+        let storage_key = format_collateral_storage_key(collateral_id);
+        let mut collateral: Collateral = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::CollateralNotFound)?;
+
+        if collateral.locked {
+            return Err(ContractError::CollateralLocked);
+        }
+
+        if collateral.status != CollateralStatus::Active {
+            return Err(ContractError::InvalidTransfer);
+        }
+
+        // Whole items: the single unit is the ownership title
+        if binding.supply == 1 && amount == 1 && from == collateral.owner && to != from {
+            apply_ownership_transfer(&env, &mut collateral, to);
+        }
+
+        Ok(())
+    }
+
+    /// Get the token contract representing a collateral entry
+    ///
+    /// # Arguments
+    /// * `collateral_id` - ID of collateral
+    ///
+    /// # Returns
+    /// Token binding details
+    pub fn get_collateral_token(
+        env: Env,
+        collateral_id: u64,
+    ) -> Result<CollateralTokenBinding, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&format_token_binding_key(collateral_id))
+            .ok_or(ContractError::CollateralNotTokenized)
+    }
+
+    /// Get collateral details
+    ///
+    /// # Arguments
+    /// * `collateral_id` - ID of collateral
+    ///
+    /// # Returns
+    /// Collateral details
+    pub fn get_collateral(env: Env, collateral_id: u64) -> Result<Collateral, ContractError> {
+        let storage_key = format_collateral_storage_key(collateral_id);
+        env.storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::CollateralNotFound)
+    }
+
+    /// Get collateral by asset hash
+    ///
+    /// # Arguments
+    /// * `asset_hash` - SHA-256 hash of asset
+    ///
+    /// # Returns
+    /// Collateral ID if found
+    pub fn get_collateral_by_hash(env: Env, asset_hash: BytesN<32>) -> Result<u64, ContractError> {
+        let hash_key = format_asset_hash_key(&asset_hash);
+        env.storage()
+            .persistent()
+            .get(&hash_key)
+            .ok_or(ContractError::CollateralNotFound)
+    }
+
+    /// Get collateral valuation history
     ///
     /// # Arguments
     /// * `collateral_id` - ID of collateral
@@ -619,47 +1018,1684 @@ impl CollateralRegistry {
             .get(&classification_key)
             .ok_or(ContractError::InvalidClassification)
     }
-}
 
-// Helper functions
+    /// Set the valuation haircut applied to an asset type
+    ///
+    /// # Arguments
+    /// * `asset_type` - Asset type the haircut applies to
+    /// * `haircut_bps` - Haircut in basis points (0-10000)
+    pub fn set_haircut(
+        env: Env,
+        asset_type: AssetType,
+        haircut_bps: u32,
+    ) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
 
-fn format_collateral_storage_key(collateral_id: u64) -> (soroban_sdk::Symbol, u64) {
-    (symbol_short!("collat"), collateral_id)
-}
+        admin.require_auth();
 
-fn format_asset_hash_key(asset_hash: &BytesN<32>) -> (soroban_sdk::Symbol, BytesN<32>) {
-    (symbol_short!("hash"), asset_hash.clone())
-}
+        if haircut_bps > 10000 {
+            return Err(ContractError::InvalidValuation);
+        }
 
-fn format_owner_collateral_key(
-    owner: &Address,
-    collateral_id: u64,
-) -> (soroban_sdk::Symbol, Address, u64) {
-    (symbol_short!("owner"), owner.clone(), collateral_id)
-}
+        env.storage()
+            .instance()
+            .set(&format_haircut_key(asset_type), &haircut_bps);
 
-fn format_valuation_history_key(
-    collateral_id: u64,
-    timestamp: u64,
-) -> (soroban_sdk::Symbol, u64, u64) {
-    (symbol_short!("val"), collateral_id, timestamp)
-}
+        env.events()
+            .publish((symbol_short!("haircut"),), (asset_type, haircut_bps));
 
-fn format_transfer_history_key(
-    collateral_id: u64,
-    timestamp: u64,
-) -> (soroban_sdk::Symbol, u64, u64) {
-    (symbol_short!("trans"), collateral_id, timestamp)
-}
+        Ok(())
+    }
 
-fn format_lock_history_key(collateral_id: u64, timestamp: u64) -> (soroban_sdk::Symbol, u64, u64) {
-    (symbol_short!("lock"), collateral_id, timestamp)
-}
+    /// Get the valuation haircut for an asset type (basis points, 0 if unset)
+    pub fn get_haircut(env: Env, asset_type: AssetType) -> u32 {
+        env.storage()
+            .instance()
+            .get(&format_haircut_key(asset_type))
+            .unwrap_or(0)
+    }
 
-fn format_verification_history_key(collateral_id: u64) -> (soroban_sdk::Symbol, u64) {
-    (symbol_short!("verif"), collateral_id)
-}
+    /// Set the age after which a valuation is no longer fresh (admin only)
+    ///
+    /// # Arguments
+    /// * `max_age` - Maximum valuation age in seconds
+    pub fn set_max_valuation_age(env: Env, max_age: u64) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
 
-fn format_classification_key(collateral_id: u64) -> (soroban_sdk::Symbol, u64) {
-    (symbol_short!("class"), collateral_id)
+        admin.require_auth();
+
+        if max_age == 0 {
+            return Err(ContractError::InvalidValuation);
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("max_vage"), &max_age);
+
+        Ok(())
+    }
+
+    /// Get the maximum valuation age in seconds
+    pub fn get_max_valuation_age(env: Env) -> u64 {
+        max_valuation_age(&env)
+    }
+
+    /// Group several collateral entries into a basket
+    ///
+    /// Every member must be owned by `owner`, active, unlocked and not already
+    /// part of another basket.
+    ///
+    /// # Arguments
+    /// * `owner` - Owner of all member collateral
+    /// * `members` - Collateral IDs and the share of each pledged to the basket
+    ///
+    /// # Returns
+    /// The basket ID
+    ///
+    /// # Events
+    /// Emits `BasketCreated` event
+    pub fn create_basket(
+        env: Env,
+        owner: Address,
+        members: Vec<BasketMember>,
+    ) -> Result<u64, ContractError> {
+        owner.require_auth();
+
+        if members.is_empty() {
+            return Err(ContractError::InvalidBasket);
+        }
+
+        // Validate members
+        for (i, member) in members.iter().enumerate() {
+            for other in members.iter().skip(i + 1) {
+                if other.collateral_id == member.collateral_id {
+                    return Err(ContractError::InvalidBasket);
+                }
+            }
+            validate_basket_member(&env, &owner, &member)?;
+        }
+
+        let basket_id: u64 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("next_bsk"))
+            .unwrap_or(1u64);
+
+        let basket = CollateralBasket {
+            id: basket_id,
+            owner: owner.clone(),
+            members: members.clone(),
+            locked: false,
+            locked_by_escrow: 0,
+            created_at: env.ledger().timestamp(),
+            updated_at: env.ledger().timestamp(),
+        };
+
+        env.storage()
+            .persistent()
+            .set(&format_basket_key(basket_id), &basket);
+
+        // Record membership
+        for member in members.iter() {
+            env.storage()
+                .persistent()
+                .set(&format_basket_member_key(member.collateral_id), &basket_id);
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("next_bsk"), &(basket_id + 1));
+
+        env.events().publish(
+            (symbol_short!("bsk_new"),),
+            (basket_id, owner, members.len()),
+        );
+
+        Ok(basket_id)
+    }
+
+    /// Lock every basket member for an escrow or loan
+    ///
+    /// Either all members are locked or none are.
+    ///
+    /// # Arguments
+    /// * `basket_id` - ID of basket
+    /// * `escrow_id` - ID of escrow/loan
+    ///
+    /// # Events
+    /// Emits `BasketLocked` event
+    pub fn lock_basket(env: Env, basket_id: u64, escrow_id: u64) -> Result<(), ContractError> {
+        lock_basket_with(&env, basket_id, escrow_id, None)
+    }
+
+    /// Lock every basket member on behalf of a secured party (lender)
+    ///
+    /// Unlike `lock_basket`, only the secured party can release the basket.
+    ///
+    /// # Arguments
+    /// * `basket_id` - ID of basket
+    /// * `escrow_id` - ID of escrow/loan
+    /// * `secured_party` - Lender or contract holding the security interest
+    ///
+    /// # Events
+    /// Emits `BasketLocked` event
+    pub fn lock_basket_for(
+        env: Env,
+        basket_id: u64,
+        escrow_id: u64,
+        secured_party: Address,
+    ) -> Result<(), ContractError> {
+        lock_basket_with(&env, basket_id, escrow_id, Some(secured_party))
+    }
+
+    /// Unlock every basket member
+    ///
+    /// # Arguments
+    /// * `basket_id` - ID of basket
+    ///
+    /// # Events
+    /// Emits `BasketUnlocked` event
+    pub fn unlock_basket(env: Env, basket_id: u64) -> Result<(), ContractError> {
+        let basket_key = format_basket_key(basket_id);
+        let mut basket: CollateralBasket = env
+            .storage()
+            .persistent()
+            .get(&basket_key)
+            .ok_or(ContractError::BasketNotFound)?;
+
+        // Verify secured party, or owner for plain locks
+        let secured_party_key = format_basket_secured_party_key(basket_id);
+        match env
+            .storage()
+            .persistent()
+            .get::<_, Address>(&secured_party_key)
+        {
+            Some(secured_party) => secured_party.require_auth(),
+            None => basket.owner.require_auth(),
+        }
+
+        if !basket.locked {
+            return Err(ContractError::CollateralNotLocked);
+        }
+
+        for member in basket.members.iter() {
+            unlock_basket_member(&env, member.collateral_id)?;
+        }
+
+        let escrow_id = basket.locked_by_escrow;
+        basket.locked = false;
+        basket.locked_by_escrow = 0;
+        basket.updated_at = env.ledger().timestamp();
+        env.storage().persistent().set(&basket_key, &basket);
+        env.storage().persistent().remove(&secured_party_key);
+
+        env.events()
+            .publish((symbol_short!("bsk_unlk"),), (basket_id, escrow_id));
+
+        Ok(())
+    }
+
+    /// Seize a locked basket on default (secured party only)
+    ///
+    /// Members pledged whole pass to `recipient` and are marked `Seized`.
+    /// For partly pledged members, `recipient` gets a liquidation claim on
+    /// the pledged share and the owner keeps the item. The basket is
+    /// dissolved.
+    ///
+    /// # Arguments
+    /// * `basket_id` - ID of basket
+    /// * `recipient` - Address taking the members (typically the lender)
+    ///
+    /// # Events
+    /// Emits `BasketSeized` event
+    pub fn seize_basket(env: Env, basket_id: u64, recipient: Address) -> Result<(), ContractError> {
+        let basket_key = format_basket_key(basket_id);
+        let basket: CollateralBasket = env
+            .storage()
+            .persistent()
+            .get(&basket_key)
+            .ok_or(ContractError::BasketNotFound)?;
+
+        // Only the secured party holding the lock can seize
+        let secured_party_key = format_basket_secured_party_key(basket_id);
+        let secured_party: Address = env
+            .storage()
+            .persistent()
+            .get(&secured_party_key)
+            .ok_or(ContractError::Unauthorized)?;
+        secured_party.require_auth();
+
+        if !basket.locked {
+            return Err(ContractError::CollateralNotLocked);
+        }
+
+        let now = env.ledger().timestamp();
+        for member in basket.members.iter() {
+            let collateral_id = member.collateral_id;
+            let storage_key = format_collateral_storage_key(collateral_id);
+            let mut collateral: Collateral = env
+                .storage()
+                .persistent()
+                .get(&storage_key)
+                .ok_or(ContractError::CollateralNotFound)?;
+            collateral.locked = false;
+            collateral.locked_by_escrow = 0;
+            env.storage()
+                .persistent()
+                .remove(&format_basket_member_key(collateral_id));
+
+            if member.share_bps == 10000 {
+                collateral.status = CollateralStatus::Seized;
+                if recipient != collateral.owner {
+                    apply_ownership_transfer(&env, &mut collateral, recipient.clone());
+                    continue;
+                }
+            } else {
+                let total_key = format_liquidated_share_key(collateral_id);
+                let liquidated: u32 = env.storage().persistent().get(&total_key).unwrap_or(0);
+                let share = member.share_bps.min(10000 - liquidated);
+                env.storage()
+                    .persistent()
+                    .set(&total_key, &(liquidated + share));
+
+                let claim_key = format_liquidation_claim_key(collateral_id, &recipient);
+                let mut claim: LiquidationClaim = env
+                    .storage()
+                    .persistent()
+                    .get(&claim_key)
+                    .unwrap_or(LiquidationClaim {
+                        collateral_id,
+                        holder: recipient.clone(),
+                        share_bps: 0,
+                        updated_at: now,
+                    });
+                claim.share_bps += share;
+                claim.updated_at = now;
+                env.storage().persistent().set(&claim_key, &claim);
+            }
+            collateral.updated_at = now;
+            env.storage().persistent().set(&storage_key, &collateral);
+        }
+
+        env.storage().persistent().remove(&basket_key);
+        env.storage().persistent().remove(&secured_party_key);
+
+        env.events().publish(
+            (symbol_short!("bsk_seiz"),),
+            (basket_id, basket.locked_by_escrow, recipient),
+        );
+
+        Ok(())
+    }
+
+    /// Get the secured party holding a lock on a basket, if any
+    pub fn get_basket_secured_party(env: Env, basket_id: u64) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&format_basket_secured_party_key(basket_id))
+    }
+
+    /// Replace one basket member with another
+    ///
+    /// While the basket is locked the replacement is locked to the same escrow
+    /// and the outgoing member is released, provided the basket's adjusted
+    /// valuation does not fall below its value before the substitution. A
+    /// basket locked to a secured party only takes a verified, freshly valued
+    /// replacement without that party's authorization.
+    ///
+    /// # Arguments
+    /// * `basket_id` - ID of basket
+    /// * `old_collateral_id` - Member being removed
+    /// * `new_member` - Member taking its place
+    ///
+    /// # Events
+    /// Emits `BasketSubstituted` event
+    pub fn substitute_basket_member(
+        env: Env,
+        basket_id: u64,
+        old_collateral_id: u64,
+        new_member: BasketMember,
+    ) -> Result<(), ContractError> {
+        let basket_key = format_basket_key(basket_id);
+        let mut basket: CollateralBasket = env
+            .storage()
+            .persistent()
+            .get(&basket_key)
+            .ok_or(ContractError::BasketNotFound)?;
+
+        // Verify owner
+        basket.owner.require_auth();
+
+        let index = basket
+            .members
+            .iter()
+            .position(|m| m.collateral_id == old_collateral_id)
+            .ok_or(ContractError::InvalidBasket)? as u32;
+
+        validate_basket_member(&env, &basket.owner, &new_member)?;
+
+        if let Some(secured_party) = env
+            .storage()
+            .persistent()
+            .get::<_, Address>(&format_basket_secured_party_key(basket_id))
+        {
+            let replacement: Collateral = env
+                .storage()
+                .persistent()
+                .get(&format_collateral_storage_key(new_member.collateral_id))
+                .ok_or(ContractError::CollateralNotFound)?;
+            let fresh = env.ledger().timestamp()
+                <= replacement
+                    .valuation_timestamp
+                    .saturating_add(max_valuation_age(&env));
+            if replacement.verification_status != collateral::VerificationStatus::Verified || !fresh
+            {
+                secured_party.require_auth();
+            }
+        }
+
+        let value_before = basket_valuation(&env, &basket)?.adjusted_value;
+
+        basket.members.set(index, new_member.clone());
+
+        if basket.locked {
+            if basket_valuation(&env, &basket)?.adjusted_value < value_before {
+                return Err(ContractError::BasketValueDecreased);
+            }
+
+            lock_basket_member(
+                &env,
+                &basket.owner,
+                new_member.collateral_id,
+                basket.locked_by_escrow,
+            );
+            unlock_basket_member(&env, old_collateral_id)?;
+        }
+
+        // Update membership
+        env.storage()
+            .persistent()
+            .remove(&format_basket_member_key(old_collateral_id));
+        env.storage().persistent().set(
+            &format_basket_member_key(new_member.collateral_id),
+            &basket_id,
+        );
+
+        basket.updated_at = env.ledger().timestamp();
+        env.storage().persistent().set(&basket_key, &basket);
+
+        env.events().publish(
+            (symbol_short!("bsk_subs"),),
+            (basket_id, old_collateral_id, new_member.collateral_id),
+        );
+
+        Ok(())
+    }
+
+    /// Dissolve an unlocked basket, releasing its members
+    ///
+    /// # Arguments
+    /// * `basket_id` - ID of basket
+    pub fn dissolve_basket(env: Env, basket_id: u64) -> Result<(), ContractError> {
+        let basket_key = format_basket_key(basket_id);
+        let basket: CollateralBasket = env
+            .storage()
+            .persistent()
+            .get(&basket_key)
+            .ok_or(ContractError::BasketNotFound)?;
+
+        // Verify owner
+        basket.owner.require_auth();
+
+        if basket.locked {
+            return Err(ContractError::CollateralLocked);
+        }
+
+        for member in basket.members.iter() {
+            env.storage()
+                .persistent()
+                .remove(&format_basket_member_key(member.collateral_id));
+        }
+        env.storage().persistent().remove(&basket_key);
+
+        env.events()
+            .publish((symbol_short!("bsk_dis"),), (basket_id, basket.owner));
+
+        Ok(())
+    }
+
+    /// Get basket details
+    pub fn get_basket(env: Env, basket_id: u64) -> Result<CollateralBasket, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&format_basket_key(basket_id))
+            .ok_or(ContractError::BasketNotFound)
+    }
+
+    /// Get the aggregate, haircut-adjusted valuation of a basket
+    ///
+    /// # Arguments
+    /// * `basket_id` - ID of basket
+    ///
+    /// # Returns
+    /// Gross and adjusted basket valuation
+    pub fn get_basket_valuation(
+        env: Env,
+        basket_id: u64,
+    ) -> Result<BasketValuation, ContractError> {
+        let basket: CollateralBasket = env
+            .storage()
+            .persistent()
+            .get(&format_basket_key(basket_id))
+            .ok_or(ContractError::BasketNotFound)?;
+
+        basket_valuation(&env, &basket)
+    }
+
+    /// Get the basket a collateral entry belongs to, if any
+    pub fn get_collateral_basket(env: Env, collateral_id: u64) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&format_basket_member_key(collateral_id))
+    }
+
+    /// Attach an insurance policy to collateral
+    ///
+    /// Replaces any existing policy. The policy counts as cover only once the
+    /// insurer confirms it, and a lapse flag raised on the old policy stays
+    /// raised until then. Replacing the policy on locked collateral also needs
+    /// the secured party's authorization.
+    ///
+    /// # Arguments
+    /// * `collateral_id` - ID of collateral
+    /// * `insurer` - Insurer address
+    /// * `policy_hash` - Hash of the policy document
+    /// * `coverage_amount` - Insured amount
+    /// * `beneficiary` - Loss payee under the policy
+    /// * `expiry` - Policy expiry timestamp
+    ///
+    /// # Events
+    /// Emits `InsuranceRegistered` event
+    pub fn register_insurance(
+        env: Env,
+        collateral_id: u64,
+        insurer: Address,
+        policy_hash: BytesN<32>,
+        coverage_amount: i128,
+        beneficiary: Address,
+        expiry: u64,
+    ) -> Result<(), ContractError> {
+        let collateral: Collateral = env
+            .storage()
+            .persistent()
+            .get(&format_collateral_storage_key(collateral_id))
+            .ok_or(ContractError::CollateralNotFound)?;
+
+        // Verify owner
+        collateral.owner.require_auth();
+
+        if coverage_amount <= 0 || expiry <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidInsurance);
+        }
+
+        // A lender relying on the existing cover must agree to replacing it
+        if collateral.locked
+            && env
+                .storage()
+                .persistent()
+                .has(&format_insurance_key(collateral_id))
+        {
+            secured_party_of(&env, collateral_id)
+                .ok_or(ContractError::CollateralLocked)?
+                .require_auth();
+        }
+
+        // Record any lapse of the policy being replaced
+        refresh_insurance_flag(&env, &collateral);
+
+        let policy = InsurancePolicy {
+            collateral_id,
+            insurer: insurer.clone(),
+            policy_hash,
+            coverage_amount,
+            beneficiary,
+            expiry,
+            state: PolicyState::Pending,
+            registered_at: env.ledger().timestamp(),
+            updated_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&format_insurance_key(collateral_id), &policy);
+
+        env.events().publish(
+            (symbol_short!("ins_reg"),),
+            (collateral_id, insurer, coverage_amount, expiry),
+        );
+
+        refresh_insurance_flag(&env, &collateral);
+
+        Ok(())
+    }
+
+    /// Confirm cover for a pending policy (insurer only)
+    ///
+    /// # Arguments
+    /// * `collateral_id` - ID of collateral
+    ///
+    /// # Events
+    /// Emits `InsuranceConfirmed` event
+    pub fn confirm_insurance(env: Env, collateral_id: u64) -> Result<(), ContractError> {
+        set_policy_state(&env, collateral_id, PolicyState::Confirmed)
+    }
+
+    /// Cancel cover (insurer only)
+    ///
+    /// Cancelling a confirmed policy flags the collateral immediately.
+    ///
+    /// # Arguments
+    /// * `collateral_id` - ID of collateral
+    ///
+    /// # Events
+    /// Emits `InsuranceCancelled` event
+    pub fn cancel_insurance(env: Env, collateral_id: u64) -> Result<(), ContractError> {
+        set_policy_state(&env, collateral_id, PolicyState::Cancelled)
+    }
+
+    /// Set the minimum coverage, as a share of current valuation, a policy must provide
+    ///
+    /// # Arguments
+    /// * `coverage_bps` - Required coverage in basis points of valuation
+    pub fn set_min_insurance_coverage(env: Env, coverage_bps: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("min_cov"), &coverage_bps);
+
+        Ok(())
+    }
+
+    /// Re-evaluate a collateral's insurance and flag it if cover lapsed
+    ///
+    /// Permissionless, so keepers can flag expired policies as soon as they lapse.
+    ///
+    /// # Arguments
+    /// * `collateral_id` - ID of collateral
+    ///
+    /// # Returns
+    /// Current insurance status
+    ///
+    /// # Events
+    /// Emits `InsuranceFlagged` or `InsuranceRestored` when the flag changes
+    pub fn check_insurance(
+        env: Env,
+        collateral_id: u64,
+    ) -> Result<collateral::InsuranceStatus, ContractError> {
+        let collateral: Collateral = env
+            .storage()
+            .persistent()
+            .get(&format_collateral_storage_key(collateral_id))
+            .ok_or(ContractError::CollateralNotFound)?;
+
+        Ok(refresh_insurance_flag(&env, &collateral))
+    }
+
+    /// Get the insurance policy attached to collateral
+    pub fn get_insurance(env: Env, collateral_id: u64) -> Result<InsurancePolicy, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&format_insurance_key(collateral_id))
+            .ok_or(ContractError::InsuranceNotFound)
+    }
+
+    /// Get the live insurance status of collateral
+    pub fn get_insurance_status(
+        env: Env,
+        collateral_id: u64,
+    ) -> Result<collateral::InsuranceStatus, ContractError> {
+        let collateral: Collateral = env
+            .storage()
+            .persistent()
+            .get(&format_collateral_storage_key(collateral_id))
+            .ok_or(ContractError::CollateralNotFound)?;
+
+        Ok(insurance_status(&env, &collateral).0)
+    }
+
+    /// Whether collateral's insurance has lapsed, been cancelled or is under-covered
+    ///
+    /// Evaluated live, so an expired policy reads as flagged even before
+    /// `check_insurance` records it.
+    pub fn is_insurance_flagged(env: Env, collateral_id: u64) -> bool {
+        match env
+            .storage()
+            .persistent()
+            .get::<_, Collateral>(&format_collateral_storage_key(collateral_id))
+        {
+            Some(collateral) => insurance_status(&env, &collateral).1,
+            None => false,
+        }
+    }
+
+    /// Approve a custodian to issue warehouse receipts
+    ///
+    /// # Arguments
+    /// * `custodian` - Warehouse operator address
+    pub fn approve_custodian(env: Env, custodian: Address) -> Result<(), ContractError> {
+        set_custodian_approval(&env, custodian, true)
+    }
+
+    /// Revoke a custodian's approval; receipts already issued remain valid
+    ///
+    /// # Arguments
+    /// * `custodian` - Warehouse operator address
+    pub fn revoke_custodian(env: Env, custodian: Address) -> Result<(), ContractError> {
+        set_custodian_approval(&env, custodian, false)
+    }
+
+    /// Check whether a custodian is approved
+    pub fn is_custodian(env: Env, custodian: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&format_custodian_key(&custodian))
+            .unwrap_or(false)
+    }
+
+    /// Issue a warehouse receipt, registering it as verified commodity collateral
+    ///
+    /// # Arguments
+    /// * `custodian` - Approved custodian holding the goods
+    /// * `owner` - Depositor who owns the receipt
+    /// * `receipt_hash` - Hash of the receipt document
+    /// * `commodity_type` - Commodity category
+    /// * `grade` - Commodity grade
+    /// * `quantity` - Quantity held, in `unit`
+    /// * `unit` - Unit of measure
+    /// * `location` - Warehouse location
+    /// * `price_per_unit` - Valuation per unit
+    ///
+    /// # Returns
+    /// The collateral ID backing the receipt
+    ///
+    /// # Events
+    /// Emits `ReceiptIssued` event
+    pub fn issue_warehouse_receipt(
+        env: Env,
+        custodian: Address,
+        owner: Address,
+        receipt_hash: BytesN<32>,
+        commodity_type: CommodityType,
+        grade: String,
+        quantity: u64,
+        unit: String,
+        location: String,
+        price_per_unit: i128,
+    ) -> Result<u64, ContractError> {
+        custodian.require_auth();
+
+        if !Self::is_custodian(env.clone(), custodian.clone()) {
+            return Err(ContractError::CustodianNotApproved);
+        }
+        if quantity == 0 {
+            return Err(ContractError::InvalidQuantity);
+        }
+
+        let valuation = price_per_unit
+            .checked_mul(quantity as i128)
+            .ok_or(ContractError::InvalidValuation)?;
+        let collateral_id = store_new_collateral(
+            &env,
+            owner.clone(),
+            receipt_hash.clone(),
+            location.clone(),
+            AssetType::Commodities,
+            valuation,
+        )?;
+
+        // The custodian's attestation stands in for admin verification
+        let storage_key = format_collateral_storage_key(collateral_id);
+        let mut collateral: Collateral = env.storage().persistent().get(&storage_key).unwrap();
+        collateral.verification_status = collateral::VerificationStatus::Verified;
+        collateral.verified_by = Some(custodian.clone());
+        collateral.verified_at = env.ledger().timestamp();
+        env.storage().persistent().set(&storage_key, &collateral);
+
+        let receipt = WarehouseReceipt {
+            collateral_id,
+            custodian: custodian.clone(),
+            receipt_hash,
+            commodity: CommoditiesClassification {
+                collateral_id,
+                commodity_type,
+                quantity,
+                unit,
+                market_price: price_per_unit,
+            },
+            grade,
+            location,
+            original_quantity: quantity,
+            issued_at: env.ledger().timestamp(),
+            updated_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&format_receipt_key(collateral_id), &receipt);
+
+        env.events().publish(
+            (symbol_short!("rcpt_iss"),),
+            (collateral_id, custodian, owner, quantity),
+        );
+
+        Ok(collateral_id)
+    }
+
+    /// Withdraw part of the commodity held under a receipt
+    ///
+    /// Requires the owner and custodian, plus the secured party while the
+    /// receipt is locked. Valuation is reduced in proportion to the quantity
+    /// released; releasing everything deactivates the collateral.
+    ///
+    /// # Arguments
+    /// * `collateral_id` - ID of the receipt's collateral
+    /// * `quantity` - Quantity to release
+    ///
+    /// # Events
+    /// Emits `ReceiptReleased` event
+    pub fn release_from_receipt(
+        env: Env,
+        collateral_id: u64,
+        quantity: u64,
+    ) -> Result<(), ContractError> {
+        let receipt_key = format_receipt_key(collateral_id);
+        let mut receipt: WarehouseReceipt = env
+            .storage()
+            .persistent()
+            .get(&receipt_key)
+            .ok_or(ContractError::ReceiptNotFound)?;
+
+        let storage_key = format_collateral_storage_key(collateral_id);
+        let mut collateral: Collateral = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::CollateralNotFound)?;
+
+        collateral.owner.require_auth();
+        receipt.custodian.require_auth();
+
+        // Lender consent while pledged
+        let approved_by = if collateral.locked {
+            let secured_party: Address = env
+                .storage()
+                .persistent()
+                .get(&format_secured_party_key(collateral_id))
+                .ok_or(ContractError::CollateralLocked)?;
+            secured_party.require_auth();
+            secured_party
+        } else {
+            collateral.owner.clone()
+        };
+
+        let held = receipt.commodity.quantity;
+        if quantity == 0 || quantity > held {
+            return Err(ContractError::InvalidQuantity);
+        }
+        let remaining = held - quantity;
+
+        // Reduce valuation pro rata
+        let old_valuation = collateral.current_valuation;
+        collateral.previous_valuation = old_valuation;
+        collateral.current_valuation = old_valuation * remaining as i128 / held as i128;
+        // A pro-rata reduction is not a fresh appraisal, so the valuation keeps its age
+        collateral.updated_at = env.ledger().timestamp();
+        if remaining == 0 {
+            collateral.status = CollateralStatus::Inactive;
+        }
+        env.storage().persistent().set(&storage_key, &collateral);
+
+        let valuation_record = ValuationRecord {
+            collateral_id,
+            old_valuation,
+            new_valuation: collateral.current_valuation,
+            oracle: receipt.custodian.clone(),
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(
+            &format_valuation_history_key(collateral_id, env.ledger().timestamp()),
+            &valuation_record,
+        );
+
+        receipt.commodity.quantity = remaining;
+        receipt.updated_at = env.ledger().timestamp();
+        env.storage().persistent().set(&receipt_key, &receipt);
+
+        let release = ReceiptRelease {
+            collateral_id,
+            quantity,
+            remaining_quantity: remaining,
+            approved_by,
+            released_at: env.ledger().timestamp(),
+        };
+        env.events().publish((symbol_short!("rcpt_rel"),), release);
+
+        refresh_insurance_flag(&env, &collateral);
+
+        Ok(())
+    }
+
+    /// Get the warehouse receipt backing a collateral entry
+    pub fn get_warehouse_receipt(
+        env: Env,
+        collateral_id: u64,
+    ) -> Result<WarehouseReceipt, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&format_receipt_key(collateral_id))
+            .ok_or(ContractError::ReceiptNotFound)
+    }
+
+    /// Set the governance address allowed to configure haircut rules (admin only)
+    ///
+    /// # Arguments
+    /// * `governance` - Governance contract address
+    pub fn set_governance(env: Env, governance: Address) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("gov"), &governance);
+
+        Ok(())
+    }
+
+    /// Get governance address
+    pub fn get_governance(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("gov"))
+    }
+
+    /// Configure the haircut and eligibility for an asset class and risk rating
+    /// (governance only)
+    ///
+    /// # Arguments
+    /// * `asset_class` - Primary asset class from the classification
+    /// * `risk_rating` - Risk rating from the classification
+    /// * `haircut_bps` - Valuation haircut in basis points (0-10000)
+    /// * `eligible` - Whether such collateral may secure loans
+    ///
+    /// # Events
+    /// Emits `HaircutRuleSet` event
+    pub fn set_haircut_rule(
+        env: Env,
+        asset_class: AssetClass,
+        risk_rating: RiskRating,
+        haircut_bps: u32,
+        eligible: bool,
+    ) -> Result<(), ContractError> {
+        let governance: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("gov"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        governance.require_auth();
+
+        if haircut_bps > 10000 {
+            return Err(ContractError::InvalidValuation);
+        }
+
+        let rule = HaircutRule {
+            haircut_bps,
+            eligible,
+        };
+        env.storage()
+            .persistent()
+            .set(&format_haircut_rule_key(asset_class, risk_rating), &rule);
+
+        env.events().publish(
+            (symbol_short!("hc_rule"),),
+            (asset_class, risk_rating, haircut_bps, eligible),
+        );
+
+        Ok(())
+    }
+
+    /// Get the haircut rule for an asset class and risk rating, if configured
+    pub fn get_haircut_rule(
+        env: Env,
+        asset_class: AssetClass,
+        risk_rating: RiskRating,
+    ) -> Option<HaircutRule> {
+        env.storage()
+            .persistent()
+            .get(&format_haircut_rule_key(asset_class, risk_rating))
+    }
+
+    /// Get the value collateral can secure: current valuation minus haircut
+    ///
+    /// The haircut comes from the rule for the collateral's classification, or
+    /// the per-asset-type haircut if it is unclassified or no rule is set.
+    ///
+    /// # Arguments
+    /// * `collateral_id` - ID of collateral
+    ///
+    /// # Returns
+    /// Lendable value, or `CollateralIneligible` if the rule excludes it
+    pub fn get_lendable_value(env: Env, collateral_id: u64) -> Result<i128, ContractError> {
+        Self::get_collateral_valuation(env, collateral_id).map(|v| v.lendable_value)
+    }
+
+    /// Get current valuation, lendable value, valuation age and verification state
+    ///
+    /// # Arguments
+    /// * `collateral_id` - ID of collateral
+    ///
+    /// # Returns
+    /// Valuation snapshot, or `CollateralIneligible` if the haircut rule excludes it
+    pub fn get_collateral_valuation(
+        env: Env,
+        collateral_id: u64,
+    ) -> Result<CollateralValuation, ContractError> {
+        let collateral: Collateral = env
+            .storage()
+            .persistent()
+            .get(&format_collateral_storage_key(collateral_id))
+            .ok_or(ContractError::CollateralNotFound)?;
+
+        let haircut_bps = collateral_haircut(&env, &collateral)?;
+
+        Ok(CollateralValuation {
+            collateral_id,
+            valuation: collateral.current_valuation,
+            lendable_value: collateral.current_valuation * (10000 - haircut_bps) as i128 / 10000,
+            valuation_timestamp: collateral.valuation_timestamp,
+            verified: collateral.verification_status == collateral::VerificationStatus::Verified,
+        })
+    }
+
+    /// Get the asset type of collateral
+    pub fn get_asset_type(env: Env, collateral_id: u64) -> Result<AssetType, ContractError> {
+        let collateral: Collateral = env
+            .storage()
+            .persistent()
+            .get(&format_collateral_storage_key(collateral_id))
+            .ok_or(ContractError::CollateralNotFound)?;
+
+        Ok(collateral.asset_type)
+    }
+}
+
+// Helper functions
+
+/// Validate and store a new collateral record, returning its ID.
+fn store_new_collateral(
+    env: &Env,
+    owner: Address,
+    asset_hash: BytesN<32>,
+    metadata_uri: String,
+    asset_type: AssetType,
+    initial_valuation: i128,
+) -> Result<u64, ContractError> {
+    // Validate inputs
+    if initial_valuation <= 0 {
+        return Err(ContractError::InvalidValuation);
+    }
+
+    // Check for duplicate asset hash
+    let hash_key = format_asset_hash_key(&asset_hash);
+    if env.storage().persistent().has(&hash_key) {
+        return Err(ContractError::DuplicateCollateral);
+    }
+
+    // Get next collateral ID
+    let collateral_id: u64 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("next_id"))
+        .unwrap_or(1u64);
+
+    // Create collateral record
+    let collateral = Collateral {
+        id: collateral_id,
+        owner: owner.clone(),
+        asset_hash,
+        metadata_uri: metadata_uri.clone(),
+        asset_type,
+        current_valuation: initial_valuation,
+        previous_valuation: 0,
+        valuation_timestamp: env.ledger().timestamp(),
+        status: CollateralStatus::Active,
+        locked: false,
+        locked_by_escrow: 0,
+        verification_status: collateral::VerificationStatus::Pending,
+        verified_by: None,
+        verified_at: 0,
+        created_at: env.ledger().timestamp(),
+        updated_at: env.ledger().timestamp(),
+        expiry_date: 0,
+        fractionalized: false,
+        fraction_count: 0,
+    };
+
+    // Store collateral
+    let storage_key = format_collateral_storage_key(collateral_id);
+    env.storage().persistent().set(&storage_key, &collateral);
+
+    // Store asset hash mapping
+    env.storage().persistent().set(&hash_key, &collateral_id);
+
+    // Store owner mapping
+    let owner_key = format_owner_collateral_key(&owner, collateral_id);
+    env.storage().persistent().set(&owner_key, &collateral_id);
+
+    // Update next ID
+    env.storage()
+        .instance()
+        .set(&symbol_short!("next_id"), &(collateral_id + 1));
+
+    // Emit event
+    env.events().publish(
+        (symbol_short!("col_regd"),),
+        (collateral_id, owner, initial_valuation),
+    );
+
+    Ok(collateral_id)
+}
+
+/// Lock collateral for an escrow, optionally recording the secured party.
+fn lock_for(
+    env: &Env,
+    collateral_id: u64,
+    escrow_id: u64,
+    secured_party: Option<Address>,
+) -> Result<(), ContractError> {
+    // Get collateral
+    let storage_key = format_collateral_storage_key(collateral_id);
+    let mut collateral: Collateral = env
+        .storage()
+        .persistent()
+        .get(&storage_key)
+        .ok_or(ContractError::CollateralNotFound)?;
+
+    // Verify owner
+    collateral.owner.require_auth();
+
+    // Check if already locked
+    if collateral.locked {
+        return Err(ContractError::CollateralLocked);
+    }
+
+    // Lock collateral
+    collateral.locked = true;
+    collateral.locked_by_escrow = escrow_id;
+    collateral.updated_at = env.ledger().timestamp();
+
+    // Store updated collateral
+    env.storage().persistent().set(&storage_key, &collateral);
+
+    // Record locking
+    let lock_record = CollateralLock {
+        collateral_id,
+        escrow_id,
+        locked_at: env.ledger().timestamp(),
+        locked_by: collateral.owner.clone(),
+    };
+
+    let lock_key = format_lock_history_key(collateral_id, env.ledger().timestamp());
+    env.storage().persistent().set(&lock_key, &lock_record);
+
+    if let Some(secured_party) = secured_party {
+        env.storage()
+            .persistent()
+            .set(&format_secured_party_key(collateral_id), &secured_party);
+    }
+
+    // Emit event
+    env.events()
+        .publish((symbol_short!("col_lock"),), (collateral_id, escrow_id));
+
+    Ok(())
+}
+
+/// Move collateral ownership, updating owner mappings and transfer history.
+fn apply_ownership_transfer(env: &Env, collateral: &mut Collateral, new_owner: Address) {
+    let collateral_id = collateral.id;
+    let previous_owner = collateral.owner.clone();
+    collateral.owner = new_owner.clone();
+    collateral.updated_at = env.ledger().timestamp();
+
+    // Store updated collateral
+    let storage_key = format_collateral_storage_key(collateral_id);
+    env.storage().persistent().set(&storage_key, collateral);
+
+    // Update owner mappings
+    let old_owner_key = format_owner_collateral_key(&previous_owner, collateral_id);
+    env.storage().persistent().remove(&old_owner_key);
+
+    let new_owner_key = format_owner_collateral_key(&new_owner, collateral_id);
+    env.storage()
+        .persistent()
+        .set(&new_owner_key, &collateral_id);
+
+    // Record transfer
+    let transfer_record = OwnershipTransfer {
+        collateral_id,
+        from: previous_owner.clone(),
+        to: new_owner.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+
+    let transfer_key = format_transfer_history_key(collateral_id, env.ledger().timestamp());
+    env.storage()
+        .persistent()
+        .set(&transfer_key, &transfer_record);
+
+    // Emit event
+    env.events().publish(
+        (symbol_short!("col_xfer"),),
+        (collateral_id, previous_owner, new_owner),
+    );
+}
+
+/// Check that a collateral entry can join a basket owned by `owner`.
+fn validate_basket_member(
+    env: &Env,
+    owner: &Address,
+    member: &BasketMember,
+) -> Result<(), ContractError> {
+    if member.share_bps == 0 || member.share_bps > 10000 {
+        return Err(ContractError::InvalidBasket);
+    }
+
+    let collateral = read_lockable_member(env, owner, member.collateral_id)?;
+    if collateral.locked {
+        return Err(ContractError::CollateralLocked);
+    }
+
+    if env
+        .storage()
+        .persistent()
+        .has(&format_basket_member_key(member.collateral_id))
+    {
+        return Err(ContractError::CollateralInBasket);
+    }
+
+    Ok(())
+}
+
+/// Secured party holding a lock on collateral, directly or through its basket.
+fn secured_party_of(env: &Env, collateral_id: u64) -> Option<Address> {
+    if let Some(secured_party) = env
+        .storage()
+        .persistent()
+        .get(&format_secured_party_key(collateral_id))
+    {
+        return Some(secured_party);
+    }
+
+    let basket_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&format_basket_member_key(collateral_id))?;
+    env.storage()
+        .persistent()
+        .get(&format_basket_secured_party_key(basket_id))
+}
+
+/// Load a basket member, checking it is still active and held by the basket owner.
+fn read_lockable_member(
+    env: &Env,
+    owner: &Address,
+    collateral_id: u64,
+) -> Result<Collateral, ContractError> {
+    let collateral: Collateral = env
+        .storage()
+        .persistent()
+        .get(&format_collateral_storage_key(collateral_id))
+        .ok_or(ContractError::CollateralNotFound)?;
+
+    if collateral.owner != *owner {
+        return Err(ContractError::UnauthorizedTransfer);
+    }
+    if collateral.status != CollateralStatus::Active {
+        return Err(ContractError::InvalidCollateralData);
+    }
+
+    Ok(collateral)
+}
+
+/// Lock every member of a basket, optionally recording a secured party.
+fn lock_basket_with(
+    env: &Env,
+    basket_id: u64,
+    escrow_id: u64,
+    secured_party: Option<Address>,
+) -> Result<(), ContractError> {
+    let basket_key = format_basket_key(basket_id);
+    let mut basket: CollateralBasket = env
+        .storage()
+        .persistent()
+        .get(&basket_key)
+        .ok_or(ContractError::BasketNotFound)?;
+
+    // Verify owner
+    basket.owner.require_auth();
+
+    if basket.locked {
+        return Err(ContractError::CollateralLocked);
+    }
+
+    // Check every member before locking any
+    for member in basket.members.iter() {
+        let collateral = read_lockable_member(env, &basket.owner, member.collateral_id)?;
+        if collateral.locked {
+            return Err(ContractError::CollateralLocked);
+        }
+    }
+
+    for member in basket.members.iter() {
+        lock_basket_member(env, &basket.owner, member.collateral_id, escrow_id);
+    }
+
+    basket.locked = true;
+    basket.locked_by_escrow = escrow_id;
+    basket.updated_at = env.ledger().timestamp();
+    env.storage().persistent().set(&basket_key, &basket);
+
+    if let Some(secured_party) = secured_party {
+        env.storage()
+            .persistent()
+            .set(&format_basket_secured_party_key(basket_id), &secured_party);
+    }
+
+    env.events()
+        .publish((symbol_short!("bsk_lock"),), (basket_id, escrow_id));
+
+    Ok(())
+}
+
+fn lock_basket_member(env: &Env, owner: &Address, collateral_id: u64, escrow_id: u64) {
+    let storage_key = format_collateral_storage_key(collateral_id);
+    let mut collateral: Collateral = env.storage().persistent().get(&storage_key).unwrap();
+
+    collateral.locked = true;
+    collateral.locked_by_escrow = escrow_id;
+    collateral.updated_at = env.ledger().timestamp();
+    env.storage().persistent().set(&storage_key, &collateral);
+
+    // Record locking
+    let lock_record = CollateralLock {
+        collateral_id,
+        escrow_id,
+        locked_at: env.ledger().timestamp(),
+        locked_by: owner.clone(),
+    };
+    let lock_key = format_lock_history_key(collateral_id, env.ledger().timestamp());
+    env.storage().persistent().set(&lock_key, &lock_record);
+
+    env.events()
+        .publish((symbol_short!("col_lock"),), (collateral_id, escrow_id));
+}
+
+fn unlock_basket_member(env: &Env, collateral_id: u64) -> Result<(), ContractError> {
+    let storage_key = format_collateral_storage_key(collateral_id);
+    let mut collateral: Collateral = env
+        .storage()
+        .persistent()
+        .get(&storage_key)
+        .ok_or(ContractError::CollateralNotFound)?;
+
+    let escrow_id = collateral.locked_by_escrow;
+    collateral.locked = false;
+    collateral.locked_by_escrow = 0;
+    collateral.updated_at = env.ledger().timestamp();
+    env.storage().persistent().set(&storage_key, &collateral);
+
+    env.events()
+        .publish((symbol_short!("col_unlk"),), (collateral_id, escrow_id));
+
+    Ok(())
+}
+
+/// Resolve the haircut for collateral from its classification rule, falling
+/// back to the asset-type haircut.
+fn collateral_haircut(env: &Env, collateral: &Collateral) -> Result<u32, ContractError> {
+    let classification: Option<AssetClassification> = env
+        .storage()
+        .persistent()
+        .get(&format_classification_key(collateral.id));
+
+    if let Some(classification) = classification {
+        let rule: Option<HaircutRule> = env.storage().persistent().get(&format_haircut_rule_key(
+            classification.primary_class,
+            classification.risk_rating,
+        ));
+        if let Some(rule) = rule {
+            if !rule.eligible {
+                return Err(ContractError::CollateralIneligible);
+            }
+            return Ok(rule.haircut_bps);
+        }
+    }
+
+    Ok(env
+        .storage()
+        .instance()
+        .get(&format_haircut_key(collateral.asset_type))
+        .unwrap_or(0))
+}
+
+/// Sum the pledged share of each member at current valuation, before and after haircuts.
+fn basket_valuation(
+    env: &Env,
+    basket: &CollateralBasket,
+) -> Result<BasketValuation, ContractError> {
+    let mut gross_value: i128 = 0;
+    let mut adjusted_value: i128 = 0;
+    let mut verified = true;
+    let mut oldest_valuation = u64::MAX;
+
+    for member in basket.members.iter() {
+        let collateral: Collateral = env
+            .storage()
+            .persistent()
+            .get(&format_collateral_storage_key(member.collateral_id))
+            .ok_or(ContractError::CollateralNotFound)?;
+
+        let haircut_bps = collateral_haircut(env, &collateral)?;
+
+        let pledged = collateral
+            .current_valuation
+            .checked_mul(member.share_bps as i128)
+            .ok_or(ContractError::InvalidValuation)?
+            / 10000;
+        let adjusted = pledged
+            .checked_mul((10000 - haircut_bps) as i128)
+            .ok_or(ContractError::InvalidValuation)?
+            / 10000;
+        gross_value = gross_value
+            .checked_add(pledged)
+            .ok_or(ContractError::InvalidValuation)?;
+        adjusted_value = adjusted_value
+            .checked_add(adjusted)
+            .ok_or(ContractError::InvalidValuation)?;
+        verified &= collateral.verification_status == collateral::VerificationStatus::Verified;
+        oldest_valuation = oldest_valuation.min(collateral.valuation_timestamp);
+    }
+
+    Ok(BasketValuation {
+        basket_id: basket.id,
+        gross_value,
+        adjusted_value,
+        verified,
+        oldest_valuation,
+    })
+}
+
+/// Move a policy to a new state on behalf of its insurer.
+fn set_policy_state(
+    env: &Env,
+    collateral_id: u64,
+    state: PolicyState,
+) -> Result<(), ContractError> {
+    let insurance_key = format_insurance_key(collateral_id);
+    let mut policy: InsurancePolicy = env
+        .storage()
+        .persistent()
+        .get(&insurance_key)
+        .ok_or(ContractError::InsuranceNotFound)?;
+
+    // Verify insurer
+    policy.insurer.require_auth();
+
+    let allowed = match state {
+        PolicyState::Confirmed => policy.state == PolicyState::Pending,
+        PolicyState::Cancelled => policy.state != PolicyState::Cancelled,
+        PolicyState::Pending => false,
+    };
+    if !allowed {
+        return Err(ContractError::InvalidInsurance);
+    }
+
+    policy.state = state;
+    policy.updated_at = env.ledger().timestamp();
+    env.storage().persistent().set(&insurance_key, &policy);
+
+    let topic = match state {
+        PolicyState::Confirmed => symbol_short!("ins_conf"),
+        _ => symbol_short!("ins_canc"),
+    };
+    env.events()
+        .publish((topic,), (collateral_id, policy.insurer.clone()));
+
+    let collateral: Collateral = env
+        .storage()
+        .persistent()
+        .get(&format_collateral_storage_key(collateral_id))
+        .ok_or(ContractError::CollateralNotFound)?;
+    refresh_insurance_flag(env, &collateral);
+
+    Ok(())
+}
+
+/// Evaluate a collateral's insurance, returning its status and whether it is flagged.
+///
+/// Collateral without a policy, or with one still awaiting confirmation, is
+/// uninsured; a policy that was confirmed and then cancelled, expired or fell
+/// below the required coverage is flagged. A pending replacement keeps any
+/// flag already raised until it is confirmed.
+fn insurance_status(env: &Env, collateral: &Collateral) -> (collateral::InsuranceStatus, bool) {
+    let policy: InsurancePolicy = match env
+        .storage()
+        .persistent()
+        .get(&format_insurance_key(collateral.id))
+    {
+        Some(policy) => policy,
+        None => return (collateral::InsuranceStatus::Uninsured, false),
+    };
+
+    match policy.state {
+        PolicyState::Pending => {
+            let flagged: bool = env
+                .storage()
+                .persistent()
+                .get(&format_insurance_flag_key(collateral.id))
+                .unwrap_or(false);
+            (collateral::InsuranceStatus::Uninsured, flagged)
+        }
+        PolicyState::Cancelled => (collateral::InsuranceStatus::Uninsured, true),
+        PolicyState::Confirmed => {
+            if policy.expiry <= env.ledger().timestamp() {
+                return (collateral::InsuranceStatus::Expired, true);
+            }
+
+            let min_coverage_bps: u32 = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("min_cov"))
+                .unwrap_or(10000);
+            let required = collateral.current_valuation * min_coverage_bps as i128 / 10000;
+
+            if policy.coverage_amount < required {
+                (collateral::InsuranceStatus::PartiallyInsured, true)
+            } else {
+                (collateral::InsuranceStatus::Insured, false)
+            }
+        }
+    }
+}
+
+/// Maximum age of a fresh valuation, in seconds.
+fn max_valuation_age(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("max_vage"))
+        .unwrap_or(DEFAULT_MAX_VALUATION_AGE)
+}
+
+/// Persist the insurance flag and emit an event when it changes.
+fn refresh_insurance_flag(env: &Env, collateral: &Collateral) -> collateral::InsuranceStatus {
+    let (status, flagged) = insurance_status(env, collateral);
+
+    let flag_key = format_insurance_flag_key(collateral.id);
+    let was_flagged: bool = env.storage().persistent().get(&flag_key).unwrap_or(false);
+
+    if flagged != was_flagged {
+        env.storage().persistent().set(&flag_key, &flagged);
+
+        let topic = if flagged {
+            symbol_short!("ins_flag")
+        } else {
+            symbol_short!("ins_ok")
+        };
+        env.events().publish((topic,), (collateral.id, status));
+    }
+
+    status
+}
+
+fn set_custodian_approval(
+    env: &Env,
+    custodian: Address,
+    approved: bool,
+) -> Result<(), ContractError> {
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("admin"))
+        .ok_or(ContractError::Unauthorized)?;
+
+    admin.require_auth();
+
+    env.storage()
+        .persistent()
+        .set(&format_custodian_key(&custodian), &approved);
+
+    env.events()
+        .publish((symbol_short!("custodn"),), (custodian, approved));
+
+    Ok(())
+}
+
+fn format_collateral_storage_key(collateral_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("collat"), collateral_id)
+}
+
+fn format_asset_hash_key(asset_hash: &BytesN<32>) -> (soroban_sdk::Symbol, BytesN<32>) {
+    (symbol_short!("hash"), asset_hash.clone())
+}
+
+fn format_owner_collateral_key(
+    owner: &Address,
+    collateral_id: u64,
+) -> (soroban_sdk::Symbol, Address, u64) {
+    (symbol_short!("owner"), owner.clone(), collateral_id)
+}
+
+fn format_valuation_history_key(
+    collateral_id: u64,
+    timestamp: u64,
+) -> (soroban_sdk::Symbol, u64, u64) {
+    (symbol_short!("val"), collateral_id, timestamp)
+}
+
+fn format_transfer_history_key(
+    collateral_id: u64,
+    timestamp: u64,
+) -> (soroban_sdk::Symbol, u64, u64) {
+    (symbol_short!("trans"), collateral_id, timestamp)
+}
+
+fn format_lock_history_key(collateral_id: u64, timestamp: u64) -> (soroban_sdk::Symbol, u64, u64) {
+    (symbol_short!("lock"), collateral_id, timestamp)
+}
+
+fn format_verification_history_key(collateral_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("verif"), collateral_id)
+}
+
+fn format_classification_key(collateral_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("class"), collateral_id)
+}
+
+fn format_token_binding_key(collateral_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("token"), collateral_id)
+}
+
+fn format_basket_key(basket_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("basket"), basket_id)
+}
+
+fn format_basket_member_key(collateral_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("bskmem"), collateral_id)
+}
+
+fn format_haircut_key(asset_type: AssetType) -> (soroban_sdk::Symbol, u32) {
+    (symbol_short!("haircut"), asset_type as u32)
+}
+
+fn format_insurance_key(collateral_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("insure"), collateral_id)
+}
+
+fn format_insurance_flag_key(collateral_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("insflag"), collateral_id)
+}
+
+fn format_basket_secured_party_key(basket_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("bsk_sec"), basket_id)
+}
+
+fn format_secured_party_key(collateral_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("secured"), collateral_id)
+}
+
+fn format_liquidation_claim_key(
+    collateral_id: u64,
+    holder: &Address,
+) -> (soroban_sdk::Symbol, u64, Address) {
+    (symbol_short!("liq_clm"), collateral_id, holder.clone())
+}
+
+fn format_liquidated_share_key(collateral_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("liq_shr"), collateral_id)
+}
+
+fn format_custodian_key(custodian: &Address) -> (soroban_sdk::Symbol, Address) {
+    (symbol_short!("custod"), custodian.clone())
+}
+
+fn format_receipt_key(collateral_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("receipt"), collateral_id)
+}
+
+fn format_haircut_rule_key(
+    asset_class: AssetClass,
+    risk_rating: RiskRating,
+) -> (soroban_sdk::Symbol, u32, u32) {
+    (
+        symbol_short!("hc_rule"),
+        asset_class as u32,
+        risk_rating as u32,
+    )
 }
@@ -146,3 +146,13 @@ pub enum VerificationMethod {
     BlockchainVerification = 2,
     LegalReview = 3,
 }
+
+/// Fractional claim on collateral taken by a liquidator
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiquidationClaim {
+    pub collateral_id: u64,
+    pub holder: Address,
+    pub share_bps: u32,
+    pub updated_at: u64,
+}
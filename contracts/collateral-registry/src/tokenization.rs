@@ -0,0 +1,14 @@
+//! SEP-41 token representation of registered collateral
+
+use soroban_sdk::{contracttype, Address};
+
+/// Binding between a collateral entry and the token contract representing it
+#[contracttype]
+#[derive(Clone)]
+pub struct CollateralTokenBinding {
+    pub collateral_id: u64,
+    pub token: Address,
+    pub supply: i128,
+    pub tokenized_by: Address,
+    pub tokenized_at: u64,
+}
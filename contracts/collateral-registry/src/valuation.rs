@@ -2,6 +2,9 @@
 
 use soroban_sdk::{contracttype, Address, BytesN};
 
+/// Default age after which a valuation no longer counts as fresh (30 days)
+pub const DEFAULT_MAX_VALUATION_AGE: u64 = 86400 * 30;
+
 /// Valuation record
 #[contracttype]
 #[derive(Clone)]
@@ -148,3 +151,14 @@ pub enum InsuranceStatus {
     Expired = 2,
     Cancelled = 3,
 }
+
+/// Point-in-time valuation of a collateral entry, as consumed by lending contracts
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralValuation {
+    pub collateral_id: u64,
+    pub valuation: i128,
+    pub lendable_value: i128,
+    pub valuation_timestamp: u64,
+    pub verified: bool,
+}
@@ -0,0 +1,33 @@
+//! Electronic warehouse receipts for commodity collateral
+
+use crate::classification::CommoditiesClassification;
+use soroban_sdk::{contracttype, Address, BytesN, String};
+
+/// Warehouse receipt issued by an approved custodian
+///
+/// The receipt is backed by a collateral entry of type `Commodities`; the
+/// pledged quantity and per-unit price live in `commodity`.
+#[contracttype]
+#[derive(Clone)]
+pub struct WarehouseReceipt {
+    pub collateral_id: u64,
+    pub custodian: Address,
+    pub receipt_hash: BytesN<32>,
+    pub commodity: CommoditiesClassification,
+    pub grade: String,
+    pub location: String,
+    pub original_quantity: u64,
+    pub issued_at: u64,
+    pub updated_at: u64,
+}
+
+/// Partial release of commodity from a receipt
+#[contracttype]
+#[derive(Clone)]
+pub struct ReceiptRelease {
+    pub collateral_id: u64,
+    pub quantity: u64,
+    pub remaining_quantity: u64,
+    pub approved_by: Address,
+    pub released_at: u64,
+}
@@ -2,9 +2,24 @@
 
 use soroban_sdk::{
     testutils::{Address as _, BytesN as _, Ledger},
-    Address, BytesN, Env, String,
+    Address, BytesN, Env, String, Vec,
 };
 
+use collateral_registry::{
+    basket::BasketMember,
+    classification::{AssetClass, AssetClassification, CommodityType, HaircutRule, RiskRating},
+    collateral::{AssetType, CollateralStatus, InsuranceStatus},
+    insurance::PolicyState,
+    verification::{VerificationData, VerificationMethod},
+    CollateralRegistry, CollateralRegistryClient,
+};
+use collateral_token::CollateralTokenClient;
+
+/// `collateral-token` wasm deployed by `tokenize_collateral`. Rebuild with
+/// `cargo build -p collateral-token --target wasm32-unknown-unknown --release`
+/// and copy it here when the token contract changes.
+const COLLATERAL_TOKEN_WASM: &[u8] = include_bytes!("fixtures/collateral_token.wasm");
+
 // Note: These are test templates. Full implementations would require:
 // - Proper contract compilation and linking
 // - Mock oracle setup
@@ -600,3 +615,1136 @@ fn test_transfer_history_tracking() {
     // assert_eq!(history.len(), 2);
     // Verify all transfers are recorded
 }
+
+// Tokenization tests
+
+fn setup_registry(env: &Env) -> (CollateralRegistryClient<'_>, Address) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let oracle = Address::generate(env);
+    let registry_id = env.register(CollateralRegistry, ());
+    let registry = CollateralRegistryClient::new(env, &registry_id);
+    registry.initialize(&admin, &oracle);
+
+    (registry, admin)
+}
+
+fn register_verified(env: &Env, registry: &CollateralRegistryClient, owner: &Address) -> u64 {
+    let asset_hash = BytesN::<32>::random(env);
+    let collateral_id = registry.register_collateral(
+        owner,
+        &asset_hash,
+        &String::from_str(env, "ipfs://QmCollateral"),
+        &AssetType::Equipment,
+        &1_000_000,
+    );
+    registry.verify_collateral(
+        &collateral_id,
+        &VerificationData {
+            document_hash: asset_hash,
+            verification_method: VerificationMethod::DocumentReview,
+            additional_data: String::from_str(env, ""),
+        },
+    );
+    collateral_id
+}
+
+fn set_token_wasm(env: &Env, registry: &CollateralRegistryClient) {
+    let wasm_hash = env.deployer().upload_contract_wasm(COLLATERAL_TOKEN_WASM);
+    registry.set_token_wasm(&wasm_hash);
+}
+
+fn tokenize(
+    env: &Env,
+    registry: &CollateralRegistryClient,
+    collateral_id: u64,
+    supply: i128,
+) -> CollateralTokenClient<'static> {
+    set_token_wasm(env, registry);
+    let token_id = registry.tokenize_collateral(
+        &collateral_id,
+        &supply,
+        &String::from_str(env, "Collateral #1"),
+        &String::from_str(env, "SVC1"),
+    );
+    CollateralTokenClient::new(env, &token_id)
+}
+
+#[test]
+fn test_tokenize_whole_collateral_transfers_ownership() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let collateral_id = register_verified(&env, &registry, &owner);
+    let token = tokenize(&env, &registry, collateral_id, 1);
+
+    assert_eq!(token.balance(&owner), 1);
+    assert_eq!(token.decimals(), 0);
+    assert_eq!(
+        token.collateral(),
+        (registry.address.clone(), collateral_id)
+    );
+
+    let binding = registry.get_collateral_token(&collateral_id);
+    assert_eq!(binding.token, token.address);
+    assert_eq!(binding.supply, 1);
+
+    token.transfer(&owner, &buyer, &1);
+
+    assert_eq!(token.balance(&owner), 0);
+    assert_eq!(token.balance(&buyer), 1);
+    assert_eq!(registry.get_collateral(&collateral_id).owner, buyer);
+}
+
+#[test]
+fn test_tokenize_fractions_keeps_registry_owner() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let spender = Address::generate(&env);
+
+    let collateral_id = register_verified(&env, &registry, &owner);
+    let token = tokenize(&env, &registry, collateral_id, 100);
+
+    let collateral = registry.get_collateral(&collateral_id);
+    assert!(collateral.fractionalized);
+    assert_eq!(collateral.fraction_count, 100);
+
+    token.approve(&owner, &spender, &40, &1000);
+    token.transfer_from(&spender, &owner, &investor, &25);
+
+    assert_eq!(token.balance(&owner), 75);
+    assert_eq!(token.balance(&investor), 25);
+    assert_eq!(token.allowance(&owner, &spender), 15);
+    assert_eq!(registry.get_collateral(&collateral_id).owner, owner);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #9)")]
+fn test_token_transfer_rejected_while_locked() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let collateral_id = register_verified(&env, &registry, &owner);
+    let token = tokenize(&env, &registry, collateral_id, 1);
+    registry.lock_collateral(&collateral_id, &7);
+
+    token.transfer(&owner, &buyer, &1);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #28)")]
+fn test_tokenize_unverified_collateral_rejected() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+
+    let collateral_id = registry.register_collateral(
+        &owner,
+        &BytesN::<32>::random(&env),
+        &String::from_str(&env, "ipfs://QmCollateral"),
+        &AssetType::Equipment,
+        &1_000_000,
+    );
+    tokenize(&env, &registry, collateral_id, 1);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #26)")]
+fn test_direct_transfer_of_tokenized_collateral_rejected() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let collateral_id = register_verified(&env, &registry, &owner);
+    tokenize(&env, &registry, collateral_id, 1);
+    assert_eq!(
+        registry.get_collateral(&collateral_id).status,
+        CollateralStatus::Active
+    );
+
+    registry.transfer_collateral(&collateral_id, &buyer);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #26)")]
+fn test_tokenized_collateral_cannot_be_locked_for_lender() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+
+    let collateral_id = register_verified(&env, &registry, &owner);
+    tokenize(&env, &registry, collateral_id, 1_000);
+
+    registry.lock_collateral_for(&collateral_id, &9, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #9)")]
+fn test_tokenize_locked_collateral_rejected() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+
+    let collateral_id = register_verified(&env, &registry, &owner);
+    registry.lock_collateral_for(&collateral_id, &9, &Address::generate(&env));
+
+    tokenize(&env, &registry, collateral_id, 1);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #31)")]
+fn test_tokenize_basket_member_rejected() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+
+    let collateral_id = register_verified(&env, &registry, &owner);
+    registry.create_basket(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [BasketMember {
+                collateral_id,
+                share_bps: 10000,
+            }],
+        ),
+    );
+
+    tokenize(&env, &registry, collateral_id, 1);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #39)")]
+fn test_tokenize_without_token_wasm_rejected() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+
+    let collateral_id = register_verified(&env, &registry, &owner);
+    registry.tokenize_collateral(
+        &collateral_id,
+        &1,
+        &String::from_str(&env, "Collateral #1"),
+        &String::from_str(&env, "SVC1"),
+    );
+}
+
+// Basket tests
+
+fn register_item(
+    env: &Env,
+    registry: &CollateralRegistryClient,
+    owner: &Address,
+    asset_type: AssetType,
+    valuation: i128,
+) -> u64 {
+    registry.register_collateral(
+        owner,
+        &BytesN::<32>::random(env),
+        &String::from_str(env, "ipfs://QmBasketItem"),
+        &asset_type,
+        &valuation,
+    )
+}
+
+#[test]
+fn test_basket_valuation_applies_shares_and_haircuts() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+
+    let inventory = register_item(&env, &registry, &owner, AssetType::Inventory, 100_000);
+    let receivable = register_item(&env, &registry, &owner, AssetType::Receivables, 50_000);
+    registry.set_haircut(&AssetType::Inventory, &3000);
+    registry.set_haircut(&AssetType::Receivables, &1000);
+
+    let basket_id = registry.create_basket(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [
+                BasketMember {
+                    collateral_id: inventory,
+                    share_bps: 10000,
+                },
+                BasketMember {
+                    collateral_id: receivable,
+                    share_bps: 5000,
+                },
+            ],
+        ),
+    );
+
+    let valuation = registry.get_basket_valuation(&basket_id);
+    assert_eq!(valuation.gross_value, 125_000);
+    assert_eq!(valuation.adjusted_value, 70_000 + 22_500);
+    // Registered but never verified
+    assert!(!valuation.verified);
+    assert_eq!(registry.get_collateral_basket(&receivable), Some(basket_id));
+}
+
+#[test]
+fn test_basket_locks_and_unlocks_all_members() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+
+    let first = register_item(&env, &registry, &owner, AssetType::Inventory, 10_000);
+    let second = register_item(&env, &registry, &owner, AssetType::Receivables, 20_000);
+    let basket_id = registry.create_basket(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [
+                BasketMember {
+                    collateral_id: first,
+                    share_bps: 10000,
+                },
+                BasketMember {
+                    collateral_id: second,
+                    share_bps: 10000,
+                },
+            ],
+        ),
+    );
+
+    registry.lock_basket(&basket_id, &42);
+    assert!(registry.get_basket(&basket_id).locked);
+    for id in [first, second] {
+        let collateral = registry.get_collateral(&id);
+        assert!(collateral.locked);
+        assert_eq!(collateral.locked_by_escrow, 42);
+    }
+
+    registry.unlock_basket(&basket_id);
+    assert!(!registry.get_collateral(&first).locked);
+    assert!(!registry.get_collateral(&second).locked);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #31)")]
+fn test_basket_member_cannot_be_locked_for_lender_alone() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+
+    let member = register_item(&env, &registry, &owner, AssetType::Inventory, 10_000);
+    registry.create_basket(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [BasketMember {
+                collateral_id: member,
+                share_bps: 10000,
+            }],
+        ),
+    );
+
+    registry.lock_collateral_for(&member, &9, &Address::generate(&env));
+}
+
+#[test]
+fn test_basket_locked_for_secured_party() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let lender = Address::generate(&env);
+
+    let first = register_item(&env, &registry, &owner, AssetType::Inventory, 10_000);
+    let basket_id = registry.create_basket(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [BasketMember {
+                collateral_id: first,
+                share_bps: 10000,
+            }],
+        ),
+    );
+
+    registry.lock_basket_for(&basket_id, &7, &lender);
+    assert_eq!(
+        registry.get_basket_secured_party(&basket_id),
+        Some(lender.clone())
+    );
+
+    registry.unlock_basket(&basket_id);
+    let (auth_address, _) = env.auths().last().unwrap().clone();
+    assert_eq!(auth_address, lender);
+    assert!(!registry.get_basket(&basket_id).locked);
+    assert_eq!(registry.get_basket_secured_party(&basket_id), None);
+}
+
+#[test]
+fn test_seize_basket_transfers_whole_members_and_claims_partial_shares() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let lender = Address::generate(&env);
+
+    let first = register_item(&env, &registry, &owner, AssetType::Inventory, 10_000);
+    let second = register_item(&env, &registry, &owner, AssetType::Receivables, 20_000);
+    let basket_id = registry.create_basket(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [
+                BasketMember {
+                    collateral_id: first,
+                    share_bps: 10000,
+                },
+                BasketMember {
+                    collateral_id: second,
+                    share_bps: 4000,
+                },
+            ],
+        ),
+    );
+    registry.lock_basket_for(&basket_id, &7, &lender);
+
+    registry.seize_basket(&basket_id, &lender);
+
+    let whole = registry.get_collateral(&first);
+    assert_eq!(whole.owner, lender);
+    assert_eq!(whole.status, CollateralStatus::Seized);
+    assert!(!whole.locked);
+
+    let partial = registry.get_collateral(&second);
+    assert_eq!(partial.owner, owner);
+    assert!(!partial.locked);
+    assert_eq!(
+        registry
+            .get_liquidation_claim(&second, &lender)
+            .unwrap()
+            .share_bps,
+        4000
+    );
+    assert_eq!(registry.get_collateral_basket(&second), None);
+    assert_eq!(registry.get_basket_secured_party(&basket_id), None);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #9)")]
+fn test_basket_lock_is_all_or_nothing() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+
+    let first = register_item(&env, &registry, &owner, AssetType::Inventory, 10_000);
+    let second = register_item(&env, &registry, &owner, AssetType::Receivables, 20_000);
+    let basket_id = registry.create_basket(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [
+                BasketMember {
+                    collateral_id: first,
+                    share_bps: 10000,
+                },
+                BasketMember {
+                    collateral_id: second,
+                    share_bps: 10000,
+                },
+            ],
+        ),
+    );
+
+    registry.lock_collateral(&second, &7);
+    registry.lock_basket(&basket_id, &42);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #31)")]
+fn test_locked_basket_member_cannot_be_unlocked_directly() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+
+    let item = register_item(&env, &registry, &owner, AssetType::Inventory, 10_000);
+    let basket_id = registry.create_basket(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [BasketMember {
+                collateral_id: item,
+                share_bps: 10000,
+            }],
+        ),
+    );
+    registry.lock_basket(&basket_id, &42);
+
+    registry.unlock_collateral(&item);
+}
+
+#[test]
+fn test_substitute_member_while_locked() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+
+    let old_item = register_item(&env, &registry, &owner, AssetType::Inventory, 10_000);
+    let new_item = register_item(&env, &registry, &owner, AssetType::Receivables, 12_000);
+    let basket_id = registry.create_basket(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [BasketMember {
+                collateral_id: old_item,
+                share_bps: 10000,
+            }],
+        ),
+    );
+    registry.lock_basket(&basket_id, &42);
+
+    registry.substitute_basket_member(
+        &basket_id,
+        &old_item,
+        &BasketMember {
+            collateral_id: new_item,
+            share_bps: 10000,
+        },
+    );
+
+    assert!(!registry.get_collateral(&old_item).locked);
+    let replacement = registry.get_collateral(&new_item);
+    assert!(replacement.locked);
+    assert_eq!(replacement.locked_by_escrow, 42);
+    assert_eq!(registry.get_collateral_basket(&old_item), None);
+    assert_eq!(
+        registry.get_basket_valuation(&basket_id).adjusted_value,
+        12_000
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #32)")]
+fn test_substitute_member_cannot_reduce_locked_value() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+
+    let old_item = register_item(&env, &registry, &owner, AssetType::Inventory, 10_000);
+    let new_item = register_item(&env, &registry, &owner, AssetType::Receivables, 12_000);
+    registry.set_haircut(&AssetType::Receivables, &2000);
+    let basket_id = registry.create_basket(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [BasketMember {
+                collateral_id: old_item,
+                share_bps: 10000,
+            }],
+        ),
+    );
+    registry.lock_basket(&basket_id, &42);
+
+    registry.substitute_basket_member(
+        &basket_id,
+        &old_item,
+        &BasketMember {
+            collateral_id: new_item,
+            share_bps: 10000,
+        },
+    );
+}
+
+fn basket_locked_for(
+    env: &Env,
+    registry: &CollateralRegistryClient,
+    owner: &Address,
+    lender: &Address,
+) -> (u64, u64) {
+    let old_item = register_item(env, registry, owner, AssetType::Inventory, 10_000);
+    let basket_id = registry.create_basket(
+        owner,
+        &Vec::from_array(
+            env,
+            [BasketMember {
+                collateral_id: old_item,
+                share_bps: 10000,
+            }],
+        ),
+    );
+    registry.lock_basket_for(&basket_id, &7, lender);
+    (basket_id, old_item)
+}
+
+fn substitution_signers(
+    env: &Env,
+    registry: &CollateralRegistryClient,
+    basket_id: u64,
+    old_item: u64,
+    new_item: u64,
+) -> std::vec::Vec<Address> {
+    registry.substitute_basket_member(
+        &basket_id,
+        &old_item,
+        &BasketMember {
+            collateral_id: new_item,
+            share_bps: 10000,
+        },
+    );
+    env.auths()
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect()
+}
+
+#[test]
+fn test_substitute_verified_fresh_member_needs_only_owner() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let lender = Address::generate(&env);
+
+    let (basket_id, old_item) = basket_locked_for(&env, &registry, &owner, &lender);
+    let new_item = register_verified(&env, &registry, &owner);
+
+    let signers = substitution_signers(&env, &registry, basket_id, old_item, new_item);
+    assert_eq!(signers, std::vec![owner]);
+    assert!(registry.get_collateral(&new_item).locked);
+}
+
+#[test]
+fn test_substitute_unverified_member_needs_secured_party() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let lender = Address::generate(&env);
+
+    let (basket_id, old_item) = basket_locked_for(&env, &registry, &owner, &lender);
+    let new_item = register_item(&env, &registry, &owner, AssetType::Inventory, 12_000);
+
+    let signers = substitution_signers(&env, &registry, basket_id, old_item, new_item);
+    assert!(signers.contains(&lender));
+}
+
+#[test]
+fn test_substitute_stale_member_needs_secured_party() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let lender = Address::generate(&env);
+
+    let (basket_id, old_item) = basket_locked_for(&env, &registry, &owner, &lender);
+    let new_item = register_verified(&env, &registry, &owner);
+    assert_eq!(registry.get_max_valuation_age(), 86400 * 30);
+
+    env.ledger().with_mut(|li| li.timestamp += 86400 * 31);
+    let signers = substitution_signers(&env, &registry, basket_id, old_item, new_item);
+    assert!(signers.contains(&lender));
+}
+
+// Insurance tests
+
+fn insure(
+    env: &Env,
+    registry: &CollateralRegistryClient,
+    collateral_id: u64,
+    insurer: &Address,
+    coverage_amount: i128,
+    expiry: u64,
+) {
+    registry.register_insurance(
+        &collateral_id,
+        insurer,
+        &BytesN::<32>::random(env),
+        &coverage_amount,
+        &Address::generate(env),
+        &expiry,
+    );
+}
+
+#[test]
+fn test_insurance_confirmed_policy_covers_collateral() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let insurer = Address::generate(&env);
+
+    let collateral_id = register_item(&env, &registry, &owner, AssetType::Equipment, 100_000);
+    insure(&env, &registry, collateral_id, &insurer, 100_000, 10_000);
+
+    assert_eq!(
+        registry.get_insurance_status(&collateral_id),
+        InsuranceStatus::Uninsured
+    );
+    assert!(!registry.is_insurance_flagged(&collateral_id));
+
+    registry.confirm_insurance(&collateral_id);
+
+    let policy = registry.get_insurance(&collateral_id);
+    assert_eq!(policy.state, PolicyState::Confirmed);
+    assert_eq!(policy.insurer, insurer);
+    assert_eq!(
+        registry.check_insurance(&collateral_id),
+        InsuranceStatus::Insured
+    );
+    assert!(!registry.is_insurance_flagged(&collateral_id));
+}
+
+#[test]
+fn test_insurance_lapse_flags_collateral() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let insurer = Address::generate(&env);
+
+    let collateral_id = register_item(&env, &registry, &owner, AssetType::Equipment, 100_000);
+    insure(&env, &registry, collateral_id, &insurer, 100_000, 10_000);
+    registry.confirm_insurance(&collateral_id);
+
+    env.ledger().with_mut(|li| li.timestamp = 10_000);
+
+    assert!(registry.is_insurance_flagged(&collateral_id));
+    assert_eq!(
+        registry.check_insurance(&collateral_id),
+        InsuranceStatus::Expired
+    );
+}
+
+#[test]
+fn test_insurance_under_coverage_after_revaluation() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let insurer = Address::generate(&env);
+
+    let collateral_id = register_item(&env, &registry, &owner, AssetType::Equipment, 100_000);
+    registry.set_min_insurance_coverage(&8000);
+    insure(&env, &registry, collateral_id, &insurer, 90_000, 10_000);
+    registry.confirm_insurance(&collateral_id);
+    assert!(!registry.is_insurance_flagged(&collateral_id));
+
+    registry.update_valuation(&collateral_id, &150_000, &BytesN::<64>::random(&env));
+
+    assert!(registry.is_insurance_flagged(&collateral_id));
+    assert_eq!(
+        registry.get_insurance_status(&collateral_id),
+        InsuranceStatus::PartiallyInsured
+    );
+}
+
+#[test]
+fn test_insurer_cancellation_flags_collateral() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let insurer = Address::generate(&env);
+
+    let collateral_id = register_item(&env, &registry, &owner, AssetType::Equipment, 100_000);
+    insure(&env, &registry, collateral_id, &insurer, 100_000, 10_000);
+    registry.confirm_insurance(&collateral_id);
+    registry.cancel_insurance(&collateral_id);
+
+    assert_eq!(
+        registry.get_insurance(&collateral_id).state,
+        PolicyState::Cancelled
+    );
+    assert!(registry.is_insurance_flagged(&collateral_id));
+}
+
+#[test]
+fn test_pending_replacement_keeps_lapse_flag() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let insurer = Address::generate(&env);
+
+    let collateral_id = register_item(&env, &registry, &owner, AssetType::Equipment, 100_000);
+    insure(&env, &registry, collateral_id, &insurer, 100_000, 10_000);
+    registry.confirm_insurance(&collateral_id);
+
+    // The old policy expires unnoticed before the owner replaces it
+    env.ledger().with_mut(|li| li.timestamp = 10_000);
+    insure(&env, &registry, collateral_id, &insurer, 100_000, 20_000);
+    assert!(registry.is_insurance_flagged(&collateral_id));
+    assert_eq!(
+        registry.check_insurance(&collateral_id),
+        InsuranceStatus::Uninsured
+    );
+
+    registry.confirm_insurance(&collateral_id);
+    assert!(!registry.is_insurance_flagged(&collateral_id));
+}
+
+#[test]
+fn test_replacing_insurance_on_locked_collateral_needs_secured_party() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let insurer = Address::generate(&env);
+    let lender = Address::generate(&env);
+
+    let collateral_id = register_item(&env, &registry, &owner, AssetType::Equipment, 100_000);
+    insure(&env, &registry, collateral_id, &insurer, 100_000, 10_000);
+    registry.lock_collateral_for(&collateral_id, &9, &lender);
+
+    insure(&env, &registry, collateral_id, &insurer, 100_000, 20_000);
+    let signers: std::vec::Vec<Address> = env
+        .auths()
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect();
+    assert!(signers.contains(&owner));
+    assert!(signers.contains(&lender));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #9)")]
+fn test_replacing_insurance_under_plain_lock_rejected() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let insurer = Address::generate(&env);
+
+    let collateral_id = register_item(&env, &registry, &owner, AssetType::Equipment, 100_000);
+    insure(&env, &registry, collateral_id, &insurer, 100_000, 10_000);
+    registry.lock_collateral(&collateral_id, &9);
+
+    insure(&env, &registry, collateral_id, &insurer, 100_000, 20_000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #34)")]
+fn test_insurance_cannot_be_confirmed_twice() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let insurer = Address::generate(&env);
+
+    let collateral_id = register_item(&env, &registry, &owner, AssetType::Equipment, 100_000);
+    insure(&env, &registry, collateral_id, &insurer, 100_000, 10_000);
+    registry.confirm_insurance(&collateral_id);
+    registry.confirm_insurance(&collateral_id);
+}
+
+// Warehouse receipt tests
+
+fn issue_receipt(
+    env: &Env,
+    registry: &CollateralRegistryClient,
+    custodian: &Address,
+    owner: &Address,
+) -> u64 {
+    registry.approve_custodian(custodian);
+    registry.issue_warehouse_receipt(
+        custodian,
+        owner,
+        &BytesN::<32>::random(env),
+        &CommodityType::Agriculture,
+        &String::from_str(env, "Grade A"),
+        &1_000,
+        &String::from_str(env, "tonne"),
+        &String::from_str(env, "Lagos Warehouse 4"),
+        &250,
+    )
+}
+
+#[test]
+fn test_issue_warehouse_receipt() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let custodian = Address::generate(&env);
+    let owner = Address::generate(&env);
+
+    let collateral_id = issue_receipt(&env, &registry, &custodian, &owner);
+
+    let collateral = registry.get_collateral(&collateral_id);
+    assert_eq!(collateral.owner, owner);
+    assert_eq!(collateral.asset_type, AssetType::Commodities);
+    assert_eq!(collateral.current_valuation, 250_000);
+    assert_eq!(collateral.verified_by, Some(custodian.clone()));
+
+    let receipt = registry.get_warehouse_receipt(&collateral_id);
+    assert_eq!(receipt.custodian, custodian);
+    assert_eq!(receipt.commodity.quantity, 1_000);
+    assert_eq!(receipt.commodity.commodity_type, CommodityType::Agriculture);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #35)")]
+fn test_unapproved_custodian_cannot_issue_receipt() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let custodian = Address::generate(&env);
+    let owner = Address::generate(&env);
+
+    registry.issue_warehouse_receipt(
+        &custodian,
+        &owner,
+        &BytesN::<32>::random(&env),
+        &CommodityType::Metals,
+        &String::from_str(&env, "LME Grade A"),
+        &10,
+        &String::from_str(&env, "tonne"),
+        &String::from_str(&env, "Rotterdam"),
+        &9_000,
+    );
+}
+
+#[test]
+fn test_partial_release_reduces_quantity_and_valuation() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let custodian = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let lender = Address::generate(&env);
+
+    let collateral_id = issue_receipt(&env, &registry, &custodian, &owner);
+    registry.lock_collateral_for(&collateral_id, &9, &lender);
+    assert_eq!(registry.get_secured_party(&collateral_id), Some(lender));
+    let appraised_at = registry.get_collateral(&collateral_id).valuation_timestamp;
+
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    registry.release_from_receipt(&collateral_id, &400);
+
+    let receipt = registry.get_warehouse_receipt(&collateral_id);
+    assert_eq!(receipt.commodity.quantity, 600);
+    assert_eq!(receipt.original_quantity, 1_000);
+
+    let collateral = registry.get_collateral(&collateral_id);
+    assert_eq!(collateral.current_valuation, 150_000);
+    assert_eq!(collateral.valuation_timestamp, appraised_at);
+    assert!(collateral.locked);
+
+    registry.unlock_collateral(&collateral_id);
+    assert_eq!(registry.get_secured_party(&collateral_id), None);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #9)")]
+fn test_release_requires_secured_party_while_locked() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let custodian = Address::generate(&env);
+    let owner = Address::generate(&env);
+
+    let collateral_id = issue_receipt(&env, &registry, &custodian, &owner);
+    registry.lock_collateral(&collateral_id, &9);
+
+    registry.release_from_receipt(&collateral_id, &100);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #37)")]
+fn test_release_cannot_exceed_held_quantity() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let custodian = Address::generate(&env);
+    let owner = Address::generate(&env);
+
+    let collateral_id = issue_receipt(&env, &registry, &custodian, &owner);
+
+    registry.release_from_receipt(&collateral_id, &1_001);
+}
+
+// Lendable value tests
+
+fn classify(
+    env: &Env,
+    registry: &CollateralRegistryClient,
+    admin: &Address,
+    collateral_id: u64,
+    primary_class: AssetClass,
+    risk_rating: RiskRating,
+) {
+    registry.classify_collateral(
+        &collateral_id,
+        &AssetClassification {
+            collateral_id,
+            primary_class,
+            secondary_class: AssetClass::Unspecified,
+            risk_rating,
+            liquidity_score: 50,
+            classified_by: admin.clone(),
+            classified_at: env.ledger().timestamp(),
+        },
+    );
+}
+
+#[test]
+fn test_lendable_value_uses_classification_rule() {
+    let env = Env::default();
+    let (registry, admin) = setup_registry(&env);
+    let governance = Address::generate(&env);
+    let owner = Address::generate(&env);
+    registry.set_governance(&governance);
+
+    let collateral_id = register_item(&env, &registry, &owner, AssetType::Inventory, 200_000);
+    registry.set_haircut(&AssetType::Inventory, &1000);
+    assert_eq!(registry.get_lendable_value(&collateral_id), 180_000);
+
+    classify(
+        &env,
+        &registry,
+        &admin,
+        collateral_id,
+        AssetClass::Inventory,
+        RiskRating::BB,
+    );
+    registry.set_haircut_rule(&AssetClass::Inventory, &RiskRating::BB, &4000, &true);
+
+    assert_eq!(registry.get_lendable_value(&collateral_id), 120_000);
+    assert_eq!(
+        registry.get_haircut_rule(&AssetClass::Inventory, &RiskRating::BB),
+        Some(HaircutRule {
+            haircut_bps: 4000,
+            eligible: true,
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #38)")]
+fn test_lendable_value_rejects_ineligible_collateral() {
+    let env = Env::default();
+    let (registry, admin) = setup_registry(&env);
+    let governance = Address::generate(&env);
+    let owner = Address::generate(&env);
+    registry.set_governance(&governance);
+
+    let collateral_id = register_item(&env, &registry, &owner, AssetType::Receivables, 50_000);
+    classify(
+        &env,
+        &registry,
+        &admin,
+        collateral_id,
+        AssetClass::Receivables,
+        RiskRating::D,
+    );
+    registry.set_haircut_rule(&AssetClass::Receivables, &RiskRating::D, &10000, &false);
+
+    registry.get_lendable_value(&collateral_id);
+}
+
+#[test]
+fn test_collateral_valuation_snapshot() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+
+    let unverified = register_item(&env, &registry, &owner, AssetType::Equipment, 80_000);
+    assert!(!registry.get_collateral_valuation(&unverified).verified);
+    assert_eq!(registry.get_asset_type(&unverified), AssetType::Equipment);
+
+    let collateral_id = register_verified(&env, &registry, &owner);
+    registry.set_haircut(&AssetType::Equipment, &2500);
+
+    let valuation = registry.get_collateral_valuation(&collateral_id);
+    assert!(valuation.verified);
+    assert_eq!(valuation.valuation, 1_000_000);
+    assert_eq!(valuation.lendable_value, 750_000);
+    assert_eq!(valuation.valuation_timestamp, 5_000);
+}
+
+#[test]
+fn test_secured_party_seizes_collateral() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let lender = Address::generate(&env);
+
+    let collateral_id = register_verified(&env, &registry, &owner);
+    registry.lock_collateral_for(&collateral_id, &9, &lender);
+
+    registry.seize_collateral(&collateral_id, &lender);
+
+    let collateral = registry.get_collateral(&collateral_id);
+    assert_eq!(collateral.owner, lender);
+    assert_eq!(collateral.status, CollateralStatus::Seized);
+    assert!(!collateral.locked);
+    assert_eq!(registry.get_secured_party(&collateral_id), None);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #1)")]
+fn test_seize_requires_secured_party_lock() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+
+    let collateral_id = register_verified(&env, &registry, &owner);
+    registry.lock_collateral(&collateral_id, &9);
+
+    registry.seize_collateral(&collateral_id, &Address::generate(&env));
+}
+
+#[test]
+fn test_full_liquidation_transfers_collateral_to_liquidator() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let lender = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+
+    let collateral_id = register_verified(&env, &registry, &owner);
+    registry.lock_collateral_for(&collateral_id, &9, &lender);
+
+    assert_eq!(
+        registry.liquidate_collateral(&collateral_id, &liquidator, &10000),
+        10000
+    );
+
+    let collateral = registry.get_collateral(&collateral_id);
+    assert_eq!(collateral.owner, liquidator);
+    assert_eq!(collateral.status, CollateralStatus::Liquidated);
+    assert!(!collateral.locked);
+    assert_eq!(registry.get_secured_party(&collateral_id), None);
+}
+
+#[test]
+fn test_partial_liquidations_record_claims_until_whole() {
+    let env = Env::default();
+    let (registry, _admin) = setup_registry(&env);
+    let owner = Address::generate(&env);
+    let lender = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+
+    let collateral_id = register_verified(&env, &registry, &owner);
+    registry.lock_collateral_for(&collateral_id, &9, &lender);
+
+    // A partial share is a claim; the lock stays in place
+    assert_eq!(
+        registry.liquidate_collateral(&collateral_id, &first, &4000),
+        4000
+    );
+    let collateral = registry.get_collateral(&collateral_id);
+    assert!(collateral.locked);
+    assert_eq!(collateral.owner, owner);
+    assert_eq!(
+        registry
+            .get_liquidation_claim(&collateral_id, &first)
+            .unwrap()
+            .share_bps,
+        4000
+    );
+
+    // The remainder is capped at the unclaimed share and ends the lock
+    assert_eq!(
+        registry.liquidate_collateral(&collateral_id, &second, &10000),
+        10000
+    );
+    assert_eq!(
+        registry
+            .get_liquidation_claim(&collateral_id, &second)
+            .unwrap()
+            .share_bps,
+        6000
+    );
+    let collateral = registry.get_collateral(&collateral_id);
+    assert!(!collateral.locked);
+    assert_eq!(collateral.status, CollateralStatus::Liquidated);
+    assert_eq!(collateral.owner, owner);
+    assert_eq!(registry.get_liquidated_share(&collateral_id), 10000);
+}
@@ -0,0 +1,276 @@
+//! Collateral Token Contract for StelloVault
+//!
+//! SEP-41 compatible token that represents a single collateral entry (or its
+//! fractions) held in the Collateral Registry. Each instance is bound to one
+//! registry entry at initialization; the registry mints the full supply to the
+//! collateral owner. Every transfer is reported back to the registry, which
+//! rejects it while the collateral is locked and keeps ownership in sync for
+//! whole (non-fractionalized) items.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short,
+    token::TokenInterface, Address, Env, IntoVal, String, Symbol, Val, Vec,
+};
+
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContractError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    InvalidAmount = 3,
+    InsufficientBalance = 4,
+    InsufficientAllowance = 5,
+    InvalidExpiration = 6,
+    BurnNotSupported = 7,
+}
+
+/// Allowance granted by a holder to a spender
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// Binding between this token and its registry entry
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TokenMetadata {
+    pub registry: Address,
+    pub collateral_id: u64,
+    pub name: String,
+    pub symbol: String,
+    pub total_supply: i128,
+}
+
+/// Collateral tokens are whole units: one unit per fraction.
+const DECIMALS: u32 = 0;
+
+#[contract]
+pub struct CollateralToken;
+
+#[contractimpl]
+impl CollateralToken {
+    /// Bind the token to a registry entry and mint the full supply
+    ///
+    /// Only callable by the registry itself, as part of `tokenize_collateral`.
+    ///
+    /// # Arguments
+    /// * `registry` - Collateral registry contract address
+    /// * `collateral_id` - ID of the collateral this token represents
+    /// * `holder` - Address receiving the initial supply (collateral owner)
+    /// * `supply` - Number of units (1 for a whole item, N for N fractions)
+    /// * `name` - Token name
+    /// * `symbol` - Token symbol
+    pub fn initialize(
+        env: Env,
+        registry: Address,
+        collateral_id: u64,
+        holder: Address,
+        supply: i128,
+        name: String,
+        symbol: String,
+    ) -> Result<(), ContractError> {
+        if env.storage().instance().has(&symbol_short!("meta")) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
+        registry.require_auth();
+
+        if supply <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let metadata = TokenMetadata {
+            registry,
+            collateral_id,
+            name,
+            symbol,
+            total_supply: supply,
+        };
+        env.storage()
+            .instance()
+            .set(&symbol_short!("meta"), &metadata);
+
+        write_balance(&env, &holder, supply);
+
+        env.events()
+            .publish((symbol_short!("mint"), holder), supply);
+
+        Ok(())
+    }
+
+    /// Get the registry address and collateral ID backing this token
+    pub fn collateral(env: Env) -> (Address, u64) {
+        let metadata = read_metadata(&env);
+        (metadata.registry, metadata.collateral_id)
+    }
+
+    /// Get the total number of units in circulation
+    pub fn total_supply(env: Env) -> i128 {
+        read_metadata(&env).total_supply
+    }
+}
+
+#[contractimpl]
+impl TokenInterface for CollateralToken {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        read_allowance(&env, &from, &spender).amount
+    }
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+
+        if amount < 0 {
+            panic_with_error!(&env, ContractError::InvalidAmount);
+        }
+        if amount > 0 && expiration_ledger < env.ledger().sequence() {
+            panic_with_error!(&env, ContractError::InvalidExpiration);
+        }
+
+        env.storage().persistent().set(
+            &(symbol_short!("allow"), from.clone(), spender.clone()),
+            &AllowanceValue {
+                amount,
+                expiration_ledger,
+            },
+        );
+
+        env.events().publish(
+            (symbol_short!("approve"), from, spender),
+            (amount, expiration_ledger),
+        );
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        read_balance(&env, &id)
+    }
+
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        move_balance(&env, &from, &to, amount);
+    }
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        spend_allowance(&env, &from, &spender, amount);
+        move_balance(&env, &from, &to, amount);
+    }
+
+    fn burn(env: Env, from: Address, _amount: i128) {
+        from.require_auth();
+        panic_with_error!(&env, ContractError::BurnNotSupported);
+    }
+
+    fn burn_from(env: Env, spender: Address, _from: Address, _amount: i128) {
+        spender.require_auth();
+        panic_with_error!(&env, ContractError::BurnNotSupported);
+    }
+
+    fn decimals(_env: Env) -> u32 {
+        DECIMALS
+    }
+
+    fn name(env: Env) -> String {
+        read_metadata(&env).name
+    }
+
+    fn symbol(env: Env) -> String {
+        read_metadata(&env).symbol
+    }
+}
+
+// Helper functions
+
+fn read_metadata(env: &Env) -> TokenMetadata {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("meta"))
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::NotInitialized))
+}
+
+fn read_balance(env: &Env, id: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("bal"), id.clone()))
+        .unwrap_or(0)
+}
+
+fn write_balance(env: &Env, id: &Address, amount: i128) {
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("bal"), id.clone()), &amount);
+}
+
+fn read_allowance(env: &Env, from: &Address, spender: &Address) -> AllowanceValue {
+    let allowance: Option<AllowanceValue> = env
+        .storage()
+        .persistent()
+        .get(&(symbol_short!("allow"), from.clone(), spender.clone()));
+
+    match allowance {
+        Some(value) if value.expiration_ledger >= env.ledger().sequence() => value,
+        _ => AllowanceValue {
+            amount: 0,
+            expiration_ledger: 0,
+        },
+    }
+}
+
+fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+    let allowance = read_allowance(env, from, spender);
+    if allowance.amount < amount {
+        panic_with_error!(env, ContractError::InsufficientAllowance);
+    }
+
+    env.storage().persistent().set(
+        &(symbol_short!("allow"), from.clone(), spender.clone()),
+        &AllowanceValue {
+            amount: allowance.amount - amount,
+            expiration_ledger: allowance.expiration_ledger,
+        },
+    );
+}
+
+/// Move units between holders after the registry has approved the transfer.
+///
+/// The registry call panics (and reverts the whole transfer) if the collateral
+/// is locked, seized or otherwise not transferable.
+fn move_balance(env: &Env, from: &Address, to: &Address, amount: i128) {
+    if amount < 0 {
+        panic_with_error!(env, ContractError::InvalidAmount);
+    }
+
+    let from_balance = read_balance(env, from);
+    if from_balance < amount {
+        panic_with_error!(env, ContractError::InsufficientBalance);
+    }
+
+    let metadata = read_metadata(env);
+    let hook_args: Vec<Val> = Vec::from_array(
+        env,
+        [
+            metadata.collateral_id.into_val(env),
+            from.into_val(env),
+            to.into_val(env),
+            amount.into_val(env),
+        ],
+    );
+    env.invoke_contract::<Val>(
+        &metadata.registry,
+        &Symbol::new(env, "on_token_transfer"),
+        hook_args,
+    );
+
+    if from != to {
+        write_balance(env, from, from_balance - amount);
+        write_balance(env, to, read_balance(env, to) + amount);
+    }
+
+    env.events().publish(
+        (symbol_short!("transfer"), from.clone(), to.clone()),
+        amount,
+    );
+}
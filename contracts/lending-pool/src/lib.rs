@@ -0,0 +1,917 @@
+//! Lending Pool Contract for StelloVault
+//!
+//! Liquidity providers deposit a stable asset and receive pool shares. The
+//! loan management contract draws loan principal from the pool; repayments
+//! and interest flow back in and raise the value of every share, while
+//! defaults written off lower it. Borrowing rates follow a kinked
+//! utilization curve set by governance. Withdrawals are paid from idle
+//! liquidity, with any remainder queued and filled first-in, first-out as
+//! liquidity returns.
+
+#![no_std]
+
+use core::cmp;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+};
+
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContractError {
+    Unauthorized = 1,
+    AlreadyInitialized = 2,
+    NotInitialized = 3,
+    InvalidAmount = 4,
+    InsufficientShares = 5,
+    InsufficientLiquidity = 6,
+    InvalidRateModel = 7,
+    PositionNotFound = 8,
+    PositionExists = 9,
+}
+
+/// Fixed-point scale of the share price
+pub const PRICE_SCALE: i128 = 10_000_000;
+
+/// Queued withdrawals filled per call, bounding the work of a single call
+const MAX_QUEUE_BATCH: u32 = 20;
+
+/// Kinked utilization rate curve, all in basis points
+///
+/// Below `optimal_utilization` the rate climbs from `base_rate` by up to
+/// `slope_low`; above it the remaining utilization adds up to `slope_high`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateModel {
+    pub base_rate: u32,
+    pub slope_low: u32,
+    pub slope_high: u32,
+    pub optimal_utilization: u32,
+}
+
+/// Principal lent to a loan from the pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanPosition {
+    pub loan_id: u64,
+    pub borrower: Address,
+    pub principal: i128,
+    pub outstanding: i128,
+    pub written_off: i128,
+    pub rate: u32, // basis points, fixed at draw
+    pub drawn_at: u64,
+}
+
+/// Shares waiting for liquidity to be redeemed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalRequest {
+    pub id: u64,
+    pub provider: Address,
+    pub shares: i128,
+    pub requested_at: u64,
+}
+
+#[contract]
+pub struct LendingPool;
+
+#[contractimpl]
+impl LendingPool {
+    /// Initialize the pool
+    ///
+    /// # Arguments
+    /// * `admin` - Pool administrator
+    /// * `asset` - Token lent by the pool
+    /// * `governance` - Address allowed to set the rate model
+    /// * `loan_manager` - Loan management contract allowed to draw principal
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        governance: Address,
+        loan_manager: Address,
+    ) -> Result<(), ContractError> {
+        if env.storage().instance().has(&symbol_short!("admin")) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("admin"), &admin);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("asset"), &asset);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("gov"), &governance);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("loan_mgr"), &loan_manager);
+
+        env.events()
+            .publish((symbol_short!("pool_init"),), (admin, asset));
+
+        Ok(())
+    }
+
+    /// Set the borrowing rate curve (governance only)
+    ///
+    /// # Arguments
+    /// * `model` - New rate model
+    ///
+    /// # Events
+    /// Emits `RateModelSet` event
+    pub fn set_rate_model(env: Env, model: RateModel) -> Result<(), ContractError> {
+        let governance: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("gov"))
+            .ok_or(ContractError::NotInitialized)?;
+        governance.require_auth();
+
+        if model.optimal_utilization == 0
+            || model.optimal_utilization >= 10000
+            || model.base_rate as u64 + model.slope_low as u64 + model.slope_high as u64 > 100000
+        {
+            return Err(ContractError::InvalidRateModel);
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("rate_mdl"), &model);
+
+        env.events().publish(
+            (symbol_short!("rate_mdl"),),
+            (
+                model.base_rate,
+                model.slope_low,
+                model.slope_high,
+                model.optimal_utilization,
+            ),
+        );
+
+        Ok(())
+    }
+
+    /// Appoint the servicer that acts for the pool on its defaulted loans (governance only)
+    ///
+    /// The pool can't sign for itself, so the loan manager accepts the
+    /// servicer's authorization for lender actions on pool-funded loans:
+    /// forbearance, recovery plans, collateral seizure and insurance claims.
+    ///
+    /// # Events
+    /// Emits `ServicerSet` event
+    pub fn set_servicer(env: Env, servicer: Address) -> Result<(), ContractError> {
+        let governance: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("gov"))
+            .ok_or(ContractError::NotInitialized)?;
+        governance.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("servicer"), &servicer);
+
+        env.events().publish((symbol_short!("servicer"),), servicer);
+
+        Ok(())
+    }
+
+    /// Get the pool's loan servicer
+    pub fn get_servicer(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("servicer"))
+    }
+
+    /// Get the token the pool lends in
+    pub fn get_asset(env: Env) -> Result<Address, ContractError> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("asset"))
+            .ok_or(ContractError::NotInitialized)
+    }
+
+    /// Get the borrowing rate curve
+    pub fn get_rate_model(env: Env) -> RateModel {
+        read_rate_model(&env)
+    }
+
+    /// Deposit liquidity in exchange for pool shares
+    ///
+    /// # Arguments
+    /// * `provider` - Address depositing
+    /// * `amount` - Amount of the pool asset
+    ///
+    /// # Returns
+    /// The shares minted
+    ///
+    /// # Events
+    /// Emits `Deposited` event
+    pub fn deposit(env: Env, provider: Address, amount: i128) -> Result<i128, ContractError> {
+        provider.require_auth();
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let supply = total_shares(&env);
+        let assets = total_assets(&env)?;
+        let shares = if supply == 0 {
+            amount
+        } else if assets > 0 {
+            amount * supply / assets
+        } else {
+            0
+        };
+        if shares <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        asset_client(&env)?.transfer(&provider, &env.current_contract_address(), &amount);
+        write_balance(&env, &provider, read_balance(&env, &provider) + shares);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("shares"), &(supply + shares));
+
+        env.events()
+            .publish((symbol_short!("deposit"),), (provider, amount, shares));
+
+        fill_withdrawals(&env)?;
+
+        Ok(shares)
+    }
+
+    /// Redeem shares for the pool asset
+    ///
+    /// Shares are paid from idle liquidity at the current share price. When
+    /// liquidity runs short, or other withdrawals are already waiting, the
+    /// unpaid shares join the withdrawal queue.
+    ///
+    /// # Arguments
+    /// * `provider` - Address redeeming
+    /// * `shares` - Shares to redeem
+    ///
+    /// # Returns
+    /// The amount paid immediately
+    ///
+    /// # Events
+    /// Emits `Withdrawn` event, and `WithdrawalQueued` for any remainder
+    pub fn withdraw(env: Env, provider: Address, shares: i128) -> Result<i128, ContractError> {
+        provider.require_auth();
+
+        let balance = read_balance(&env, &provider);
+        if shares <= 0 || shares > balance {
+            return Err(ContractError::InsufficientShares);
+        }
+
+        // Earlier requests are served first
+        let (head, tail) = queue_bounds(&env);
+        let redeemed = if head == tail {
+            cmp::min(shares, redeemable_shares(&env)?)
+        } else {
+            0
+        };
+        let paid = redeem(&env, &provider, redeemed)?;
+
+        let queued = shares - redeemed;
+        write_balance(&env, &provider, balance - shares);
+        if queued > 0 {
+            let request = WithdrawalRequest {
+                id: tail,
+                provider: provider.clone(),
+                shares: queued,
+                requested_at: env.ledger().timestamp(),
+            };
+            env.storage()
+                .persistent()
+                .set(&(symbol_short!("withdraw"), tail), &request);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("wq_tail"), &(tail + 1));
+            env.storage()
+                .instance()
+                .set(&symbol_short!("queued"), &(queued_shares(&env) + queued));
+
+            env.events()
+                .publish((symbol_short!("wd_queue"),), (provider, tail, queued));
+        }
+
+        Ok(paid)
+    }
+
+    /// Pay queued withdrawals from idle liquidity, oldest first
+    ///
+    /// Also runs after deposits and repayments. A request that cannot be
+    /// paid in full is paid in part and stays at the front of the queue.
+    ///
+    /// # Returns
+    /// The total amount paid
+    pub fn process_withdrawals(env: Env) -> Result<i128, ContractError> {
+        fill_withdrawals(&env)
+    }
+
+    /// Lend principal to a loan (loan manager only)
+    ///
+    /// The loan's rate is fixed from the curve at the utilization the draw
+    /// leaves behind. Liquidity owed to queued withdrawals cannot be lent.
+    ///
+    /// # Arguments
+    /// * `loan_id` - Loan management loan ID
+    /// * `borrower` - Recipient of the principal
+    /// * `amount` - Principal to lend
+    ///
+    /// # Returns
+    /// The loan's interest rate in basis points
+    ///
+    /// # Events
+    /// Emits `PrincipalDrawn` event
+    pub fn draw(
+        env: Env,
+        loan_id: u64,
+        borrower: Address,
+        amount: i128,
+    ) -> Result<u32, ContractError> {
+        require_loan_manager(&env)?;
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        if env.storage().persistent().has(&position_key(loan_id)) {
+            return Err(ContractError::PositionExists);
+        }
+        if amount > available_liquidity(&env)? {
+            return Err(ContractError::InsufficientLiquidity);
+        }
+
+        let borrowed = total_borrowed(&env) + amount;
+        let rate = rate_at(
+            &read_rate_model(&env),
+            utilization_of(borrowed, total_assets(&env)?),
+        );
+
+        asset_client(&env)?.transfer(&env.current_contract_address(), &borrower, &amount);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("borrowed"), &borrowed);
+        env.storage().persistent().set(
+            &position_key(loan_id),
+            &LoanPosition {
+                loan_id,
+                borrower: borrower.clone(),
+                principal: amount,
+                outstanding: amount,
+                written_off: 0,
+                rate,
+                drawn_at: env.ledger().timestamp(),
+            },
+        );
+
+        env.events()
+            .publish((symbol_short!("draw"),), (loan_id, borrower, amount, rate));
+
+        Ok(rate)
+    }
+
+    /// Record principal repaid to the pool (loan manager only)
+    ///
+    /// The repayment itself has already been transferred to the pool;
+    /// anything beyond principal still owed, such as interest, fees or
+    /// recoveries on a written-off loan, is earnings for share holders.
+    ///
+    /// # Arguments
+    /// * `loan_id` - Loan management loan ID
+    /// * `principal` - Principal portion of the repayment
+    ///
+    /// # Events
+    /// Emits `PrincipalRepaid` event
+    pub fn repay(env: Env, loan_id: u64, principal: i128) -> Result<(), ContractError> {
+        require_loan_manager(&env)?;
+
+        let mut position = read_position(&env, loan_id)?;
+        let applied = cmp::min(cmp::max(principal, 0), position.outstanding);
+        position.outstanding -= applied;
+        env.storage()
+            .persistent()
+            .set(&position_key(loan_id), &position);
+        env.storage().instance().set(
+            &symbol_short!("borrowed"),
+            &(total_borrowed(&env) - applied),
+        );
+
+        env.events().publish(
+            (symbol_short!("repay"),),
+            (loan_id, applied, position.outstanding),
+        );
+
+        fill_withdrawals(&env)?;
+
+        Ok(())
+    }
+
+    /// Write off a defaulted loan's principal against share holders (loan
+    /// manager only)
+    ///
+    /// # Arguments
+    /// * `loan_id` - Loan management loan ID
+    /// * `amount` - Principal lost
+    ///
+    /// # Events
+    /// Emits `LoanWrittenOff` event
+    pub fn write_off(env: Env, loan_id: u64, amount: i128) -> Result<(), ContractError> {
+        require_loan_manager(&env)?;
+
+        let mut position = read_position(&env, loan_id)?;
+        let loss = cmp::min(cmp::max(amount, 0), position.outstanding);
+        position.outstanding -= loss;
+        position.written_off += loss;
+        env.storage()
+            .persistent()
+            .set(&position_key(loan_id), &position);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("borrowed"), &(total_borrowed(&env) - loss));
+
+        env.events()
+            .publish((symbol_short!("write_off"),), (loan_id, loss));
+
+        Ok(())
+    }
+
+    /// Transfer pool shares
+    ///
+    /// # Arguments
+    /// * `from` - Current holder
+    /// * `to` - New holder
+    /// * `amount` - Shares to transfer
+    pub fn transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        from.require_auth();
+
+        let balance = read_balance(&env, &from);
+        if amount <= 0 || amount > balance {
+            return Err(ContractError::InsufficientShares);
+        }
+        write_balance(&env, &from, balance - amount);
+        write_balance(&env, &to, read_balance(&env, &to) + amount);
+
+        env.events()
+            .publish((symbol_short!("transfer"),), (from, to, amount));
+
+        Ok(())
+    }
+
+    /// Get a holder's share balance, excluding shares waiting in the queue
+    pub fn balance(env: Env, id: Address) -> i128 {
+        read_balance(&env, &id)
+    }
+
+    /// Get the total shares outstanding, including queued shares
+    pub fn total_supply(env: Env) -> i128 {
+        total_shares(&env)
+    }
+
+    /// Get the pool's idle liquidity plus principal lent out
+    pub fn total_assets(env: Env) -> Result<i128, ContractError> {
+        total_assets(&env)
+    }
+
+    /// Get the liquidity that can be lent, net of queued withdrawals
+    pub fn available_liquidity(env: Env) -> Result<i128, ContractError> {
+        available_liquidity(&env)
+    }
+
+    /// Get the value of `PRICE_SCALE` shares in the pool asset
+    pub fn share_price(env: Env) -> Result<i128, ContractError> {
+        let supply = total_shares(&env);
+        if supply == 0 {
+            return Ok(PRICE_SCALE);
+        }
+        Ok(total_assets(&env)? * PRICE_SCALE / supply)
+    }
+
+    /// Get the share of pool assets lent out, in basis points
+    pub fn utilization(env: Env) -> Result<u32, ContractError> {
+        Ok(utilization_of(total_borrowed(&env), total_assets(&env)?))
+    }
+
+    /// Get the current borrowing rate in basis points
+    pub fn borrow_rate(env: Env) -> Result<u32, ContractError> {
+        Ok(rate_at(
+            &read_rate_model(&env),
+            Self::utilization(env.clone())?,
+        ))
+    }
+
+    /// Get the pool's position in a loan
+    pub fn get_position(env: Env, loan_id: u64) -> Option<LoanPosition> {
+        env.storage().persistent().get(&position_key(loan_id))
+    }
+
+    /// Get a queued withdrawal
+    pub fn get_withdrawal(env: Env, request_id: u64) -> Option<WithdrawalRequest> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("withdraw"), request_id))
+    }
+
+    /// Get the total shares waiting in the withdrawal queue
+    pub fn get_queued_shares(env: Env) -> i128 {
+        queued_shares(&env)
+    }
+}
+
+fn asset_client(env: &Env) -> Result<token::Client<'_>, ContractError> {
+    let asset: Address = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("asset"))
+        .ok_or(ContractError::NotInitialized)?;
+    Ok(token::Client::new(env, &asset))
+}
+
+fn require_loan_manager(env: &Env) -> Result<(), ContractError> {
+    let loan_manager: Address = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("loan_mgr"))
+        .ok_or(ContractError::NotInitialized)?;
+    loan_manager.require_auth();
+    Ok(())
+}
+
+fn read_rate_model(env: &Env) -> RateModel {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("rate_mdl"))
+        .unwrap_or(RateModel {
+            base_rate: 200,
+            slope_low: 800,
+            slope_high: 7500,
+            optimal_utilization: 8000,
+        })
+}
+
+/// Rate on the curve at a utilization in basis points
+fn rate_at(model: &RateModel, utilization: u32) -> u32 {
+    let utilization = utilization.min(10000) as u64;
+    let optimal = model.optimal_utilization as u64;
+    let rate = if utilization <= optimal {
+        model.base_rate as u64 + model.slope_low as u64 * utilization / optimal
+    } else {
+        model.base_rate as u64
+            + model.slope_low as u64
+            + model.slope_high as u64 * (utilization - optimal) / (10000 - optimal)
+    };
+    rate as u32
+}
+
+fn utilization_of(borrowed: i128, assets: i128) -> u32 {
+    if assets <= 0 {
+        return 0;
+    }
+    (borrowed * 10000 / assets) as u32
+}
+
+fn read_balance(env: &Env, holder: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("balance"), holder.clone()))
+        .unwrap_or(0)
+}
+
+fn write_balance(env: &Env, holder: &Address, shares: i128) {
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("balance"), holder.clone()), &shares);
+}
+
+fn total_shares(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("shares"))
+        .unwrap_or(0)
+}
+
+fn queued_shares(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("queued"))
+        .unwrap_or(0)
+}
+
+fn total_borrowed(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("borrowed"))
+        .unwrap_or(0)
+}
+
+fn idle_liquidity(env: &Env) -> Result<i128, ContractError> {
+    Ok(asset_client(env)?.balance(&env.current_contract_address()))
+}
+
+fn total_assets(env: &Env) -> Result<i128, ContractError> {
+    Ok(idle_liquidity(env)? + total_borrowed(env))
+}
+
+/// Idle liquidity less the value owed to queued withdrawals
+fn available_liquidity(env: &Env) -> Result<i128, ContractError> {
+    let supply = total_shares(env);
+    let owed = if supply == 0 {
+        0
+    } else {
+        queued_shares(env) * total_assets(env)? / supply
+    };
+    Ok(cmp::max(idle_liquidity(env)? - owed, 0))
+}
+
+/// Shares whose value idle liquidity can pay at the current share price
+fn redeemable_shares(env: &Env) -> Result<i128, ContractError> {
+    let assets = total_assets(env)?;
+    if assets <= 0 {
+        return Ok(0);
+    }
+    Ok(idle_liquidity(env)? * total_shares(env) / assets)
+}
+
+/// Burn shares already taken from their holder and pay out their value
+fn redeem(env: &Env, provider: &Address, shares: i128) -> Result<i128, ContractError> {
+    if shares <= 0 {
+        return Ok(0);
+    }
+
+    let supply = total_shares(env);
+    let amount = shares * total_assets(env)? / supply;
+    env.storage()
+        .instance()
+        .set(&symbol_short!("shares"), &(supply - shares));
+    asset_client(env)?.transfer(&env.current_contract_address(), provider, &amount);
+
+    env.events().publish(
+        (symbol_short!("withdrawn"),),
+        (provider.clone(), shares, amount),
+    );
+
+    Ok(amount)
+}
+
+fn queue_bounds(env: &Env) -> (u64, u64) {
+    let head: u64 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("wq_head"))
+        .unwrap_or(0);
+    let tail: u64 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("wq_tail"))
+        .unwrap_or(0);
+    (head, tail)
+}
+
+/// Pay queued withdrawals oldest first until liquidity or the batch runs out
+fn fill_withdrawals(env: &Env) -> Result<i128, ContractError> {
+    let (mut head, tail) = queue_bounds(env);
+    let mut paid = 0;
+    let mut filled = 0;
+
+    while head < tail && filled < MAX_QUEUE_BATCH {
+        let key = (symbol_short!("withdraw"), head);
+        let mut request: WithdrawalRequest = match env.storage().persistent().get(&key) {
+            Some(request) => request,
+            None => break,
+        };
+
+        let shares = cmp::min(request.shares, redeemable_shares(env)?);
+        if shares <= 0 {
+            break;
+        }
+        paid += redeem(env, &request.provider, shares)?;
+        env.storage()
+            .instance()
+            .set(&symbol_short!("queued"), &(queued_shares(env) - shares));
+
+        request.shares -= shares;
+        if request.shares > 0 {
+            env.storage().persistent().set(&key, &request);
+            break;
+        }
+        env.storage().persistent().remove(&key);
+        head += 1;
+        filled += 1;
+    }
+
+    env.storage()
+        .instance()
+        .set(&symbol_short!("wq_head"), &head);
+
+    Ok(paid)
+}
+
+fn read_position(env: &Env, loan_id: u64) -> Result<LoanPosition, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&position_key(loan_id))
+        .ok_or(ContractError::PositionNotFound)
+}
+
+fn position_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("position"), loan_id)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::{token, Address, Env};
+
+    struct PoolSetup<'a> {
+        pool: LendingPoolClient<'a>,
+        asset: token::Client<'a>,
+        loan_manager: Address,
+    }
+
+    fn setup_pool(env: &Env) -> PoolSetup<'_> {
+        env.mock_all_auths();
+
+        let asset = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        let loan_manager = Address::generate(env);
+        let pool = LendingPoolClient::new(env, &env.register(LendingPool, ()));
+        pool.initialize(
+            &Address::generate(env),
+            &asset,
+            &Address::generate(env),
+            &loan_manager,
+        );
+
+        PoolSetup {
+            pool,
+            asset: token::Client::new(env, &asset),
+            loan_manager,
+        }
+    }
+
+    fn deposit(env: &Env, setup: &PoolSetup, provider: &Address, amount: i128) -> i128 {
+        token::StellarAssetClient::new(env, &setup.asset.address).mint(provider, &amount);
+        setup.pool.deposit(provider, &amount)
+    }
+
+    #[test]
+    fn test_initialize_twice_rejected() {
+        let env = Env::default();
+        let setup = setup_pool(&env);
+        assert_eq!(setup.pool.get_asset(), setup.asset.address);
+        assert_eq!(setup.pool.get_servicer(), None);
+
+        let servicer = Address::generate(&env);
+        setup.pool.set_servicer(&servicer);
+        assert_eq!(setup.pool.get_servicer(), Some(servicer));
+
+        assert_eq!(
+            setup.pool.try_initialize(
+                &Address::generate(&env),
+                &setup.asset.address,
+                &Address::generate(&env),
+                &setup.loan_manager,
+            ),
+            Err(Ok(ContractError::AlreadyInitialized))
+        );
+    }
+
+    #[test]
+    fn test_interest_raises_share_price() {
+        let env = Env::default();
+        let setup = setup_pool(&env);
+        let early = Address::generate(&env);
+        let late = Address::generate(&env);
+        let borrower = Address::generate(&env);
+
+        assert_eq!(deposit(&env, &setup, &early, 100_000), 100_000);
+
+        setup.pool.draw(&1, &borrower, &50_000);
+        assert_eq!(setup.asset.balance(&borrower), 50_000);
+        assert_eq!(setup.pool.utilization(), 5000);
+
+        // 50,000 principal and 10,000 interest come back
+        token::StellarAssetClient::new(&env, &setup.asset.address).mint(&borrower, &10_000);
+        setup
+            .asset
+            .transfer(&borrower, &setup.pool.address, &60_000);
+        setup.pool.repay(&1, &50_000);
+        assert_eq!(setup.pool.get_position(&1).unwrap().outstanding, 0);
+        assert_eq!(setup.pool.total_assets(), 110_000);
+        assert_eq!(setup.pool.share_price(), PRICE_SCALE * 11 / 10);
+
+        // Later providers buy in at the higher price
+        assert_eq!(deposit(&env, &setup, &late, 11_000), 10_000);
+        assert_eq!(setup.pool.withdraw(&early, &100_000), 110_000);
+        assert_eq!(setup.pool.total_supply(), 10_000);
+    }
+
+    #[test]
+    fn test_rate_follows_kinked_curve() {
+        let env = Env::default();
+        let setup = setup_pool(&env);
+        let provider = Address::generate(&env);
+        let borrower = Address::generate(&env);
+
+        setup.pool.set_rate_model(&RateModel {
+            base_rate: 100,
+            slope_low: 400,
+            slope_high: 6000,
+            optimal_utilization: 8000,
+        });
+        deposit(&env, &setup, &provider, 100_000);
+        assert_eq!(setup.pool.borrow_rate(), 100);
+
+        // Half of the optimal utilization: base plus half the low slope
+        assert_eq!(setup.pool.draw(&1, &borrower, &40_000), 300);
+        assert_eq!(setup.pool.get_position(&1).unwrap().rate, 300);
+
+        // Past the kink the steep slope applies
+        assert_eq!(setup.pool.draw(&2, &borrower, &50_000), 100 + 400 + 3000);
+        assert_eq!(setup.pool.borrow_rate(), 3500);
+    }
+
+    #[test]
+    fn test_invalid_rate_model_rejected() {
+        let env = Env::default();
+        let setup = setup_pool(&env);
+
+        assert_eq!(
+            setup.pool.try_set_rate_model(&RateModel {
+                base_rate: 100,
+                slope_low: 400,
+                slope_high: 6000,
+                optimal_utilization: 10000,
+            }),
+            Err(Ok(ContractError::InvalidRateModel))
+        );
+    }
+
+    #[test]
+    fn test_withdrawal_queue_filled_as_liquidity_returns() {
+        let env = Env::default();
+        let setup = setup_pool(&env);
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+        let borrower = Address::generate(&env);
+
+        deposit(&env, &setup, &first, 60_000);
+        deposit(&env, &setup, &second, 40_000);
+        setup.pool.draw(&1, &borrower, &80_000);
+
+        // Only 20,000 is idle; the rest of the first withdrawal is queued
+        assert_eq!(setup.pool.withdraw(&first, &60_000), 20_000);
+        assert_eq!(setup.pool.balance(&first), 0);
+        assert_eq!(setup.pool.get_queued_shares(), 40_000);
+
+        // A later request waits behind it, and queued value cannot be lent
+        assert_eq!(setup.pool.withdraw(&second, &10_000), 0);
+        assert_eq!(setup.pool.get_withdrawal(&1).unwrap().shares, 10_000);
+        assert_eq!(
+            setup.pool.try_draw(&2, &borrower, &1),
+            Err(Ok(ContractError::InsufficientLiquidity))
+        );
+
+        // A partial repayment pays the oldest request first
+        setup
+            .asset
+            .transfer(&borrower, &setup.pool.address, &45_000);
+        setup.pool.repay(&1, &45_000);
+        assert_eq!(setup.asset.balance(&first), 60_000);
+        assert_eq!(setup.asset.balance(&second), 5_000);
+        assert_eq!(setup.pool.get_withdrawal(&0), None);
+        assert_eq!(setup.pool.get_withdrawal(&1).unwrap().shares, 5_000);
+        assert_eq!(setup.pool.get_queued_shares(), 5_000);
+    }
+
+    #[test]
+    fn test_write_off_lowers_share_price() {
+        let env = Env::default();
+        let setup = setup_pool(&env);
+        let provider = Address::generate(&env);
+        let borrower = Address::generate(&env);
+
+        deposit(&env, &setup, &provider, 100_000);
+        setup.pool.draw(&1, &borrower, &40_000);
+        setup.pool.write_off(&1, &40_000);
+
+        let position = setup.pool.get_position(&1).unwrap();
+        assert_eq!(position.outstanding, 0);
+        assert_eq!(position.written_off, 40_000);
+        assert_eq!(setup.pool.share_price(), PRICE_SCALE * 6 / 10);
+
+        // Recoveries after the write-off are earnings
+        setup
+            .asset
+            .transfer(&borrower, &setup.pool.address, &10_000);
+        setup.pool.repay(&1, &10_000);
+        assert_eq!(setup.pool.total_assets(), 70_000);
+    }
+}
@@ -1,6 +1,7 @@
 //! Loan portfolio analytics module
 
-use soroban_sdk::{contracttype, String};
+use crate::loan::{CollateralType, Loan, LoanStatus};
+use soroban_sdk::{contracttype, Address, String};
 
 /// Portfolio analytics
 #[contracttype]
@@ -17,6 +18,163 @@ pub struct PortfolioAnalytics {
     pub portfolio_ltv: u32,
 }
 
+/// Scope a running portfolio aggregate is kept for
+#[contracttype]
+#[derive(Clone)]
+pub enum PortfolioScope {
+    All,
+    Borrower(Address),
+    CollateralType(CollateralType),
+}
+
+/// Running portfolio totals, updated whenever a loan is stored
+#[contracttype]
+#[derive(Clone)]
+pub struct PortfolioAggregate {
+    pub total_loans: u64,
+    pub total_principal: i128,
+    pub total_outstanding: i128, // funded loans not yet paid or closed
+    pub total_interest_paid: i128,
+    pub total_collateral_value: i128, // collateral behind the outstanding balance
+    pub rate_weighted_outstanding: i128, // sum of interest rate x outstanding balance
+    pub pending_loans: u64,
+    pub active_loans: u64,
+    pub restructured_loans: u64,
+    pub defaulted_loans: u64,
+    pub paid_loans: u64,
+    pub closed_loans: u64,
+    pub funded_loans: u64,           // loans ever funded
+    pub default_count: u64,          // defaults ever recorded
+    pub defaulted_exposure: i128,    // outstanding balance at default
+    pub defaulted_outstanding: i128, // balance of loans currently in default
+    pub recovered_amount: i128,
+}
+
+impl PortfolioAggregate {
+    pub fn new() -> Self {
+        PortfolioAggregate {
+            total_loans: 0,
+            total_principal: 0,
+            total_outstanding: 0,
+            total_interest_paid: 0,
+            total_collateral_value: 0,
+            rate_weighted_outstanding: 0,
+            pending_loans: 0,
+            active_loans: 0,
+            restructured_loans: 0,
+            defaulted_loans: 0,
+            paid_loans: 0,
+            closed_loans: 0,
+            funded_loans: 0,
+            default_count: 0,
+            defaulted_exposure: 0,
+            defaulted_outstanding: 0,
+            recovered_amount: 0,
+        }
+    }
+
+    /// Replace a loan's previous contribution with its current one
+    pub fn update(&mut self, previous: Option<&Loan>, loan: &Loan) {
+        match previous {
+            Some(previous) => {
+                self.apply(previous, -1);
+                if previous.status == LoanStatus::Pending && loan.status != LoanStatus::Pending {
+                    self.funded_loans += 1;
+                }
+                if previous.status != LoanStatus::Defaulted && loan.status == LoanStatus::Defaulted
+                {
+                    self.default_count += 1;
+                    self.defaulted_exposure += loan.outstanding_balance;
+                }
+            }
+            None => self.total_loans += 1,
+        }
+        self.apply(loan, 1);
+    }
+
+    fn apply(&mut self, loan: &Loan, sign: i128) {
+        self.total_principal += sign * loan.principal;
+        self.total_interest_paid += sign * loan.total_interest_paid;
+        self.recovered_amount += sign * loan.recovered_amount;
+
+        if matches!(
+            loan.status,
+            LoanStatus::Active | LoanStatus::Restructured | LoanStatus::Defaulted
+        ) {
+            self.total_outstanding += sign * loan.outstanding_balance;
+            self.total_collateral_value += sign * loan.collateral_value;
+            self.rate_weighted_outstanding +=
+                sign * loan.interest_rate as i128 * loan.outstanding_balance;
+        }
+        if loan.status == LoanStatus::Defaulted {
+            self.defaulted_outstanding += sign * loan.outstanding_balance;
+        }
+
+        let count = match loan.status {
+            LoanStatus::Pending => &mut self.pending_loans,
+            LoanStatus::Active => &mut self.active_loans,
+            LoanStatus::Restructured => &mut self.restructured_loans,
+            LoanStatus::Defaulted => &mut self.defaulted_loans,
+            LoanStatus::Paid => &mut self.paid_loans,
+            LoanStatus::Closed => &mut self.closed_loans,
+            LoanStatus::Suspended => return,
+        };
+        *count = if sign > 0 { *count + 1 } else { *count - 1 };
+    }
+
+    pub fn analytics(&self) -> PortfolioAnalytics {
+        PortfolioAnalytics {
+            total_loans: self.total_loans,
+            total_principal: self.total_principal,
+            total_outstanding: self.total_outstanding,
+            total_interest_paid: self.total_interest_paid,
+            active_loans: self.active_loans + self.restructured_loans,
+            defaulted_loans: self.defaulted_loans,
+            paid_loans: self.paid_loans,
+            average_interest_rate: ratio(self.rate_weighted_outstanding, self.total_outstanding),
+            portfolio_ltv: ratio(self.total_outstanding * 10000, self.total_collateral_value),
+        }
+    }
+
+    /// Risk metrics in basis points: default probability is defaults per
+    /// funded loan, loss given default the unrecovered share of defaulted
+    /// exposure, and value at risk the balance currently in default
+    pub fn risk_metrics(&self) -> RiskMetrics {
+        let default_probability = ratio(
+            self.default_count as i128 * 10000,
+            self.funded_loans as i128,
+        );
+        let loss_given_default = ratio(
+            (self.defaulted_exposure - self.recovered_amount).max(0) * 10000,
+            self.defaulted_exposure,
+        );
+        let portfolio_risk_score =
+            (default_probability as u64 * loss_given_default as u64 / 10000) as u32;
+
+        RiskMetrics {
+            portfolio_risk_score,
+            default_probability,
+            loss_given_default,
+            expected_loss: self.total_outstanding * portfolio_risk_score as i128 / 10000,
+            value_at_risk: self.defaulted_outstanding,
+        }
+    }
+}
+
+impl Default for PortfolioAggregate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `numerator / denominator` as a u32, or 0 when the denominator is empty
+fn ratio(numerator: i128, denominator: i128) -> u32 {
+    if denominator <= 0 {
+        return 0;
+    }
+    (numerator / denominator).clamp(0, u32::MAX as i128) as u32
+}
+
 /// Loan performance metrics
 #[contracttype]
 #[derive(Clone)]
@@ -30,6 +188,32 @@ pub struct PerformanceMetrics {
     pub delinquency_rate: u32,
 }
 
+impl PerformanceMetrics {
+    pub fn new(loan_id: u64) -> Self {
+        PerformanceMetrics {
+            loan_id,
+            on_time_payments: 0,
+            late_payments: 0,
+            missed_payments: 0,
+            payment_history_score: 10000,
+            days_past_due: 0,
+            delinquency_rate: 0,
+        }
+    }
+
+    /// Recompute the score and delinquency rate from the payment counts
+    pub fn refresh(&mut self) {
+        let total = (self.on_time_payments + self.late_payments + self.missed_payments) as i128;
+        if total > 0 {
+            self.payment_history_score = ratio(self.on_time_payments as i128 * 10000, total);
+            self.delinquency_rate = ratio(
+                (self.late_payments + self.missed_payments) as i128 * 10000,
+                total,
+            );
+        }
+    }
+}
+
 /// Risk metrics
 #[contracttype]
 #[derive(Clone)]
@@ -0,0 +1,62 @@
+//! Revolving credit line data structures and types
+
+use soroban_sdk::{contracttype, Address};
+
+/// Collateral securing a credit line
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CreditSecurity {
+    Collateral(u64),
+    Basket(u64),
+}
+
+/// Credit line status
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CreditLineStatus {
+    Pending = 0, // awaiting a lender's commitment
+    Open = 1,
+    Frozen = 2, // no new draws until coverage and covenants recover
+    Closed = 3,
+    Defaulted = 4, // still owed after maturity; the lender may seize the security
+}
+
+/// Revolving credit facility
+///
+/// The committed limit is held by the contract; draws and principal
+/// repayments move funds between the facility and the borrower.
+#[contracttype]
+#[derive(Clone)]
+pub struct CreditLine {
+    pub id: u64,
+    pub borrower: Address,
+    pub lender: Option<Address>, // set when the limit is committed
+    pub asset: Address,
+    pub security: CreditSecurity,
+    pub limit: i128,
+    pub drawn: i128,
+    pub interest_rate: u32,       // basis points, charged on the drawn balance
+    pub commitment_fee_rate: u32, // basis points, charged on the undrawn limit
+    pub accrued_interest: i128,
+    pub accrued_fees: i128,
+    pub total_interest_paid: i128,
+    pub total_fees_paid: i128,
+    pub term: u64, // in seconds
+    pub maturity_date: u64,
+    pub grace_period: u64, // after maturity, before an unpaid line defaults
+    pub last_accrual: u64,
+    pub status: CreditLineStatus,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+/// Basket valuation snapshot (from CollateralRegistry)
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BasketValuation {
+    pub basket_id: u64,
+    pub gross_value: i128,
+    pub adjusted_value: i128,
+    pub verified: bool,
+    pub oldest_valuation: u64,
+}
@@ -22,6 +22,8 @@ pub struct RecoveryPlan {
     pub created_date: u64,
     pub recovery_strategy: RecoveryStrategy,
     pub status: RecoveryStatus,
+    pub target_amount: i128, // amount the borrower commits to repay
+    pub deadline: u64,
 }
 
 /// Recovery strategy
@@ -92,6 +94,26 @@ pub enum SeizureStatus {
     Disputed = 4,
 }
 
+/// Debt covered by liquidations of an undercollateralized loan
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanLiquidation {
+    pub loan_id: u64,
+    pub debt_covered: i128, // cumulative across liquidations
+    pub liquidation_count: u32,
+    pub outstanding_balance: i128,
+    pub status: LiquidationStatus,
+    pub last_liquidated_at: u64,
+}
+
+/// Liquidation status
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LiquidationStatus {
+    Partial = 0,
+    Closed = 1,
+}
+
 /// Default fee
 #[contracttype]
 #[derive(Clone)]
@@ -34,3 +34,227 @@ pub struct InterestRateAdjustment {
     pub effective_date: u64,
     pub reason: String,
 }
+
+/// Reset terms for `InterestType::Floating` loans
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FloatingRateTerms {
+    pub loan_id: u64,
+    pub rate_floor: u32,   // basis points
+    pub rate_cap: u32,     // basis points
+    pub reset_period: u64, // in seconds
+    pub next_reset: u64,   // set when the loan is funded
+}
+
+/// Compounding frequency for `InterestType::Compound` loans
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Compounding {
+    Daily = 0,
+    Monthly = 1,
+    Continuous = 2,
+}
+
+/// Day-count convention used to turn an accrual window into a year fraction
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DayCountConvention {
+    Act365 = 0,
+    Act360 = 1,
+    Thirty360 = 2,
+}
+
+/// Fixed-point scale (1e12) used for growth factors
+pub const SCALE: i128 = 1_000_000_000_000;
+
+const SECONDS_PER_DAY: u64 = 86400;
+const BPS: i128 = 10000;
+
+/// Accrual window `[start, end)` expressed as `(elapsed, year)` seconds under
+/// the given convention; the year fraction is `elapsed / year`.
+pub fn accrual_seconds(convention: DayCountConvention, start: u64, end: u64) -> (u64, u64) {
+    let end = end.max(start);
+    match convention {
+        DayCountConvention::Act365 => (end - start, 365 * SECONDS_PER_DAY),
+        DayCountConvention::Act360 => (end - start, 360 * SECONDS_PER_DAY),
+        DayCountConvention::Thirty360 => {
+            let days = thirty_360_days(start, end);
+            let start_tod = (start % SECONDS_PER_DAY) as i64;
+            let end_tod = (end % SECONDS_PER_DAY) as i64;
+            let elapsed = days * SECONDS_PER_DAY as i64 + end_tod - start_tod;
+            (elapsed.max(0) as u64, 360 * SECONDS_PER_DAY)
+        }
+    }
+}
+
+/// Simple interest: `P * r * t`
+pub fn simple_interest(principal: i128, annual_rate: u32, elapsed: u64, year: u64) -> Option<i128> {
+    let rate_time = (annual_rate as i128).checked_mul(elapsed as i128)?;
+    mul_div(principal, rate_time, BPS.checked_mul(year as i128)?)
+}
+
+/// Compound interest: `P * ((1 + r/n)^(n*t) - 1)`, or `P * (e^(r*t) - 1)` for
+/// continuous compounding.
+///
+/// The result is never below simple interest over the same window, which
+/// fixed-point truncation could otherwise produce for windows shorter than
+/// one period.
+pub fn compound_interest(
+    principal: i128,
+    annual_rate: u32,
+    elapsed: u64,
+    year: u64,
+    compounding: Compounding,
+) -> Option<i128> {
+    let factor = growth_factor(annual_rate, elapsed, year, compounding)?;
+    let compound = mul_fixed(principal, factor - SCALE)?;
+    let simple = simple_interest(principal, annual_rate, elapsed, year)?;
+    Some(compound.max(simple))
+}
+
+/// Growth factor `(1 + r/n)^(n*t)`, or `e^(r*t)` for continuous compounding,
+/// scaled by `SCALE`. Partial compounding periods accrue linearly.
+pub fn growth_factor(
+    annual_rate: u32,
+    elapsed: u64,
+    year: u64,
+    compounding: Compounding,
+) -> Option<i128> {
+    match compounding {
+        Compounding::Continuous => {
+            // x = r * t
+            let x = mul_div(
+                SCALE,
+                annual_rate as i128 * elapsed as i128,
+                BPS * year as i128,
+            )?;
+            exp_fixed(x)
+        }
+        Compounding::Daily | Compounding::Monthly => {
+            let periods_per_year: i128 = match compounding {
+                Compounding::Monthly => 12,
+                _ => (year / SECONDS_PER_DAY) as i128,
+            };
+            let periods = mul_div(SCALE, elapsed as i128 * periods_per_year, year as i128)?;
+            let rate_per_period = mul_div(SCALE, annual_rate as i128, BPS * periods_per_year)?;
+            let whole = (periods / SCALE) as u64;
+            let partial = periods % SCALE;
+
+            let grown = pow_fixed(SCALE + rate_per_period, whole)?;
+            mul_fixed(grown, SCALE + mul_fixed(rate_per_period, partial)?)
+        }
+    }
+}
+
+/// Level installment paying off `principal` over `installments` periods of
+/// `elapsed / year` years each: `P * r * g / (g - 1)` with `g = (1 + r)^n`
+pub fn annuity_payment(
+    principal: i128,
+    annual_rate: u32,
+    elapsed: u64,
+    year: u64,
+    installments: u32,
+) -> Option<i128> {
+    if installments == 0 {
+        return None;
+    }
+    let rate = mul_div(
+        SCALE,
+        annual_rate as i128 * elapsed as i128,
+        BPS * year as i128,
+    )?;
+    if rate == 0 {
+        return Some((principal + installments as i128 - 1) / installments as i128);
+    }
+    let growth = pow_fixed(SCALE + rate, installments as u64)?;
+    mul_div(principal, mul_fixed(rate, growth)?, growth - SCALE)
+}
+
+/// `a * b / c` without overflowing the intermediate product
+fn mul_div(a: i128, b: i128, c: i128) -> Option<i128> {
+    if c == 0 {
+        return None;
+    }
+    let whole = (a / c).checked_mul(b)?;
+    let rest = (a % c).checked_mul(b)? / c;
+    whole.checked_add(rest)
+}
+
+/// `a * b` in fixed point (both operands non-negative), splitting each operand
+/// into whole and fractional parts so large growth factors stay in range
+fn mul_fixed(a: i128, b: i128) -> Option<i128> {
+    let (a_whole, a_frac) = (a / SCALE, a % SCALE);
+    let (b_whole, b_frac) = (b / SCALE, b % SCALE);
+    a_whole
+        .checked_mul(b)?
+        .checked_add(a_frac.checked_mul(b_whole)?)?
+        .checked_add(a_frac * b_frac / SCALE)
+}
+
+/// `base^exp` in fixed point, by squaring
+fn pow_fixed(mut base: i128, mut exp: u64) -> Option<i128> {
+    let mut result = SCALE;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_fixed(result, base)?;
+        }
+        exp >>= 1;
+        if exp > 0 {
+            base = mul_fixed(base, base)?;
+        }
+    }
+    Some(result)
+}
+
+/// `e^x` in fixed point for `x >= 0`
+///
+/// `x` is halved until it is below 1/2, expanded as a Taylor series and then
+/// squared back up.
+fn exp_fixed(x: i128) -> Option<i128> {
+    let mut reduced = x;
+    let mut halvings = 0u32;
+    while reduced > SCALE / 2 {
+        reduced /= 2;
+        halvings += 1;
+    }
+
+    let mut sum = SCALE;
+    let mut term = SCALE;
+    let mut n = 1;
+    while term > 0 {
+        term = mul_fixed(term, reduced)? / n;
+        sum += term;
+        n += 1;
+    }
+
+    for _ in 0..halvings {
+        sum = mul_fixed(sum, sum)?;
+    }
+    Some(sum)
+}
+
+/// Day count between two timestamps under the 30/360 (bond basis) convention
+fn thirty_360_days(start: u64, end: u64) -> i64 {
+    let (y1, m1, d1) = civil_date(start);
+    let (y2, m2, d2) = civil_date(end);
+
+    let d1 = d1.min(30);
+    let d2 = if d2 == 31 && d1 == 30 { 30 } else { d2 };
+
+    360 * (y2 - y1) + 30 * (m2 - m1) + (d2 - d1)
+}
+
+/// Convert a Unix timestamp to a (year, month, day) civil date
+fn civil_date(timestamp: u64) -> (i64, i64, i64) {
+    let days = (timestamp / SECONDS_PER_DAY) as i64 + 719468;
+    let era = days / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
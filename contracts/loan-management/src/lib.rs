@@ -2,28 +2,60 @@
 //!
 //! This contract handles the complete loan lifecycle:
 //! - Loan origination with collateral validation
+//! - Token disbursement to borrowers and repayment routing to lenders
 //! - Interest calculation with multiple compounding methods
 //! - Repayment processing with partial payment support
 //! - Default detection and handling
 //! - Loan restructuring and modification
+//! - Revolving credit lines with commitment fees
 //! - Performance tracking and analytics
 //! - Risk-based pricing integration
 
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 
 use core::cmp;
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Vec};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, Address, Env, IntoVal, String,
+    Symbol, Val, Vec,
+};
 
-mod analytics;
-mod default_handling;
-mod interest;
-mod loan;
-mod repayment;
-mod restructuring;
+pub mod analytics;
+pub mod credit_line;
+pub mod default_handling;
+pub mod interest;
+pub mod loan;
+pub mod repayment;
+pub mod restructuring;
 
-use analytics::PortfolioAnalytics;
-use loan::{InterestType, Loan, LoanRestructuring, LoanStatus};
-use repayment::RepaymentRecord;
+use analytics::{
+    PerformanceMetrics, PortfolioAggregate, PortfolioAnalytics, PortfolioScope, RiskMetrics,
+};
+use credit_line::{BasketValuation, CreditLine, CreditLineStatus, CreditSecurity};
+use default_handling::{
+    CollateralSeizure, DefaultEvent, DefaultFee, ForbearanceAgreement, ForbearanceStatus,
+    LiquidationStatus, LoanLiquidation, RecoveryPlan, RecoveryStatus, RecoveryStrategy,
+    SeizureStatus,
+};
+use interest::{
+    accrual_seconds, annuity_payment, compound_interest, simple_interest, Compounding,
+    DayCountConvention, FloatingRateTerms, InterestRateAdjustment, SCALE,
+};
+use loan::{
+    BreachAction, CollateralInsuranceStatus, CollateralType, CollateralValuation, CovenantStatus,
+    CovenantType, CrossCurrencyLoan, FxRate, HedgeStatus, InsuranceStatus, InterestType, Loan,
+    LoanCovenant, LoanFees, LoanInsurance, LoanPortfolio, LoanRestructuring, LoanSecuritization,
+    LoanStatus, LoanSyndication, MarginCall, MarginCallStatus, ReputationAction,
+    ReputationBehavior, ReputationOutcome, SecuritizationStatus, SyndicateShare, SyndicationStatus,
+    Tranche, TrancheClass, TranchePosition, DEFAULT_GRACE_PERIOD, FX_RATE_SCALE,
+};
+use repayment::{
+    Installment, InstallmentStatus, RepaymentProfile, RepaymentRecord, RepaymentSchedule,
+    ScheduleStatus, INSTALLMENT_INTERVAL,
+};
+use restructuring::{
+    ProposalStatus, RestructuringHistory, RestructuringProposal, RestructuringTerms, ScheduleChange,
+};
 
 /// Contract errors
 #[contracttype]
@@ -54,6 +86,31 @@ pub enum ContractError {
     InvalidGracePeriod = 23,
     LoanInDefault = 24,
     InvalidPrepaymentAmount = 25,
+    LoanNotPending = 26,
+    LoanNotActive = 27,
+    CollateralNotVerified = 28,
+    StaleValuation = 29,
+    LoanNotDefaulted = 30,
+    RecoveryPlanNotFound = 31,
+    RecoveryInProgress = 32,
+    ForbearanceActive = 33,
+    SeizureNotFound = 34,
+    InvalidRecoveryTerms = 35,
+    ProposalNotFound = 36,
+    ProposalExpired = 37,
+    ProposalPending = 38,
+    SyndicationNotFound = 39,
+    InvalidSyndication = 40,
+    CommitmentShortfall = 41,
+    CovenantNotFound = 42,
+    InvalidCovenant = 43,
+    LoanNotCallable = 44,
+    CreditLineNotFound = 45,
+    CreditLineFrozen = 46,
+    CreditLimitExceeded = 47,
+    InvalidCreditLine = 48,
+    CreditLineOutstanding = 49,
+    RateResetNotDue = 50,
 }
 
 impl From<soroban_sdk::Error> for ContractError {
@@ -120,6 +177,15 @@ impl LoanContract {
         env.storage()
             .instance()
             .set(&symbol_short!("max_term"), &31536000u64); // 1 year maximum
+        env.storage()
+            .instance()
+            .set(&symbol_short!("max_vage"), &2592000u64); // 30 days maximum valuation age
+        env.storage()
+            .instance()
+            .set(&symbol_short!("mc_ltv"), &8500u32); // 85% margin call LTV
+        env.storage()
+            .instance()
+            .set(&symbol_short!("def_fee"), &200u32); // 2% default fee
 
         env.events()
             .publish((symbol_short!("loan_init"),), (admin.clone(), treasury));
@@ -127,15 +193,20 @@ impl LoanContract {
         Ok(())
     }
 
-    /// Create a new loan
+    /// Create a new loan request
+    ///
+    /// The collateral is locked with this contract as secured party and the
+    /// loan stays `Pending` until a lender funds it with `fund_loan`.
     ///
     /// # Arguments
     /// * `borrower` - Address of the borrower
     /// * `collateral_id` - ID of collateral asset
+    /// * `asset` - Token the loan is disbursed and repaid in
     /// * `principal` - Loan principal amount
     /// * `interest_rate` - Annual interest rate (basis points)
     /// * `term` - Loan term in seconds
     /// * `interest_type` - Type of interest calculation
+    /// * `repayment_profile` - How principal is spread over the installments
     ///
     /// # Returns
     /// The loan ID
@@ -146,159 +217,177 @@ impl LoanContract {
         env: Env,
         borrower: Address,
         collateral_id: u64,
+        asset: Address,
         principal: i128,
         interest_rate: u32,
         term: u64,
         interest_type: InterestType,
+        repayment_profile: RepaymentProfile,
     ) -> Result<u64, ContractError> {
         borrower.require_auth();
+        validate_loan_terms(&env, principal, interest_rate, term)?;
 
-        // Validate loan parameters
-        if principal <= 0 {
-            return Err(ContractError::InvalidLoanData);
-        }
-
-        if interest_rate > 100000 {
-            // Max 1000% annual rate
-            return Err(ContractError::InvalidInterestRate);
-        }
-
-        let min_term: u64 = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("min_term"))
-            .unwrap_or(86400);
-        let max_term: u64 = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("max_term"))
-            .unwrap_or(31536000);
-
-        if term < min_term || term > max_term {
-            return Err(ContractError::InvalidTerm);
-        }
-
-        // Validate collateral LTV
-        // Note: In production, would call collateral registry to get value
-        let collateral_value = principal * 2; // Simplified: assume 2x collateral
-        let ltv = (principal * 10000 / collateral_value) as u32;
+        let collateral_value = current_collateral_value(&env, collateral_id)?;
+        originate_loan(
+            &env,
+            borrower,
+            collateral_id,
+            asset,
+            principal,
+            interest_rate,
+            term,
+            interest_type,
+            repayment_profile,
+            collateral_value,
+        )
+    }
 
-        let min_ltv: u32 = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("min_ltv"))
-            .unwrap_or(5000);
-        let max_ltv: u32 = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("max_ltv"))
-            .unwrap_or(8000);
+    /// Create a loan whose collateral is valued in another currency
+    ///
+    /// The collateral's lendable value is converted into the loan currency at
+    /// the oracle's FX rate before the LTV check, and `check_fx_coverage`
+    /// re-converts it as the rate moves. Rates older than the maximum FX age
+    /// are rejected.
+    ///
+    /// # Arguments
+    /// * `borrower` - Address of the borrower
+    /// * `collateral_id` - ID of collateral asset
+    /// * `asset` - Token the loan is disbursed and repaid in
+    /// * `principal` - Loan principal amount
+    /// * `interest_rate` - Annual interest rate (basis points)
+    /// * `term` - Loan term in seconds
+    /// * `interest_type` - Type of interest calculation
+    /// * `repayment_profile` - How principal is spread over the installments
+    /// * `base_currency` - Currency the collateral is valued in
+    /// * `loan_currency` - Currency of the loan asset
+    ///
+    /// # Returns
+    /// The loan ID
+    ///
+    /// # Events
+    /// Emits `LoanCreated` event
+    pub fn create_cross_currency_loan(
+        env: Env,
+        borrower: Address,
+        collateral_id: u64,
+        asset: Address,
+        principal: i128,
+        interest_rate: u32,
+        term: u64,
+        interest_type: InterestType,
+        repayment_profile: RepaymentProfile,
+        base_currency: String,
+        loan_currency: String,
+    ) -> Result<u64, ContractError> {
+        borrower.require_auth();
+        validate_loan_terms(&env, principal, interest_rate, term)?;
 
-        if ltv < min_ltv || ltv > max_ltv {
-            return Err(ContractError::InsufficientCollateral);
+        if base_currency == loan_currency {
+            return Err(ContractError::InvalidCurrency);
         }
 
-        // Get next loan ID
-        let loan_id: u64 = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("nextloan"))
-            .unwrap_or(1u64);
-
-        // Create loan record
-        let loan = Loan {
-            id: loan_id,
-            borrower: borrower.clone(),
+        let fx_rate = fetch_fx_rate(&env, &base_currency, &loan_currency)?;
+        let collateral_value =
+            convert_currency(current_collateral_value(&env, collateral_id)?, fx_rate.rate)?;
+        let loan_id = originate_loan(
+            &env,
+            borrower,
+            collateral_id,
+            asset,
             principal,
-            outstanding_balance: principal,
             interest_rate,
+            term,
             interest_type,
-            collateral_id,
+            repayment_profile,
             collateral_value,
-            ltv,
-            term,
-            start_date: env.ledger().timestamp(),
-            maturity_date: env.ledger().timestamp() + term,
-            status: LoanStatus::Active,
-            total_interest_paid: 0,
-            total_repaid: 0,
-            last_payment_date: env.ledger().timestamp(),
-            next_payment_date: env.ledger().timestamp() + 86400 * 30, // 30 days
-            grace_period: 86400 * 5,                                  // 5 days grace period
-            default_date: 0,
-            created_at: env.ledger().timestamp(),
-            updated_at: env.ledger().timestamp(),
-        };
-
-        // Store loan
-        let storage_key = format_loan_storage_key(loan_id);
-        env.storage().persistent().set(&storage_key, &loan);
+        )?;
 
-        // Update next loan ID
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("next_ccy"))
+            .unwrap_or(1u64);
         env.storage()
             .instance()
-            .set(&symbol_short!("nextloan"), &(loan_id + 1));
+            .set(&symbol_short!("next_ccy"), &(id + 1));
 
-        // Emit event
-        env.events().publish(
-            (symbol_short!("loan_crea"),),
-            (loan_id, borrower, principal, interest_rate),
+        let now = env.ledger().timestamp();
+        env.storage().persistent().set(
+            &format_cross_currency_key(loan_id),
+            &CrossCurrencyLoan {
+                id,
+                loan_id,
+                base_currency,
+                loan_currency,
+                exchange_rate: fx_rate.rate,
+                hedge_status: HedgeStatus::Unhedged,
+                created_at: now,
+                updated_at: now,
+            },
         );
 
         Ok(loan_id)
     }
 
-    /// Calculate accrued interest for a loan
+    /// Fund a pending loan, disbursing the principal to the borrower
     ///
     /// # Arguments
     /// * `loan_id` - ID of the loan
-    /// * `period` - Period in seconds to calculate interest for
+    /// * `lender` - Address providing the principal
     ///
-    /// # Returns
-    /// Accrued interest amount
-    pub fn calculate_interest(env: Env, loan_id: u64, period: u64) -> Result<i128, ContractError> {
+    /// # Events
+    /// Emits `LoanFunded` event
+    pub fn fund_loan(env: Env, loan_id: u64, lender: Address) -> Result<(), ContractError> {
+        lender.require_auth();
+
         // Get loan
         let storage_key = format_loan_storage_key(loan_id);
-        let loan: Loan = env
+        let mut loan: Loan = env
             .storage()
             .persistent()
             .get(&storage_key)
             .ok_or(ContractError::LoanNotFound)?;
 
-        // Calculate interest based on type
-        let interest = match loan.interest_type {
-            InterestType::Simple => {
-                calculate_simple_interest(loan.outstanding_balance, loan.interest_rate, period)?
-            }
-            InterestType::Compound => {
-                calculate_compound_interest(loan.outstanding_balance, loan.interest_rate, period)?
-            }
-            InterestType::Fixed => {
-                calculate_fixed_interest(loan.principal, loan.interest_rate, period)?
-            }
-        };
+        if loan.status != LoanStatus::Pending {
+            return Err(ContractError::LoanNotPending);
+        }
+
+        // Syndicated loans are funded by their participants
+        if read_syndication(&env, loan_id).is_some() {
+            return Err(ContractError::InvalidSyndication);
+        }
 
-        Ok(interest)
+        // Disburse principal
+        token::Client::new(&env, &loan.asset).transfer(&lender, &loan.borrower, &loan.principal);
+
+        start_loan(&env, &mut loan, &lender)?;
+
+        env.events().publish(
+            (symbol_short!("loan_fund"),),
+            (loan_id, lender, loan.principal),
+        );
+
+        Ok(())
     }
 
-    /// Make a loan repayment
+    /// Fund a pending loan from the lending pool (borrower only)
+    ///
+    /// The pool disburses the principal, becomes lender of record and fixes
+    /// the loan's rate from its utilization curve. Repayments return to the
+    /// pool and defaults are written off against it.
     ///
     /// # Arguments
     /// * `loan_id` - ID of the loan
-    /// * `amount` - Repayment amount
-    /// * `payer` - Address of the payer
     ///
     /// # Events
-    /// Emits `RepaymentMade` event
-    pub fn make_repayment(
-        env: Env,
-        loan_id: u64,
-        amount: i128,
-        payer: Address,
-    ) -> Result<(), ContractError> {
-        payer.require_auth();
+    /// Emits `LoanFunded` event
+    pub fn fund_loan_from_pool(env: Env, loan_id: u64) -> Result<(), ContractError> {
+        let pool: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("lend_pool"))
+            .ok_or(ContractError::Unauthorized)?;
 
-        // Get loan
         let storage_key = format_loan_storage_key(loan_id);
         let mut loan: Loan = env
             .storage()
@@ -306,140 +395,206 @@ impl LoanContract {
             .get(&storage_key)
             .ok_or(ContractError::LoanNotFound)?;
 
-        // Validate loan status
-        if loan.status == LoanStatus::Defaulted {
-            return Err(ContractError::LoanInDefault);
+        loan.borrower.require_auth();
+
+        if loan.status != LoanStatus::Pending {
+            return Err(ContractError::LoanNotPending);
         }
 
-        if loan.status == LoanStatus::Paid {
-            return Err(ContractError::LoanAlreadyPaid);
+        if read_syndication(&env, loan_id).is_some() {
+            return Err(ContractError::InvalidSyndication);
         }
 
-        // Validate repayment amount
-        if amount <= 0 {
-            return Err(ContractError::InvalidRepaymentAmount);
+        // The pool prices the loan; floating terms would override it
+        if loan.interest_type == InterestType::Floating {
+            return Err(ContractError::InvalidInterestRate);
         }
 
-        // Calculate current interest
-        let time_since_last_payment = env.ledger().timestamp() - loan.last_payment_date;
-        let accrued_interest =
-            Self::calculate_interest(env.clone(), loan_id, time_since_last_payment)?;
+        require_pool_asset(&env, &pool, &loan.asset)?;
+
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                loan_id.into_val(&env),
+                loan.borrower.into_val(&env),
+                loan.principal.into_val(&env),
+            ],
+        );
+        loan.interest_rate = env.invoke_contract::<u32>(&pool, &Symbol::new(&env, "draw"), args);
 
-        // Calculate total owed
-        let total_owed = loan.outstanding_balance + accrued_interest;
+        start_loan(&env, &mut loan, &pool)?;
 
-        if amount > total_owed {
-            return Err(ContractError::RepaymentExceedsBalance);
-        }
+        env.events().publish(
+            (symbol_short!("loan_fund"),),
+            (loan_id, pool, loan.principal),
+        );
 
-        // Apply repayment: interest first, then principal.
-        let interest_payment = cmp::min(amount, accrued_interest);
-        let principal_payment = amount - interest_payment;
+        Ok(())
+    }
 
-        loan.outstanding_balance -= principal_payment;
-        loan.total_repaid += amount;
-        loan.total_interest_paid += interest_payment;
-        loan.last_payment_date = env.ledger().timestamp();
-        loan.updated_at = env.ledger().timestamp();
+    /// Open a syndicate for a pending loan (arranger only)
+    ///
+    /// The arranger invites participants, funds the loan from their accepted
+    /// commitments and acts as lender of record for the syndicate.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    /// * `arranger` - Lead lender arranging the syndicate
+    ///
+    /// # Events
+    /// Emits `SyndicationCreated` event
+    pub fn create_syndication(
+        env: Env,
+        loan_id: u64,
+        arranger: Address,
+    ) -> Result<u64, ContractError> {
+        arranger.require_auth();
 
-        // Update status
-        if loan.outstanding_balance <= 0 {
-            loan.status = LoanStatus::Paid;
-        } else {
-            loan.status = LoanStatus::Active;
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+
+        if loan.status != LoanStatus::Pending {
+            return Err(ContractError::LoanNotPending);
         }
 
-        // Store updated loan
-        env.storage().persistent().set(&storage_key, &loan);
+        if read_syndication(&env, loan_id).is_some() {
+            return Err(ContractError::InvalidSyndication);
+        }
 
-        // Record repayment
-        let repayment = RepaymentRecord {
+        let syndication_id: u64 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("next_synd"))
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("next_synd"), &(syndication_id + 1));
+
+        let syndication = LoanSyndication {
+            id: syndication_id,
             loan_id,
-            payer: payer.clone(),
-            amount,
-            principal_payment,
-            interest_payment,
-            timestamp: env.ledger().timestamp(),
+            lead_lender: arranger.clone(),
+            participants: Vec::new(&env),
+            total_amount: 0,
+            status: SyndicationStatus::Pending,
+            created_at: env.ledger().timestamp(),
         };
+        env.storage()
+            .persistent()
+            .set(&format_syndication_key(loan_id), &syndication);
 
-        let repayment_key = format_repayment_key(loan_id, env.ledger().timestamp());
-        env.storage().persistent().set(&repayment_key, &repayment);
-
-        // Emit event
         env.events().publish(
-            (symbol_short!("repaymade"),),
-            (loan_id, amount, loan.outstanding_balance),
+            (symbol_short!("synd_crt"),),
+            (loan_id, syndication_id, arranger),
         );
 
-        Ok(())
+        Ok(syndication_id)
     }
 
-    /// Check and handle loan default
+    /// Invite a participant with a commitment amount (arranger only)
+    ///
+    /// Re-inviting a participant replaces their commitment and requires them
+    /// to accept again.
     ///
     /// # Arguments
     /// * `loan_id` - ID of the loan
-    ///
-    /// # Returns
-    /// Whether loan is in default
+    /// * `participant` - Lender invited to the syndicate
+    /// * `commitment` - Amount the participant is asked to commit
     ///
     /// # Events
-    /// Emits `LoanDefaulted` event if default detected
-    pub fn check_default(env: Env, loan_id: u64) -> Result<bool, ContractError> {
-        // Get loan
-        let storage_key = format_loan_storage_key(loan_id);
-        let mut loan: Loan = env
-            .storage()
-            .persistent()
-            .get(&storage_key)
-            .ok_or(ContractError::LoanNotFound)?;
+    /// Emits `ParticipantInvited` event
+    pub fn invite_participant(
+        env: Env,
+        loan_id: u64,
+        participant: Address,
+        commitment: i128,
+    ) -> Result<(), ContractError> {
+        let mut syndication = pending_syndication(&env, loan_id)?;
+        syndication.lead_lender.require_auth();
 
-        // Check if loan is past due
-        let current_time = env.ledger().timestamp();
-        let grace_period_end = loan.next_payment_date + loan.grace_period;
+        if commitment <= 0 {
+            return Err(ContractError::InvalidSyndication);
+        }
 
-        if current_time > grace_period_end && loan.outstanding_balance > 0 {
-            // Loan is in default
-            loan.status = LoanStatus::Defaulted;
-            loan.default_date = current_time;
-            loan.updated_at = current_time;
+        if !syndication.participants.contains(&participant) {
+            syndication.participants.push_back(participant.clone());
+            env.storage()
+                .persistent()
+                .set(&format_syndication_key(loan_id), &syndication);
+        }
 
-            // Store updated loan
-            env.storage().persistent().set(&storage_key, &loan);
+        env.storage().persistent().set(
+            &format_syndicate_share_key(loan_id, &participant),
+            &SyndicateShare {
+                loan_id,
+                participant: participant.clone(),
+                commitment,
+                funded: 0,
+                accepted: false,
+            },
+        );
 
-            // Emit event
-            env.events().publish(
-                (symbol_short!("loan_def"),),
-                (loan_id, loan.outstanding_balance),
-            );
+        env.events().publish(
+            (symbol_short!("synd_inv"),),
+            (loan_id, participant, commitment),
+        );
 
-            Ok(true)
-        } else {
-            Ok(false)
-        }
+        Ok(())
     }
 
-    /// Restructure loan terms
+    /// Accept a syndicate invitation (participant only)
+    ///
+    /// The participant must approve this contract to spend its commitment in
+    /// the loan asset; funding draws from that allowance.
     ///
     /// # Arguments
     /// * `loan_id` - ID of the loan
-    /// * `new_terms` - New loan terms
+    /// * `participant` - Invited participant
     ///
     /// # Events
-    /// Emits `LoanRestructured` event
-    pub fn restructure_loan(
+    /// Emits `ParticipantAccepted` event
+    pub fn accept_participation(
         env: Env,
         loan_id: u64,
-        new_terms: LoanRestructuring,
+        participant: Address,
     ) -> Result<(), ContractError> {
-        let admin: Address = env
+        participant.require_auth();
+        pending_syndication(&env, loan_id)?;
+
+        let share_key = format_syndicate_share_key(loan_id, &participant);
+        let mut share: SyndicateShare = env
             .storage()
-            .instance()
-            .get(&symbol_short!("admin"))
-            .ok_or(ContractError::Unauthorized)?;
+            .persistent()
+            .get(&share_key)
+            .ok_or(ContractError::SyndicationNotFound)?;
+        share.accepted = true;
+        env.storage().persistent().set(&share_key, &share);
 
-        admin.require_auth();
+        env.events().publish(
+            (symbol_short!("synd_acc"),),
+            (loan_id, participant, share.commitment),
+        );
+
+        Ok(())
+    }
+
+    /// Fund a syndicated loan from accepted commitments (arranger only)
+    ///
+    /// The principal is drawn from participants pro rata to their
+    /// commitments, so an oversubscribed syndicate draws less than committed.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    ///
+    /// # Events
+    /// Emits `LoanFunded` event
+    pub fn fund_syndicated_loan(env: Env, loan_id: u64) -> Result<(), ContractError> {
+        let mut syndication = pending_syndication(&env, loan_id)?;
+        syndication.lead_lender.require_auth();
 
-        // Get loan
         let storage_key = format_loan_storage_key(loan_id);
         let mut loan: Loan = env
             .storage()
@@ -447,221 +602,5830 @@ impl LoanContract {
             .get(&storage_key)
             .ok_or(ContractError::LoanNotFound)?;
 
-        // Validate restructuring
-        if loan.status == LoanStatus::Paid {
-            return Err(ContractError::InvalidRestructuring);
+        if loan.status != LoanStatus::Pending {
+            return Err(ContractError::LoanNotPending);
         }
 
-        // Apply new terms
-        if let Some(new_rate) = new_terms.new_interest_rate {
-            if new_rate > 100000 {
-                return Err(ContractError::InvalidInterestRate);
+        // Only accepted commitments count
+        let mut shares: Vec<SyndicateShare> = Vec::new(&env);
+        let mut total_committed: i128 = 0;
+        for participant in syndication.participants.iter() {
+            let share: SyndicateShare = env
+                .storage()
+                .persistent()
+                .get(&format_syndicate_share_key(loan_id, &participant))
+                .ok_or(ContractError::SyndicationNotFound)?;
+            if share.accepted {
+                total_committed += share.commitment;
+                shares.push_back(share);
             }
-            loan.interest_rate = new_rate;
         }
 
-        if let Some(new_term) = new_terms.new_term {
-            let min_term: u64 = env
-                .storage()
-                .instance()
-                .get(&symbol_short!("min_term"))
-                .unwrap_or(86400);
-            let max_term: u64 = env
-                .storage()
-                .instance()
-                .get(&symbol_short!("max_term"))
-                .unwrap_or(31536000);
-
-            if new_term < min_term || new_term > max_term {
-                return Err(ContractError::InvalidTerm);
-            }
-            loan.maturity_date = env.ledger().timestamp() + new_term;
+        if total_committed < loan.principal {
+            return Err(ContractError::CommitmentShortfall);
         }
 
-        if let Some(new_grace) = new_terms.new_grace_period {
-            loan.grace_period = new_grace;
+        // Draw each participant's pro-rata share; the last absorbs rounding
+        let token_client = token::Client::new(&env, &loan.asset);
+        let contract = env.current_contract_address();
+        let mut participants = Vec::new(&env);
+        let mut drawn: i128 = 0;
+        let count = shares.len();
+        for (i, mut share) in shares.iter().enumerate() {
+            let amount = if i as u32 + 1 == count {
+                loan.principal - drawn
+            } else {
+                loan.principal
+                    .checked_mul(share.commitment)
+                    .ok_or(ContractError::InvalidLoanData)?
+                    / total_committed
+            };
+            token_client.transfer_from(&contract, &share.participant, &loan.borrower, &amount);
+            drawn += amount;
+
+            share.funded = amount;
+            env.storage().persistent().set(
+                &format_syndicate_share_key(loan_id, &share.participant),
+                &share,
+            );
+            participants.push_back(share.participant.clone());
         }
 
-        loan.updated_at = env.ledger().timestamp();
+        syndication.participants = participants;
+        syndication.total_amount = loan.principal;
+        syndication.status = SyndicationStatus::Active;
+        env.storage()
+            .persistent()
+            .set(&format_syndication_key(loan_id), &syndication);
 
-        // Store updated loan
-        env.storage().persistent().set(&storage_key, &loan);
+        start_loan(&env, &mut loan, &syndication.lead_lender)?;
 
-        // Emit event
-        env.events()
-            .publish((symbol_short!("loan_rest"),), (loan_id, loan.interest_rate));
+        env.events().publish(
+            (symbol_short!("loan_fund"),),
+            (loan_id, syndication.lead_lender, loan.principal),
+        );
 
         Ok(())
     }
 
-    /// Get current loan status
+    /// Sell part or all of a funded share to another lender
+    ///
+    /// Requires the seller, the buyer and the arranger's consent. The buyer
+    /// pays `price` to the seller in the loan asset.
     ///
     /// # Arguments
     /// * `loan_id` - ID of the loan
+    /// * `seller` - Participant selling
+    /// * `buyer` - Lender buying
+    /// * `amount` - Funded principal being transferred
+    /// * `price` - Price paid by the buyer
     ///
-    /// # Returns
-    /// Current loan details
-    pub fn get_loan_status(env: Env, loan_id: u64) -> Result<Loan, ContractError> {
-        let storage_key = format_loan_storage_key(loan_id);
+    /// # Events
+    /// Emits `ShareSold` event
+    pub fn sell_participation(
+        env: Env,
+        loan_id: u64,
+        seller: Address,
+        buyer: Address,
+        amount: i128,
+        price: i128,
+    ) -> Result<(), ContractError> {
+        seller.require_auth();
+        buyer.require_auth();
+
+        let mut syndication =
+            read_syndication(&env, loan_id).ok_or(ContractError::SyndicationNotFound)?;
+        syndication.lead_lender.require_auth();
+
+        if syndication.status != SyndicationStatus::Active
+            && syndication.status != SyndicationStatus::Defaulted
+        {
+            return Err(ContractError::InvalidSyndication);
+        }
+
+        let seller_key = format_syndicate_share_key(loan_id, &seller);
+        let mut seller_share: SyndicateShare = env
+            .storage()
+            .persistent()
+            .get(&seller_key)
+            .ok_or(ContractError::SyndicationNotFound)?;
+
+        if seller == buyer || amount <= 0 || amount > seller_share.funded || price < 0 {
+            return Err(ContractError::InvalidSyndication);
+        }
+
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+        if price > 0 {
+            token::Client::new(&env, &loan.asset).transfer(&buyer, &seller, &price);
+        }
+
+        seller_share.funded -= amount;
+        env.storage().persistent().set(&seller_key, &seller_share);
+
+        let buyer_key = format_syndicate_share_key(loan_id, &buyer);
+        let mut buyer_share: SyndicateShare =
+            env.storage()
+                .persistent()
+                .get(&buyer_key)
+                .unwrap_or(SyndicateShare {
+                    loan_id,
+                    participant: buyer.clone(),
+                    commitment: 0,
+                    funded: 0,
+                    accepted: true,
+                });
+        buyer_share.funded += amount;
+        env.storage().persistent().set(&buyer_key, &buyer_share);
+
+        if !syndication.participants.contains(&buyer) {
+            syndication.participants.push_back(buyer.clone());
+            env.storage()
+                .persistent()
+                .set(&format_syndication_key(loan_id), &syndication);
+        }
+
+        env.events().publish(
+            (symbol_short!("synd_sell"),),
+            (loan_id, seller, buyer, amount, price),
+        );
+
+        Ok(())
+    }
+
+    /// Get the syndicate of a loan
+    pub fn get_syndication(env: Env, loan_id: u64) -> Result<LoanSyndication, ContractError> {
+        read_syndication(&env, loan_id).ok_or(ContractError::SyndicationNotFound)
+    }
+
+    /// Get a participant's share of a syndicated loan
+    pub fn get_syndicate_share(
+        env: Env,
+        loan_id: u64,
+        participant: Address,
+    ) -> Result<SyndicateShare, ContractError> {
         env.storage()
             .persistent()
-            .get(&storage_key)
-            .ok_or(ContractError::LoanNotFound)
+            .get(&format_syndicate_share_key(loan_id, &participant))
+            .ok_or(ContractError::SyndicationNotFound)
     }
 
-    /// Get loan repayment history
+    /// Pool performing loans into a securitization (lender only)
+    ///
+    /// The issuer must be lender of every loan. The pool's outstanding balance
+    /// is split into a senior tranche of `senior_size` paying `senior_rate`
+    /// and a junior tranche taking the rest. Once both tranches are fully
+    /// subscribed the issuer receives the proceeds and the loans' collections
+    /// flow to tranche holders. Subscriptions stay open for 30 days.
     ///
     /// # Arguments
-    /// * `loan_id` - ID of the loan
+    /// * `issuer` - Lender selling the loans
+    /// * `loan_ids` - Loans to pool, all in the same asset
+    /// * `senior_size` - Face value of the senior tranche
+    /// * `senior_rate` - Senior coupon (basis points)
     ///
     /// # Returns
-    /// Vector of repayment records
-    pub fn get_repayment_history(
+    /// The securitization ID
+    ///
+    /// # Events
+    /// Emits `SecuritizationCreated` event
+    pub fn create_securitization(
         env: Env,
-        _loan_id: u64,
-    ) -> Result<Vec<RepaymentRecord>, ContractError> {
-        // Note: In production, would iterate through repayment records
-        Ok(Vec::new(&env))
+        issuer: Address,
+        loan_ids: Vec<u64>,
+        senior_size: i128,
+        senior_rate: u32,
+    ) -> Result<u64, ContractError> {
+        issuer.require_auth();
+
+        if loan_ids.is_empty() || senior_size <= 0 || senior_rate > 100000 {
+            return Err(ContractError::InvalidLoanData);
+        }
+
+        let now = env.ledger().timestamp();
+        let securitization_id: u64 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("next_sec"))
+            .unwrap_or(1u64);
+
+        // Only performing loans the issuer holds outright can be pooled
+        let mut asset: Option<Address> = None;
+        let mut total_principal: i128 = 0;
+        let mut total_outstanding: i128 = 0;
+        for loan_id in loan_ids.iter() {
+            let loan: Loan = env
+                .storage()
+                .persistent()
+                .get(&format_loan_storage_key(loan_id))
+                .ok_or(ContractError::LoanNotFound)?;
+
+            if loan.lender != Some(issuer.clone()) {
+                return Err(ContractError::Unauthorized);
+            }
+            if loan.status != LoanStatus::Active && loan.status != LoanStatus::Restructured {
+                return Err(ContractError::LoanNotActive);
+            }
+            if now > loan.next_payment_date {
+                return Err(ContractError::LoanInDefault);
+            }
+            if read_syndication(&env, loan_id).is_some()
+                || env
+                    .storage()
+                    .persistent()
+                    .has(&format_securitized_loan_key(loan_id))
+                || asset.as_ref().is_some_and(|asset| *asset != loan.asset)
+            {
+                return Err(ContractError::InvalidLoanData);
+            }
+
+            asset = Some(loan.asset.clone());
+            total_principal += loan.principal;
+            total_outstanding += loan.outstanding_balance;
+            env.storage()
+                .persistent()
+                .set(&format_securitized_loan_key(loan_id), &securitization_id);
+        }
+
+        if senior_size >= total_outstanding {
+            return Err(ContractError::InvalidLoanData);
+        }
+
+        let portfolio_id: u64 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("next_pool"))
+            .unwrap_or(1u64);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("next_pool"), &(portfolio_id + 1));
+        env.storage().persistent().set(
+            &format_loan_pool_key(portfolio_id),
+            &LoanPortfolio {
+                id: portfolio_id,
+                owner: issuer.clone(),
+                loan_ids,
+                total_principal,
+                total_outstanding,
+                created_at: now,
+                updated_at: now,
+            },
+        );
+
+        for (class, size, coupon_rate) in [
+            (TrancheClass::Senior, senior_size, senior_rate),
+            (TrancheClass::Junior, total_outstanding - senior_size, 0),
+        ] {
+            env.storage().persistent().set(
+                &format_tranche_key(securitization_id, class),
+                &Tranche {
+                    securitization_id,
+                    class,
+                    size,
+                    subscribed: 0,
+                    balance: size,
+                    coupon_rate,
+                    interest_due: 0,
+                    interest_accrued_at: now,
+                    distributed: 0,
+                    losses: 0,
+                    payout_per_unit: 0,
+                },
+            );
+        }
+
+        env.storage().persistent().set(
+            &format_securitization_key(securitization_id),
+            &LoanSecuritization {
+                id: securitization_id,
+                portfolio_id,
+                issuer: issuer.clone(),
+                asset: asset.ok_or(ContractError::InvalidLoanData)?,
+                total_value: total_outstanding,
+                tranche_count: 2,
+                status: SecuritizationStatus::Pending,
+                subscription_deadline: now + 86400 * 30,
+                created_at: now,
+                updated_at: now,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&symbol_short!("next_sec"), &(securitization_id + 1));
+
+        env.events().publish(
+            (symbol_short!("sec_crea"),),
+            (securitization_id, issuer, total_outstanding, senior_size),
+        );
+
+        Ok(securitization_id)
     }
 
-    /// Calculate early repayment amount
+    /// Buy units of a tranche at face value while the securitization is
+    /// taking subscriptions
+    ///
+    /// The final subscription activates the securitization and pays the
+    /// issuer for the pool at its live balance. Loans that stopped performing
+    /// while subscriptions were open are dropped from the pool, and any fall
+    /// in the pool balance is returned to junior holders as principal.
     ///
     /// # Arguments
-    /// * `loan_id` - ID of the loan
-    /// * `prepayment_penalty_rate` - Penalty rate for early repayment (basis points)
+    /// * `securitization_id` - ID of the securitization
+    /// * `class` - Tranche to subscribe to
+    /// * `investor` - Address paying for the units
+    /// * `amount` - Face value to buy
     ///
-    /// # Returns
-    /// Total amount needed for early repayment
-    pub fn calculate_early_repayment(
+    /// # Events
+    /// Emits `TrancheSubscribed` event, and `SecuritizationActivated` once
+    /// fully subscribed
+    pub fn subscribe_tranche(
         env: Env,
-        loan_id: u64,
-        prepayment_penalty_rate: u32,
-    ) -> Result<i128, ContractError> {
-        // Get loan
-        let storage_key = format_loan_storage_key(loan_id);
-        let loan: Loan = env
-            .storage()
+        securitization_id: u64,
+        class: TrancheClass,
+        investor: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        investor.require_auth();
+
+        let mut securitization = read_securitization(&env, securitization_id)?;
+        if securitization.status != SecuritizationStatus::Pending
+            || env.ledger().timestamp() > securitization.subscription_deadline
+        {
+            return Err(ContractError::InvalidLoanData);
+        }
+
+        let mut tranche = read_tranche(&env, securitization_id, class)?;
+        if amount <= 0 || tranche.subscribed + amount > tranche.size {
+            return Err(ContractError::InvalidLoanData);
+        }
+
+        let contract = env.current_contract_address();
+        let token = token::Client::new(&env, &securitization.asset);
+        token.transfer(&investor, &contract, &amount);
+
+        tranche.subscribed += amount;
+        env.storage()
             .persistent()
-            .get(&storage_key)
-            .ok_or(ContractError::LoanNotFound)?;
+            .set(&format_tranche_key(securitization_id, class), &tranche);
 
-        // Calculate remaining interest
-        let time_remaining = if loan.maturity_date > env.ledger().timestamp() {
-            loan.maturity_date - env.ledger().timestamp()
-        } else {
-            0
+        let mut position = read_tranche_position(&env, &tranche, &investor);
+        position.units += amount;
+        env.storage().persistent().set(
+            &format_tranche_position_key(securitization_id, class, &investor),
+            &position,
+        );
+
+        env.events().publish(
+            (symbol_short!("tr_subs"),),
+            (securitization_id, class, investor, amount),
+        );
+
+        // Fully subscribed: the issuer is paid and the pool goes live
+        let other_class = match class {
+            TrancheClass::Senior => TrancheClass::Junior,
+            TrancheClass::Junior => TrancheClass::Senior,
         };
+        let mut other = read_tranche(&env, securitization_id, other_class)?;
+        if tranche.subscribed == tranche.size && other.subscribed == other.size {
+            let now = env.ledger().timestamp();
+            let pool_value = cmp::min(
+                reprice_securitization(&env, &securitization)?,
+                securitization.total_value,
+            );
+
+            // The junior tranche gives back what the pool no longer covers;
+            // a pool below the senior tranche must be cancelled instead
+            let shortfall = securitization.total_value - pool_value;
+            let junior = match class {
+                TrancheClass::Junior => &mut tranche,
+                TrancheClass::Senior => &mut other,
+            };
+            if shortfall >= junior.size {
+                return Err(ContractError::InvalidLoanData);
+            }
+            junior.balance -= shortfall;
+            pay_tranche(junior, shortfall)?;
+
+            token.transfer(&contract, &securitization.issuer, &pool_value);
 
-        let remaining_interest = Self::calculate_interest(env, loan_id, time_remaining)?;
+            tranche.interest_accrued_at = now;
+            other.interest_accrued_at = now;
+            env.storage()
+                .persistent()
+                .set(&format_tranche_key(securitization_id, class), &tranche);
+            env.storage()
+                .persistent()
+                .set(&format_tranche_key(securitization_id, other_class), &other);
+
+            securitization.status = SecuritizationStatus::Active;
+            securitization.total_value = pool_value;
+            securitization.updated_at = now;
+            env.storage().persistent().set(
+                &format_securitization_key(securitization_id),
+                &securitization,
+            );
 
-        // Calculate penalty
-        let penalty = (loan.outstanding_balance * prepayment_penalty_rate as i128) / 10000;
+            env.events().publish(
+                (symbol_short!("sec_act"),),
+                (securitization_id, securitization.total_value),
+            );
+        }
 
-        // Total = outstanding balance + remaining interest + penalty
-        Ok(loan.outstanding_balance + remaining_interest + penalty)
+        Ok(())
     }
 
-    /// Update LTV parameters
+    /// Cancel a securitization that has not filled
+    ///
+    /// The issuer may cancel while subscriptions are open, and anyone once
+    /// the subscription deadline has passed. The loans return to the issuer
+    /// free of the pool and subscribers reclaim their payments through
+    /// `claim_tranche`.
     ///
     /// # Arguments
-    /// * `min_ltv` - Minimum LTV ratio (basis points)
-    /// * `max_ltv` - Maximum LTV ratio (basis points)
-    pub fn update_ltv_parameters(
+    /// * `securitization_id` - ID of the securitization
+    /// * `caller` - Issuer, or anyone after the deadline
+    ///
+    /// # Events
+    /// Emits `SecuritizationCancelled` event
+    pub fn cancel_securitization(
         env: Env,
-        min_ltv: u32,
-        max_ltv: u32,
+        securitization_id: u64,
+        caller: Address,
     ) -> Result<(), ContractError> {
-        let admin: Address = env
+        caller.require_auth();
+
+        let mut securitization = read_securitization(&env, securitization_id)?;
+        if securitization.status != SecuritizationStatus::Pending {
+            return Err(ContractError::InvalidLoanData);
+        }
+
+        let now = env.ledger().timestamp();
+        if caller != securitization.issuer && now <= securitization.subscription_deadline {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let portfolio: LoanPortfolio = env
             .storage()
-            .instance()
-            .get(&symbol_short!("admin"))
-            .ok_or(ContractError::Unauthorized)?;
+            .persistent()
+            .get(&format_loan_pool_key(securitization.portfolio_id))
+            .ok_or(ContractError::LoanNotFound)?;
+        for loan_id in portfolio.loan_ids.iter() {
+            env.storage()
+                .persistent()
+                .remove(&format_securitized_loan_key(loan_id));
+        }
 
-        admin.require_auth();
+        securitization.status = SecuritizationStatus::Cancelled;
+        securitization.updated_at = now;
+        env.storage().persistent().set(
+            &format_securitization_key(securitization_id),
+            &securitization,
+        );
 
-        if min_ltv >= max_ltv || max_ltv > 10000 {
+        env.events()
+            .publish((symbol_short!("sec_canc"),), (securitization_id, caller));
+
+        Ok(())
+    }
+
+    /// Transfer tranche units between holders
+    ///
+    /// Payouts earned before the transfer stay claimable by the sender.
+    ///
+    /// # Arguments
+    /// * `securitization_id` - ID of the securitization
+    /// * `class` - Tranche of the units
+    /// * `from` - Current holder
+    /// * `to` - New holder
+    /// * `units` - Units to transfer
+    pub fn transfer_tranche(
+        env: Env,
+        securitization_id: u64,
+        class: TrancheClass,
+        from: Address,
+        to: Address,
+        units: i128,
+    ) -> Result<(), ContractError> {
+        from.require_auth();
+
+        let tranche = read_tranche(&env, securitization_id, class)?;
+        let mut sender = read_tranche_position(&env, &tranche, &from);
+        if units <= 0 || units > sender.units || from == to {
             return Err(ContractError::InvalidLoanData);
         }
+        let mut recipient = read_tranche_position(&env, &tranche, &to);
 
-        env.storage()
-            .instance()
-            .set(&symbol_short!("min_ltv"), &min_ltv);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("max_ltv"), &max_ltv);
+        settle_tranche_position(&tranche, &mut sender)?;
+        settle_tranche_position(&tranche, &mut recipient)?;
+        sender.units -= units;
+        recipient.units += units;
 
-        env.events()
-            .publish((symbol_short!("ltv_updt"),), (min_ltv, max_ltv));
+        env.storage().persistent().set(
+            &format_tranche_position_key(securitization_id, class, &from),
+            &sender,
+        );
+        env.storage().persistent().set(
+            &format_tranche_position_key(securitization_id, class, &to),
+            &recipient,
+        );
+
+        env.events().publish(
+            (symbol_short!("tr_xfer"),),
+            (securitization_id, class, from, to, units),
+        );
 
         Ok(())
     }
 
-    /// Get loan portfolio analytics
+    /// Withdraw a holder's accumulated tranche payouts
+    ///
+    /// Once a securitization is cancelled, this refunds the holder's units at
+    /// face value instead.
     ///
     /// # Arguments
-    /// * `borrower` - Address of borrower (optional for all loans)
+    /// * `securitization_id` - ID of the securitization
+    /// * `class` - Tranche held
+    /// * `holder` - Address claiming
     ///
     /// # Returns
-    /// Portfolio analytics
-    pub fn get_portfolio_analytics(
-        _env: Env,
-        _borrower: Option<Address>,
-    ) -> Result<PortfolioAnalytics, ContractError> {
-        // Note: In production, would aggregate loan data
-        Ok(PortfolioAnalytics {
-            total_loans: 0,
-            total_principal: 0,
-            total_outstanding: 0,
-            total_interest_paid: 0,
-            active_loans: 0,
-            defaulted_loans: 0,
-            paid_loans: 0,
-            average_interest_rate: 0,
-            portfolio_ltv: 0,
-        })
-    }
-}
+    /// The amount paid out
+    ///
+    /// # Events
+    /// Emits `TrancheClaimed` event
+    pub fn claim_tranche(
+        env: Env,
+        securitization_id: u64,
+        class: TrancheClass,
+        holder: Address,
+    ) -> Result<i128, ContractError> {
+        holder.require_auth();
 
-// Helper functions
+        let securitization = read_securitization(&env, securitization_id)?;
+        let mut tranche = read_tranche(&env, securitization_id, class)?;
+        let mut position = read_tranche_position(&env, &tranche, &holder);
+        settle_tranche_position(&tranche, &mut position)?;
 
-fn format_loan_storage_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
-    (symbol_short!("loan"), loan_id)
-}
+        if securitization.status == SecuritizationStatus::Cancelled {
+            position.claimable += position.units;
+            tranche.subscribed -= position.units;
+            position.units = 0;
+            env.storage()
+                .persistent()
+                .set(&format_tranche_key(securitization_id, class), &tranche);
+        }
 
-fn format_repayment_key(loan_id: u64, timestamp: u64) -> (soroban_sdk::Symbol, u64, u64) {
-    (symbol_short!("repay"), loan_id, timestamp)
-}
+        let amount = position.claimable;
+        position.claimable = 0;
+        env.storage().persistent().set(
+            &format_tranche_position_key(securitization_id, class, &holder),
+            &position,
+        );
 
-fn calculate_simple_interest(
-    principal: i128,
-    annual_rate: u32,
-    period: u64,
-) -> Result<i128, ContractError> {
-    // Simple Interest = P * r * t
-    // where P = principal, r = annual rate, t = time in years
-    let interest = (principal as u128 * annual_rate as u128 * period as u128)
-        / (10000u128 * 365u128 * 86400u128);
-    Ok(interest as i128)
-}
+        if amount > 0 {
+            token::Client::new(&env, &securitization.asset).transfer(
+                &env.current_contract_address(),
+                &holder,
+                &amount,
+            );
+            env.events().publish(
+                (symbol_short!("tr_claim"),),
+                (securitization_id, class, holder, amount),
+            );
+        }
 
-fn calculate_compound_interest(
-    principal: i128,
-    annual_rate: u32,
-    period: u64,
-) -> Result<i128, ContractError> {
-    // Approximation for compound accrual over short intervals.
-    let interest = (principal as u128 * annual_rate as u128 * period as u128)
-        / (10000u128 * 365u128 * 86400u128);
-    Ok(interest as i128)
-}
+        Ok(amount)
+    }
 
-fn calculate_fixed_interest(
-    principal: i128,
-    annual_rate: u32,
-    period: u64,
-) -> Result<i128, ContractError> {
-    // Fixed Interest = P * r * t (same as simple, but fixed for loan term)
-    let interest = (principal as u128 * annual_rate as u128 * period as u128)
-        / (10000u128 * 365u128 * 86400u128);
-    Ok(interest as i128)
+    /// Get a securitization
+    pub fn get_securitization(
+        env: Env,
+        securitization_id: u64,
+    ) -> Result<LoanSecuritization, ContractError> {
+        read_securitization(&env, securitization_id)
+    }
+
+    /// Get a tranche of a securitization
+    pub fn get_tranche(
+        env: Env,
+        securitization_id: u64,
+        class: TrancheClass,
+    ) -> Result<Tranche, ContractError> {
+        read_tranche(&env, securitization_id, class)
+    }
+
+    /// Get a holder's tranche position, with payouts to date in `claimable`
+    pub fn get_tranche_position(
+        env: Env,
+        securitization_id: u64,
+        class: TrancheClass,
+        holder: Address,
+    ) -> Result<TranchePosition, ContractError> {
+        let tranche = read_tranche(&env, securitization_id, class)?;
+        let mut position = read_tranche_position(&env, &tranche, &holder);
+        settle_tranche_position(&tranche, &mut position)?;
+        Ok(position)
+    }
+
+    /// Get a pool of loans backing a securitization
+    pub fn get_loan_portfolio(env: Env, portfolio_id: u64) -> Option<LoanPortfolio> {
+        env.storage()
+            .persistent()
+            .get(&format_loan_pool_key(portfolio_id))
+    }
+
+    /// Set the compounding frequency and day-count convention of a pending loan
+    ///
+    /// Only the borrower may change the conventions, and only before the loan
+    /// is funded, so the lender accepts them by funding.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    /// * `compounding` - Compounding frequency for `InterestType::Compound`
+    /// * `day_count` - Day-count convention for all interest types
+    ///
+    /// # Events
+    /// Emits `ConventionsSet` event
+    pub fn set_interest_conventions(
+        env: Env,
+        loan_id: u64,
+        compounding: Compounding,
+        day_count: DayCountConvention,
+    ) -> Result<(), ContractError> {
+        let storage_key = format_loan_storage_key(loan_id);
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::LoanNotFound)?;
+
+        loan.borrower.require_auth();
+
+        if loan.status != LoanStatus::Pending {
+            return Err(ContractError::LoanNotPending);
+        }
+
+        loan.compounding = compounding;
+        loan.day_count = day_count;
+        loan.next_payment_date = store_schedule(
+            &env,
+            &loan,
+            loan.principal,
+            loan.start_date,
+            loan.maturity_date,
+        )?;
+        loan.updated_at = env.ledger().timestamp();
+        save_loan(&env, &loan);
+
+        env.events().publish(
+            (symbol_short!("conv_set"),),
+            (loan_id, compounding, day_count),
+        );
+
+        Ok(())
+    }
+
+    /// Cancel an unfunded loan and release its collateral (borrower only)
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    pub fn cancel_loan(env: Env, loan_id: u64) -> Result<(), ContractError> {
+        let storage_key = format_loan_storage_key(loan_id);
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::LoanNotFound)?;
+
+        loan.borrower.require_auth();
+
+        if loan.status != LoanStatus::Pending {
+            return Err(ContractError::LoanNotPending);
+        }
+
+        loan.status = LoanStatus::Closed;
+        loan.updated_at = env.ledger().timestamp();
+        save_loan(&env, &loan);
+
+        release_loan_collateral(&env, loan.collateral_id, &loan.borrower);
+
+        env.events()
+            .publish((symbol_short!("loan_canc"),), (loan_id, loan.borrower));
+
+        Ok(())
+    }
+
+    /// Calculate accrued interest for a loan
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    /// * `period` - Period in seconds to calculate interest for
+    ///
+    /// # Returns
+    /// Accrued interest amount
+    pub fn calculate_interest(env: Env, loan_id: u64, period: u64) -> Result<i128, ContractError> {
+        // Get loan
+        let storage_key = format_loan_storage_key(loan_id);
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::LoanNotFound)?;
+
+        interest_for_window(
+            &env,
+            &loan,
+            loan.outstanding_balance,
+            loan.last_payment_date,
+            loan.last_payment_date.saturating_add(period),
+        )
+    }
+
+    /// Make a loan repayment
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    /// * `amount` - Repayment amount
+    /// * `payer` - Address of the payer
+    ///
+    /// # Events
+    /// Emits `RepaymentMade` event
+    pub fn make_repayment(
+        env: Env,
+        loan_id: u64,
+        amount: i128,
+        payer: Address,
+    ) -> Result<(), ContractError> {
+        payer.require_auth();
+
+        // Get loan
+        let storage_key = format_loan_storage_key(loan_id);
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::LoanNotFound)?;
+
+        // Validate loan status; defaulted loans only accept payments under an
+        // agreed recovery plan
+        let mut recovery_plan = None;
+        if loan.status == LoanStatus::Defaulted {
+            let plan: RecoveryPlan = env
+                .storage()
+                .persistent()
+                .get(&format_recovery_plan_key(loan_id))
+                .ok_or(ContractError::LoanInDefault)?;
+            if plan.status != RecoveryStatus::InProgress || env.ledger().timestamp() > plan.deadline
+            {
+                return Err(ContractError::LoanInDefault);
+            }
+            recovery_plan = Some(plan);
+        }
+
+        if loan.status == LoanStatus::Paid {
+            return Err(ContractError::LoanAlreadyPaid);
+        }
+
+        if recovery_plan.is_none()
+            && loan.status != LoanStatus::Active
+            && loan.status != LoanStatus::Restructured
+        {
+            return Err(ContractError::LoanNotActive);
+        }
+
+        // Validate repayment amount
+        if amount <= 0 {
+            return Err(ContractError::InvalidRepaymentAmount);
+        }
+
+        // Overdue installments pick up late fees and penalty interest
+        let now = env.ledger().timestamp();
+        if recovery_plan.is_none() {
+            apply_late_charges(&env, &mut loan, now)?;
+        }
+
+        // Calculate current interest
+        let accrued_interest = interest_for_window(
+            &env,
+            &loan,
+            loan.outstanding_balance,
+            loan.last_payment_date,
+            now,
+        )?;
+
+        // Calculate total owed; settling before maturity adds the prepayment
+        // fee, so a payment reaching the balance must cover the fee as well
+        let balance_owed =
+            loan.outstanding_balance + accrued_interest + loan.default_fees + loan.fees;
+        if recovery_plan.is_none() && amount >= balance_owed && now < loan.maturity_date {
+            let prepayment_fee = apply_bps(
+                loan.outstanding_balance,
+                read_loan_fees(&env).prepayment_penalty,
+            )?;
+            if amount < balance_owed + prepayment_fee {
+                return Err(ContractError::InvalidPrepaymentAmount);
+            }
+            loan.fees += prepayment_fee;
+        }
+        let total_owed =
+            loan.outstanding_balance + accrued_interest + loan.default_fees + loan.fees;
+
+        if amount > total_owed {
+            return Err(ContractError::RepaymentExceedsBalance);
+        }
+
+        // Apply repayment: fees first, then interest, then principal.
+        let fee_payment = cmp::min(amount, loan.default_fees + loan.fees);
+        let interest_payment = cmp::min(amount - fee_payment, accrued_interest);
+        let principal_payment = amount - fee_payment - interest_payment;
+
+        let default_fee_payment = cmp::min(fee_payment, loan.default_fees);
+        loan.default_fees -= default_fee_payment;
+        loan.fees -= fee_payment - default_fee_payment;
+        loan.total_fees_paid += fee_payment;
+        loan.outstanding_balance -= principal_payment;
+        loan.total_repaid += amount;
+        loan.total_interest_paid += interest_payment;
+        loan.last_payment_date = env.ledger().timestamp();
+        loan.updated_at = env.ledger().timestamp();
+
+        // Update status
+        if let Some(mut plan) = recovery_plan {
+            loan.recovered_amount += amount;
+            if loan.recovered_amount >= plan.target_amount || loan.outstanding_balance <= 0 {
+                // Plan honoured: any shortfall is written off
+                plan.status = RecoveryStatus::Successful;
+                env.storage()
+                    .persistent()
+                    .set(&format_recovery_plan_key(loan_id), &plan);
+                loan.status = if loan.outstanding_balance <= 0 {
+                    LoanStatus::Paid
+                } else {
+                    LoanStatus::Closed
+                };
+            }
+        } else if loan.outstanding_balance <= 0 {
+            loan.status = LoanStatus::Paid;
+        } else {
+            loan.status = LoanStatus::Active;
+        }
+
+        // Score the payment against the due date it was made for
+        let current_time = env.ledger().timestamp();
+        update_performance(&env, loan_id, |metrics| {
+            if current_time <= loan.next_payment_date {
+                metrics.on_time_payments += 1;
+            } else {
+                metrics.late_payments += 1;
+            }
+        });
+
+        // Credit the payment to installments in due order
+        loan.next_payment_date = credit_schedule(&env, &loan, amount)?;
+
+        // Store updated loan
+        save_loan(&env, &loan);
+
+        // Route funds: protocol fee on interest to treasury, remainder to lender
+        let fee = route_repayment(&env, &loan, &payer, amount, interest_payment)?;
+        if let Some(pool) = lending_pool_of(&env, &loan) {
+            notify_lending_pool(&env, &pool, "repay", loan_id, principal_payment);
+        }
+
+        // Release collateral once fully repaid or settled
+        if loan.status == LoanStatus::Paid || loan.status == LoanStatus::Closed {
+            release_loan_collateral(&env, loan.collateral_id, &loan.borrower);
+            set_syndication_status(&env, loan_id, SyndicationStatus::Completed);
+            expire_insurance(&env, loan_id);
+        }
+
+        // Record repayment
+        let repayment = RepaymentRecord {
+            loan_id,
+            payer: payer.clone(),
+            amount,
+            principal_payment,
+            interest_payment,
+            timestamp: env.ledger().timestamp(),
+        };
+
+        let repayment_key = format_repayment_key(loan_id, env.ledger().timestamp());
+        env.storage().persistent().set(&repayment_key, &repayment);
+
+        // Emit event
+        env.events().publish(
+            (symbol_short!("repaymade"),),
+            (loan_id, amount, loan.outstanding_balance, fee),
+        );
+
+        Ok(())
+    }
+
+    /// Check and handle loan default
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    ///
+    /// # Returns
+    /// Whether loan is in default
+    ///
+    /// # Events
+    /// Emits `LoanDefaulted` event if default detected
+    pub fn check_default(env: Env, loan_id: u64) -> Result<bool, ContractError> {
+        // Get loan
+        let storage_key = format_loan_storage_key(loan_id);
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::LoanNotFound)?;
+
+        // Only funded loans can fall into default
+        if loan.status != LoanStatus::Active && loan.status != LoanStatus::Restructured {
+            return Ok(false);
+        }
+
+        // No default during an agreed payment holiday
+        let current_time = env.ledger().timestamp();
+        let forbearance_key = format_forbearance_key(loan_id);
+        if let Some(mut forbearance) = env
+            .storage()
+            .persistent()
+            .get::<_, ForbearanceAgreement>(&forbearance_key)
+        {
+            if forbearance.status == ForbearanceStatus::Active {
+                if current_time < forbearance.end_date {
+                    return Ok(false);
+                }
+                forbearance.status = ForbearanceStatus::Completed;
+                env.storage()
+                    .persistent()
+                    .set(&forbearance_key, &forbearance);
+            }
+        }
+
+        // Overdue installments pick up late fees and penalty interest
+        apply_late_charges(&env, &mut loan, current_time)?;
+
+        // Check if loan is past due
+        let grace_period_end = loan.next_payment_date + loan.grace_period;
+
+        if current_time > grace_period_end && loan.outstanding_balance > 0 {
+            // Loan is in default
+            let accrued_interest = interest_for_window(
+                &env,
+                &loan,
+                loan.outstanding_balance,
+                loan.last_payment_date,
+                current_time,
+            )?;
+            let fee_rate: u32 = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("def_fee"))
+                .unwrap_or(0);
+            let fee_amount = loan
+                .outstanding_balance
+                .checked_mul(fee_rate as i128)
+                .ok_or(ContractError::FeeCalculationError)?
+                / 10000;
+
+            update_performance(&env, loan_id, |metrics| metrics.missed_payments += 1);
+
+            loan.status = LoanStatus::Defaulted;
+            loan.default_date = current_time;
+            loan.default_fees += fee_amount;
+            loan.updated_at = current_time;
+
+            env.storage().persistent().set(
+                &format_default_key(loan_id),
+                &DefaultEvent {
+                    loan_id,
+                    default_date: current_time,
+                    outstanding_balance: loan.outstanding_balance,
+                    accrued_interest,
+                    default_reason: String::from_str(&env, "payment overdue"),
+                },
+            );
+            env.storage().persistent().set(
+                &format_default_fee_key(loan_id),
+                &DefaultFee {
+                    loan_id,
+                    fee_amount,
+                    fee_rate,
+                    applied_date: current_time,
+                },
+            );
+
+            // Store updated loan
+            save_loan(&env, &loan);
+            set_syndication_status(&env, loan_id, SyndicationStatus::Defaulted);
+            if let Some(securitization) = issued_securitization(&env, loan_id) {
+                allocate_securitization_loss(&env, securitization, loan.outstanding_balance)?;
+            }
+            if let Some(pool) = lending_pool_of(&env, &loan) {
+                notify_lending_pool(&env, &pool, "write_off", loan_id, loan.outstanding_balance);
+            }
+
+            let schedule_key = format_schedule_key(loan_id);
+            if let Some(mut schedule) = env
+                .storage()
+                .persistent()
+                .get::<_, RepaymentSchedule>(&schedule_key)
+            {
+                schedule.status = ScheduleStatus::Defaulted;
+                env.storage().persistent().set(&schedule_key, &schedule);
+            }
+
+            // Emit event
+            env.events().publish(
+                (symbol_short!("loan_def"),),
+                (loan_id, loan.outstanding_balance),
+            );
+
+            Ok(true)
+        } else {
+            save_loan(&env, &loan);
+            Ok(false)
+        }
+    }
+
+    /// Restructure loan terms
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    /// * `new_terms` - New loan terms
+    ///
+    /// # Events
+    /// Emits `LoanRestructured` event
+    pub fn restructure_loan(
+        env: Env,
+        loan_id: u64,
+        new_terms: LoanRestructuring,
+    ) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        admin.require_auth();
+
+        // Get loan
+        let storage_key = format_loan_storage_key(loan_id);
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::LoanNotFound)?;
+
+        // Validate restructuring
+        if loan.status == LoanStatus::Paid {
+            return Err(ContractError::InvalidRestructuring);
+        }
+
+        // Apply new terms
+        if let Some(new_rate) = new_terms.new_interest_rate {
+            if new_rate > 100000 {
+                return Err(ContractError::InvalidInterestRate);
+            }
+            loan.interest_rate = new_rate;
+        }
+
+        if let Some(new_term) = new_terms.new_term {
+            validate_term(&env, new_term)?;
+            loan.maturity_date = env.ledger().timestamp() + new_term;
+        }
+
+        if let Some(new_grace) = new_terms.new_grace_period {
+            loan.grace_period = new_grace;
+        }
+
+        // Reschedule the outstanding balance under the new terms
+        let now = env.ledger().timestamp();
+        loan.next_payment_date = store_schedule(
+            &env,
+            &loan,
+            loan.outstanding_balance,
+            now,
+            loan.maturity_date,
+        )?;
+        loan.updated_at = now;
+
+        // Store updated loan
+        save_loan(&env, &loan);
+        record_restructuring(&env, &loan, 0);
+
+        // Emit event
+        env.events()
+            .publish((symbol_short!("loan_rest"),), (loan_id, loan.interest_rate));
+
+        Ok(())
+    }
+
+    /// Propose new terms for a loan (borrower or lender)
+    ///
+    /// The other party accepts or rejects the proposal before `deadline`.
+    /// Only one proposal per loan can be open at a time.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    /// * `proposer` - Borrower or lender making the proposal
+    /// * `terms` - Proposed terms
+    /// * `deadline` - Time by which the counterparty must respond
+    ///
+    /// # Returns
+    /// The proposal ID
+    ///
+    /// # Events
+    /// Emits `RestructuringProposed` event
+    pub fn propose_restructuring(
+        env: Env,
+        loan_id: u64,
+        proposer: Address,
+        terms: RestructuringTerms,
+        deadline: u64,
+    ) -> Result<u64, ContractError> {
+        proposer.require_auth();
+
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+
+        if proposer != loan.borrower && Some(proposer.clone()) != loan.lender {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if loan.status != LoanStatus::Active
+            && loan.status != LoanStatus::Restructured
+            && loan.status != LoanStatus::Defaulted
+        {
+            return Err(ContractError::RestructuringNotAllowed);
+        }
+
+        let now = env.ledger().timestamp();
+        if deadline <= now {
+            return Err(ContractError::InvalidRestructuring);
+        }
+        validate_restructuring_terms(&env, &terms)?;
+
+        // One open proposal per loan
+        let open_key = format_open_proposal_key(loan_id);
+        if let Some(open_id) = env.storage().persistent().get::<_, u64>(&open_key) {
+            let open: RestructuringProposal = env
+                .storage()
+                .persistent()
+                .get(&format_proposal_key(open_id))
+                .ok_or(ContractError::ProposalNotFound)?;
+            if open.status == ProposalStatus::Proposed && now <= open.deadline {
+                return Err(ContractError::ProposalPending);
+            }
+        }
+
+        let proposal_id: u64 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("next_prop"))
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("next_prop"), &(proposal_id + 1));
+
+        let proposal = RestructuringProposal {
+            id: proposal_id,
+            loan_id,
+            proposed_by: proposer.clone(),
+            proposal_date: now,
+            new_terms: terms,
+            status: ProposalStatus::Proposed,
+            deadline,
+        };
+        env.storage()
+            .persistent()
+            .set(&format_proposal_key(proposal_id), &proposal);
+        env.storage().persistent().set(&open_key, &proposal_id);
+
+        env.events().publish(
+            (symbol_short!("rst_prop"),),
+            (loan_id, proposal_id, proposer, deadline),
+        );
+
+        Ok(proposal_id)
+    }
+
+    /// Accept a restructuring proposal (counterparty only)
+    ///
+    /// Accrued interest is capitalized, the terms are applied and the
+    /// repayment schedule is regenerated from now to the new maturity.
+    ///
+    /// # Arguments
+    /// * `proposal_id` - ID of the proposal
+    ///
+    /// # Events
+    /// Emits `RestructuringAccepted` event
+    pub fn accept_restructuring(env: Env, proposal_id: u64) -> Result<(), ContractError> {
+        let (mut proposal, mut loan) = open_proposal_for_counterparty(&env, proposal_id)?;
+
+        if loan.status != LoanStatus::Active
+            && loan.status != LoanStatus::Restructured
+            && loan.status != LoanStatus::Defaulted
+        {
+            return Err(ContractError::RestructuringNotAllowed);
+        }
+
+        let now = env.ledger().timestamp();
+        let terms = proposal.new_terms.clone();
+
+        // Capitalize interest accrued under the old terms
+        let accrued_interest = interest_for_window(
+            &env,
+            &loan,
+            loan.outstanding_balance,
+            loan.last_payment_date,
+            now,
+        )?;
+        loan.outstanding_balance += accrued_interest;
+        loan.last_payment_date = now;
+
+        if let Some(new_principal) = terms.new_principal {
+            loan.outstanding_balance = new_principal;
+        }
+        let forgiveness = terms.forgiveness_amount.unwrap_or(0);
+        if forgiveness > loan.outstanding_balance {
+            return Err(ContractError::InvalidRestructuring);
+        }
+        loan.outstanding_balance -= forgiveness;
+
+        if let Some(new_rate) = terms.new_interest_rate {
+            loan.interest_rate = new_rate;
+        }
+        if let Some(new_term) = terms.new_term {
+            loan.term = new_term;
+            loan.maturity_date = now + new_term;
+        }
+        if let ScheduleChange::Switch(profile) = terms.new_payment_schedule {
+            loan.repayment_profile = profile;
+        }
+        if loan.maturity_date <= now {
+            return Err(ContractError::InvalidRestructuring);
+        }
+
+        // Regenerate the schedule for the remaining balance
+        loan.next_payment_date = store_schedule(
+            &env,
+            &loan,
+            loan.outstanding_balance,
+            now,
+            loan.maturity_date,
+        )?;
+        loan.status = LoanStatus::Restructured;
+        loan.default_date = 0;
+        loan.updated_at = now;
+        save_loan(&env, &loan);
+
+        proposal.status = ProposalStatus::Implemented;
+        env.storage()
+            .persistent()
+            .set(&format_proposal_key(proposal_id), &proposal);
+        env.storage()
+            .persistent()
+            .remove(&format_open_proposal_key(loan.id));
+        record_restructuring(&env, &loan, forgiveness);
+
+        env.events().publish(
+            (symbol_short!("rst_acpt"),),
+            (
+                loan.id,
+                proposal_id,
+                loan.outstanding_balance,
+                loan.interest_rate,
+            ),
+        );
+
+        Ok(())
+    }
+
+    /// Reject a restructuring proposal (counterparty only)
+    ///
+    /// # Arguments
+    /// * `proposal_id` - ID of the proposal
+    ///
+    /// # Events
+    /// Emits `RestructuringRejected` event
+    pub fn reject_restructuring(env: Env, proposal_id: u64) -> Result<(), ContractError> {
+        let (mut proposal, loan) = open_proposal_for_counterparty(&env, proposal_id)?;
+
+        proposal.status = ProposalStatus::Rejected;
+        env.storage()
+            .persistent()
+            .set(&format_proposal_key(proposal_id), &proposal);
+        env.storage()
+            .persistent()
+            .remove(&format_open_proposal_key(loan.id));
+
+        env.events()
+            .publish((symbol_short!("rst_rjct"),), (loan.id, proposal_id));
+
+        Ok(())
+    }
+
+    /// Get a restructuring proposal
+    pub fn get_restructuring_proposal(
+        env: Env,
+        proposal_id: u64,
+    ) -> Result<RestructuringProposal, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&format_proposal_key(proposal_id))
+            .ok_or(ContractError::ProposalNotFound)
+    }
+
+    /// Get the restructuring history of a loan
+    ///
+    /// Read by risk assessment when scoring the borrower.
+    pub fn get_restructuring_history(
+        env: Env,
+        loan_id: u64,
+    ) -> Result<RestructuringHistory, ContractError> {
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+        Ok(read_restructuring_history(&env, &loan))
+    }
+
+    /// Grant a payment holiday (lender only)
+    ///
+    /// Unpaid installments and maturity move back by `duration`; interest keeps
+    /// accruing and the loan cannot default until the holiday ends. A
+    /// defaulted loan is returned to `Active`.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    /// * `duration` - Length of the holiday in seconds
+    /// * `payment_reduction` - Reduction of scheduled payments (basis points)
+    ///
+    /// # Events
+    /// Emits `ForbearanceGranted` event
+    pub fn grant_forbearance(
+        env: Env,
+        loan_id: u64,
+        duration: u64,
+        payment_reduction: u32,
+    ) -> Result<u64, ContractError> {
+        let storage_key = format_loan_storage_key(loan_id);
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::LoanNotFound)?;
+
+        require_lender(&env, &loan)?;
+
+        if loan.status != LoanStatus::Active
+            && loan.status != LoanStatus::Restructured
+            && loan.status != LoanStatus::Defaulted
+        {
+            return Err(ContractError::LoanNotActive);
+        }
+
+        if duration == 0 || payment_reduction > 10000 {
+            return Err(ContractError::InvalidRecoveryTerms);
+        }
+
+        let forbearance_key = format_forbearance_key(loan_id);
+        if let Some(existing) = env
+            .storage()
+            .persistent()
+            .get::<_, ForbearanceAgreement>(&forbearance_key)
+        {
+            if existing.status == ForbearanceStatus::Active
+                && env.ledger().timestamp() < existing.end_date
+            {
+                return Err(ContractError::ForbearanceActive);
+            }
+        }
+
+        let now = env.ledger().timestamp();
+        let forbearance = ForbearanceAgreement {
+            id: next_recovery_id(&env),
+            loan_id,
+            start_date: now,
+            end_date: now + duration,
+            payment_reduction,
+            status: ForbearanceStatus::Active,
+        };
+        env.storage()
+            .persistent()
+            .set(&forbearance_key, &forbearance);
+
+        // Defer the remaining schedule
+        loan.next_payment_date = defer_schedule(&env, &loan, duration)?;
+        loan.maturity_date += duration;
+        if loan.status == LoanStatus::Defaulted {
+            loan.status = LoanStatus::Active;
+            loan.default_date = 0;
+        }
+        loan.updated_at = now;
+        save_loan(&env, &loan);
+
+        env.events().publish(
+            (symbol_short!("forbear"),),
+            (loan_id, forbearance.end_date, payment_reduction),
+        );
+
+        Ok(forbearance.id)
+    }
+
+    /// Propose a recovery plan for a defaulted loan (lender only)
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    /// * `strategy` - Recovery strategy
+    /// * `target_amount` - Amount the borrower must repay to settle the loan
+    /// * `deadline` - Time by which the target must be reached
+    ///
+    /// # Events
+    /// Emits `RecoveryProposed` event
+    pub fn propose_recovery_plan(
+        env: Env,
+        loan_id: u64,
+        strategy: RecoveryStrategy,
+        target_amount: i128,
+        deadline: u64,
+    ) -> Result<u64, ContractError> {
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+
+        require_lender(&env, &loan)?;
+
+        if loan.status != LoanStatus::Defaulted {
+            return Err(ContractError::LoanNotDefaulted);
+        }
+
+        if target_amount <= 0 || deadline <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidRecoveryTerms);
+        }
+
+        let plan_key = format_recovery_plan_key(loan_id);
+        if let Some(existing) = env.storage().persistent().get::<_, RecoveryPlan>(&plan_key) {
+            if existing.status == RecoveryStatus::InProgress {
+                return Err(ContractError::RecoveryInProgress);
+            }
+        }
+
+        let plan = RecoveryPlan {
+            id: next_recovery_id(&env),
+            loan_id,
+            created_date: env.ledger().timestamp(),
+            recovery_strategy: strategy,
+            status: RecoveryStatus::Pending,
+            target_amount,
+            deadline,
+        };
+        env.storage().persistent().set(&plan_key, &plan);
+
+        env.events().publish(
+            (symbol_short!("rcv_prop"),),
+            (loan_id, plan.id, target_amount, deadline),
+        );
+
+        Ok(plan.id)
+    }
+
+    /// Accept the proposed recovery plan (borrower only)
+    ///
+    /// Once accepted, the defaulted loan takes repayments until the plan
+    /// target is reached, at which point it is settled and the collateral
+    /// released.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    ///
+    /// # Events
+    /// Emits `RecoveryAccepted` event
+    pub fn accept_recovery_plan(env: Env, loan_id: u64) -> Result<(), ContractError> {
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+
+        loan.borrower.require_auth();
+
+        let plan_key = format_recovery_plan_key(loan_id);
+        let mut plan: RecoveryPlan = env
+            .storage()
+            .persistent()
+            .get(&plan_key)
+            .ok_or(ContractError::RecoveryPlanNotFound)?;
+
+        if plan.status != RecoveryStatus::Pending || env.ledger().timestamp() > plan.deadline {
+            return Err(ContractError::InvalidRecoveryTerms);
+        }
+
+        plan.status = RecoveryStatus::InProgress;
+        env.storage().persistent().set(&plan_key, &plan);
+
+        env.events()
+            .publish((symbol_short!("rcv_acpt"),), (loan_id, plan.id));
+
+        Ok(())
+    }
+
+    /// Seize the collateral of a defaulted loan (lender only)
+    ///
+    /// The registry transfers the collateral to the lender and the default is
+    /// reported to risk assessment. Blocked while a payment holiday or an
+    /// accepted recovery plan is still running.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    ///
+    /// # Events
+    /// Emits `CollateralSeized` event
+    pub fn seize_collateral(env: Env, loan_id: u64) -> Result<u64, ContractError> {
+        let storage_key = format_loan_storage_key(loan_id);
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::LoanNotFound)?;
+
+        let lender = require_lender(&env, &loan)?;
+
+        if loan.status != LoanStatus::Defaulted {
+            return Err(ContractError::LoanNotDefaulted);
+        }
+
+        let now = env.ledger().timestamp();
+        if let Some(forbearance) = env
+            .storage()
+            .persistent()
+            .get::<_, ForbearanceAgreement>(&format_forbearance_key(loan_id))
+        {
+            if forbearance.status == ForbearanceStatus::Active && now < forbearance.end_date {
+                return Err(ContractError::ForbearanceActive);
+            }
+        }
+
+        // A running plan must lapse before seizure; it then counts as failed
+        let plan_key = format_recovery_plan_key(loan_id);
+        if let Some(mut plan) = env.storage().persistent().get::<_, RecoveryPlan>(&plan_key) {
+            if plan.status == RecoveryStatus::InProgress {
+                if now <= plan.deadline {
+                    return Err(ContractError::RecoveryInProgress);
+                }
+                plan.status = RecoveryStatus::Failed;
+                env.storage().persistent().set(&plan_key, &plan);
+            }
+        }
+
+        // Registry transfers the collateral to the lender
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("col_reg"))
+            .ok_or(ContractError::CollateralNotFound)?;
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [loan.collateral_id.into_val(&env), lender.into_val(&env)],
+        );
+        env.invoke_contract::<Val>(&registry, &Symbol::new(&env, "seize_collateral"), args);
+
+        // Report the default, net of any insurance payout, to risk assessment
+        // and the reputation registry, if configured
+        let exposure = loan.outstanding_balance + loan.default_fees
+            - read_insurance(&env, loan_id).map_or(0, |policy| policy.claimed_amount);
+        if let Some(risk_assessment) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&symbol_short!("risk_mgr"))
+        {
+            let args: Vec<Val> = Vec::from_array(
+                &env,
+                [
+                    loan.borrower.into_val(&env),
+                    loan_id.into_val(&env),
+                    exposure.into_val(&env),
+                ],
+            );
+            env.invoke_contract::<Val>(
+                &risk_assessment,
+                &Symbol::new(&env, "record_default"),
+                args,
+            );
+        }
+        report_reputation(&env, &loan.borrower, ReputationOutcome::Default, exposure);
+
+        let seizure = CollateralSeizure {
+            id: next_recovery_id(&env),
+            loan_id,
+            collateral_id: loan.collateral_id,
+            seizure_date: now,
+            sale_date: 0,
+            sale_amount: 0,
+            status: SeizureStatus::InProgress,
+        };
+        env.storage()
+            .persistent()
+            .set(&format_seizure_key(loan_id), &seizure);
+
+        loan.updated_at = now;
+        save_loan(&env, &loan);
+
+        env.events().publish(
+            (symbol_short!("loan_seiz"),),
+            (loan_id, loan.collateral_id, exposure),
+        );
+
+        Ok(seizure.id)
+    }
+
+    /// Record the proceeds of selling seized collateral (lender only)
+    ///
+    /// Proceeds count as recovered and the loan is closed, with any shortfall
+    /// written off.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    /// * `sale_amount` - Amount realized from the sale
+    ///
+    /// # Events
+    /// Emits `SeizureSold` event
+    pub fn record_seizure_sale(
+        env: Env,
+        loan_id: u64,
+        sale_amount: i128,
+    ) -> Result<(), ContractError> {
+        let storage_key = format_loan_storage_key(loan_id);
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::LoanNotFound)?;
+
+        let lender = require_lender(&env, &loan)?;
+
+        let seizure_key = format_seizure_key(loan_id);
+        let mut seizure: CollateralSeizure = env
+            .storage()
+            .persistent()
+            .get(&seizure_key)
+            .ok_or(ContractError::SeizureNotFound)?;
+
+        if seizure.status != SeizureStatus::InProgress {
+            return Err(ContractError::SeizureNotFound);
+        }
+
+        if sale_amount < 0 {
+            return Err(ContractError::InvalidRecoveryTerms);
+        }
+
+        let now = env.ledger().timestamp();
+        seizure.sale_date = now;
+        seizure.sale_amount = sale_amount;
+        seizure.status = SeizureStatus::Sold;
+        env.storage().persistent().set(&seizure_key, &seizure);
+
+        // Syndicate members receive their share of the proceeds from the arranger
+        if let Some(syndication) = read_syndication(&env, loan_id) {
+            let arranger = syndication.lead_lender.clone();
+            distribute_pro_rata(&env, &loan, &syndication, &arranger, sale_amount);
+            set_syndication_status(&env, loan_id, SyndicationStatus::Completed);
+        }
+
+        loan.recovered_amount += sale_amount;
+        loan.status = LoanStatus::Closed;
+        loan.updated_at = now;
+        save_loan(&env, &loan);
+        expire_insurance(&env, loan_id);
+
+        // The servicer remits proceeds of a securitized loan to its holders
+        if let Some(securitization) = issued_securitization(&env, loan_id) {
+            collect_into_securitization(&env, &securitization, &lender, sale_amount)?;
+        }
+
+        env.events().publish(
+            (symbol_short!("seiz_sold"),),
+            (loan_id, sale_amount, loan.recovered_amount),
+        );
+
+        Ok(())
+    }
+
+    /// Attach a credit protection policy to a funded loan (lender and insurer)
+    ///
+    /// Coverage is capped at `coverage_rate` of the balance outstanding when
+    /// the policy is written. The insurer must approve the contract to draw
+    /// claim payouts from its balance.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    /// * `insurer` - Address providing the protection
+    /// * `coverage_rate` - Share of the loss covered (basis points)
+    /// * `premium_rate` - Share of each repayment paid to the insurer (basis points)
+    ///
+    /// # Returns
+    /// The policy ID
+    ///
+    /// # Events
+    /// Emits `LoanInsured` event
+    pub fn insure_loan(
+        env: Env,
+        loan_id: u64,
+        insurer: Address,
+        coverage_rate: u32,
+        premium_rate: u32,
+    ) -> Result<u64, ContractError> {
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+
+        let lender = require_lender(&env, &loan)?;
+        insurer.require_auth();
+
+        if loan.status != LoanStatus::Active && loan.status != LoanStatus::Restructured {
+            return Err(ContractError::LoanNotActive);
+        }
+        if coverage_rate == 0 || coverage_rate > 10000 || premium_rate > 10000 || insurer == lender
+        {
+            return Err(ContractError::InvalidLoanData);
+        }
+        if read_insurance(&env, loan_id)
+            .is_some_and(|policy| policy.status == InsuranceStatus::Active)
+        {
+            return Err(ContractError::InvalidLoanData);
+        }
+
+        let now = env.ledger().timestamp();
+        let policy_id: u64 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("next_ins"))
+            .unwrap_or(1u64);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("next_ins"), &(policy_id + 1));
+
+        let policy = LoanInsurance {
+            id: policy_id,
+            loan_id,
+            insurer: insurer.clone(),
+            coverage_rate,
+            coverage_amount: apply_bps(loan.outstanding_balance, coverage_rate)?,
+            premium_rate,
+            premiums_paid: 0,
+            claimed_amount: 0,
+            status: InsuranceStatus::Active,
+            created_at: now,
+            updated_at: now,
+        };
+        env.storage()
+            .persistent()
+            .set(&format_insurance_key(loan_id), &policy);
+
+        env.events().publish(
+            (symbol_short!("ins_new"),),
+            (loan_id, policy_id, insurer, policy.coverage_amount),
+        );
+
+        Ok(policy_id)
+    }
+
+    /// Claim on a defaulted loan's credit protection (lender only)
+    ///
+    /// The insurer pays its share of the unpaid balance and default fees,
+    /// which counts as recovered. Claims filed before collateral is seized
+    /// also reduce the exposure reported to risk assessment and the
+    /// reputation registry.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the defaulted loan
+    ///
+    /// # Returns
+    /// The amount paid out
+    ///
+    /// # Events
+    /// Emits `InsuranceClaimed` event
+    pub fn file_insurance_claim(env: Env, loan_id: u64) -> Result<i128, ContractError> {
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+
+        let lender = require_lender(&env, &loan)?;
+
+        if loan.status != LoanStatus::Defaulted {
+            return Err(ContractError::LoanNotDefaulted);
+        }
+
+        let mut policy = read_insurance(&env, loan_id).ok_or(ContractError::LoanNotFound)?;
+        if policy.status != InsuranceStatus::Active {
+            return Err(ContractError::InvalidLoanData);
+        }
+
+        let loss = loan.outstanding_balance + loan.default_fees;
+        let payout = cmp::min(
+            apply_bps(loss, policy.coverage_rate)?,
+            policy.coverage_amount,
+        );
+
+        if payout > 0 {
+            token::Client::new(&env, &loan.asset).transfer_from(
+                &env.current_contract_address(),
+                &policy.insurer,
+                &lender,
+                &payout,
+            );
+
+            // Payouts follow the loan's collections to syndicate members or
+            // securitization holders
+            if let Some(securitization) = issued_securitization(&env, loan_id) {
+                collect_into_securitization(&env, &securitization, &lender, payout)?;
+            } else if let Some(syndication) = read_syndication(&env, loan_id) {
+                distribute_pro_rata(&env, &loan, &syndication, &lender, payout);
+            }
+        }
+
+        let now = env.ledger().timestamp();
+        policy.claimed_amount = payout;
+        policy.status = InsuranceStatus::Claimed;
+        policy.updated_at = now;
+        env.storage()
+            .persistent()
+            .set(&format_insurance_key(loan_id), &policy);
+
+        loan.recovered_amount += payout;
+        loan.updated_at = now;
+        save_loan(&env, &loan);
+
+        env.events()
+            .publish((symbol_short!("ins_claim"),), (loan_id, policy.id, payout));
+
+        Ok(payout)
+    }
+
+    /// Get the credit protection policy on a loan
+    pub fn get_loan_insurance(env: Env, loan_id: u64) -> Option<LoanInsurance> {
+        read_insurance(&env, loan_id)
+    }
+
+    /// Settle a liquidation executed by risk assessment (risk assessment only)
+    ///
+    /// Risk assessment deposits the liquidator's `debt_covered` here first.
+    /// It settles fees, accrued interest and then principal, and the part
+    /// applied is split like a repayment between the protocol fee, the insurer
+    /// and the lender, syndicate or securitization; anything over what was
+    /// owed goes back to the liquidator. A loan left with no balance is
+    /// closed; otherwise it stays open as partially liquidated.
+    /// As secured party, this contract hands `collateral_share_bps` of the
+    /// collateral to the liquidator in the registry; a closed loan releases
+    /// whatever share remains. The outcome is reported to the reputation
+    /// registry.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    /// * `liquidator` - Address receiving the seized collateral
+    /// * `debt_covered` - Debt paid by the liquidator
+    /// * `collateral_share_bps` - Share of the collateral seized, in basis points
+    ///
+    /// # Returns
+    /// The loan's cumulative liquidation record
+    ///
+    /// # Events
+    /// Emits `LoanLiquidated` event
+    pub fn settle_liquidation(
+        env: Env,
+        loan_id: u64,
+        liquidator: Address,
+        debt_covered: i128,
+        collateral_share_bps: u32,
+    ) -> Result<LoanLiquidation, ContractError> {
+        let risk_assessment: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("risk_mgr"))
+            .ok_or(ContractError::Unauthorized)?;
+        risk_assessment.require_auth();
+
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+
+        if loan.status != LoanStatus::Active
+            && loan.status != LoanStatus::Restructured
+            && loan.status != LoanStatus::Defaulted
+        {
+            return Err(ContractError::LoanNotActive);
+        }
+        if debt_covered <= 0 {
+            return Err(ContractError::InvalidRepaymentAmount);
+        }
+
+        // Same order as a repayment: fees, interest, then principal
+        let now = env.ledger().timestamp();
+        let accrued_interest = interest_for_window(
+            &env,
+            &loan,
+            loan.outstanding_balance,
+            loan.last_payment_date,
+            now,
+        )?;
+        let fee_payment = cmp::min(debt_covered, loan.default_fees + loan.fees);
+        let interest_payment = cmp::min(debt_covered - fee_payment, accrued_interest);
+        let principal_payment = cmp::min(
+            debt_covered - fee_payment - interest_payment,
+            loan.outstanding_balance,
+        );
+        let applied = fee_payment + interest_payment + principal_payment;
+
+        let default_fee_payment = cmp::min(fee_payment, loan.default_fees);
+        loan.default_fees -= default_fee_payment;
+        loan.fees -= fee_payment - default_fee_payment;
+        loan.total_fees_paid += fee_payment;
+        loan.total_interest_paid += interest_payment;
+        loan.outstanding_balance -= principal_payment;
+        loan.recovered_amount += applied;
+        loan.last_payment_date = now;
+        loan.updated_at = now;
+
+        let closed = loan.outstanding_balance <= 0;
+        if closed {
+            loan.status = LoanStatus::Closed;
+        } else {
+            loan.next_payment_date = credit_schedule(&env, &loan, applied)?;
+        }
+        save_loan(&env, &loan);
+
+        let contract = env.current_contract_address();
+        route_repayment(&env, &loan, &contract, applied, interest_payment)?;
+        if debt_covered > applied {
+            token::Client::new(&env, &loan.asset).transfer(
+                &contract,
+                &liquidator,
+                &(debt_covered - applied),
+            );
+        }
+
+        if let Some(pool) = lending_pool_of(&env, &loan) {
+            notify_lending_pool(&env, &pool, "repay", loan_id, principal_payment);
+        }
+
+        // Registry hands the seized share to the liquidator
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("col_reg"))
+            .ok_or(ContractError::CollateralNotFound)?;
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                loan.collateral_id.into_val(&env),
+                liquidator.into_val(&env),
+                collateral_share_bps.into_val(&env),
+            ],
+        );
+        let liquidated_share: u32 =
+            env.invoke_contract(&registry, &Symbol::new(&env, "liquidate_collateral"), args);
+
+        if closed {
+            if liquidated_share < 10000 {
+                release_loan_collateral(&env, loan.collateral_id, &loan.borrower);
+            }
+            set_syndication_status(&env, loan_id, SyndicationStatus::Completed);
+            expire_insurance(&env, loan_id);
+        }
+
+        let key = format_liquidation_key(loan_id);
+        let mut liquidation: LoanLiquidation =
+            env.storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(LoanLiquidation {
+                    loan_id,
+                    debt_covered: 0,
+                    liquidation_count: 0,
+                    outstanding_balance: 0,
+                    status: LiquidationStatus::Partial,
+                    last_liquidated_at: 0,
+                });
+        liquidation.debt_covered += applied;
+        liquidation.liquidation_count += 1;
+        liquidation.outstanding_balance = loan.outstanding_balance;
+        liquidation.status = if closed {
+            LiquidationStatus::Closed
+        } else {
+            LiquidationStatus::Partial
+        };
+        liquidation.last_liquidated_at = now;
+        env.storage().persistent().set(&key, &liquidation);
+
+        // A closed-out loan counts as a default; a partial one as a late payment
+        let outcome = if closed {
+            ReputationOutcome::Default
+        } else {
+            ReputationOutcome::Late
+        };
+        report_reputation(&env, &loan.borrower, outcome, applied);
+
+        env.events().publish(
+            (symbol_short!("loan_liq"),),
+            (loan_id, applied, loan.outstanding_balance, closed),
+        );
+
+        Ok(liquidation)
+    }
+
+    /// Quote the debt a liquidation must cover to close a loan now
+    ///
+    /// Outstanding principal, interest accrued to date and unpaid fees: the
+    /// amounts `settle_liquidation` applies, in the same order.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    ///
+    /// # Returns
+    /// Total amount that settles the loan in a liquidation
+    pub fn get_liquidation_payoff(env: Env, loan_id: u64) -> Result<i128, ContractError> {
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+
+        if loan.status != LoanStatus::Active
+            && loan.status != LoanStatus::Restructured
+            && loan.status != LoanStatus::Defaulted
+        {
+            return Err(ContractError::LoanNotActive);
+        }
+
+        let accrued_interest = interest_for_window(
+            &env,
+            &loan,
+            loan.outstanding_balance,
+            loan.last_payment_date,
+            env.ledger().timestamp(),
+        )?;
+
+        Ok(loan.outstanding_balance + accrued_interest + loan.default_fees + loan.fees)
+    }
+
+    /// Get the liquidation record for a loan
+    pub fn get_liquidation(env: Env, loan_id: u64) -> Option<LoanLiquidation> {
+        env.storage()
+            .persistent()
+            .get(&format_liquidation_key(loan_id))
+    }
+
+    /// Get the forbearance agreement for a loan
+    pub fn get_forbearance(env: Env, loan_id: u64) -> Option<ForbearanceAgreement> {
+        env.storage()
+            .persistent()
+            .get(&format_forbearance_key(loan_id))
+    }
+
+    /// Get the recovery plan for a loan
+    pub fn get_recovery_plan(env: Env, loan_id: u64) -> Option<RecoveryPlan> {
+        env.storage()
+            .persistent()
+            .get(&format_recovery_plan_key(loan_id))
+    }
+
+    /// Get the collateral seizure for a loan
+    pub fn get_collateral_seizure(env: Env, loan_id: u64) -> Option<CollateralSeizure> {
+        env.storage().persistent().get(&format_seizure_key(loan_id))
+    }
+
+    /// Get the default record for a loan
+    pub fn get_default_event(env: Env, loan_id: u64) -> Option<DefaultEvent> {
+        env.storage().persistent().get(&format_default_key(loan_id))
+    }
+
+    /// Get the default fee applied to a loan
+    pub fn get_default_fee(env: Env, loan_id: u64) -> Option<DefaultFee> {
+        env.storage()
+            .persistent()
+            .get(&format_default_fee_key(loan_id))
+    }
+
+    /// Get current loan status
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    ///
+    /// # Returns
+    /// Current loan details
+    pub fn get_loan_status(env: Env, loan_id: u64) -> Result<Loan, ContractError> {
+        let storage_key = format_loan_storage_key(loan_id);
+        env.storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::LoanNotFound)
+    }
+
+    /// Get loan repayment history
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    ///
+    /// # Returns
+    /// Vector of repayment records
+    pub fn get_repayment_history(
+        env: Env,
+        _loan_id: u64,
+    ) -> Result<Vec<RepaymentRecord>, ContractError> {
+        // Note: In production, would iterate through repayment records
+        Ok(Vec::new(&env))
+    }
+
+    /// Get the repayment schedule with live installment markers
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    ///
+    /// # Returns
+    /// The schedule, each installment marked paid, due, overdue or upcoming
+    pub fn get_repayment_schedule(
+        env: Env,
+        loan_id: u64,
+    ) -> Result<RepaymentSchedule, ContractError> {
+        let mut schedule: RepaymentSchedule = env
+            .storage()
+            .persistent()
+            .get(&format_schedule_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+
+        let now = env.ledger().timestamp();
+        let mut next_found = false;
+        let mut installments = Vec::new(&env);
+        for mut installment in schedule.installments.iter() {
+            installment.status = if installment.paid {
+                InstallmentStatus::Paid
+            } else if installment.due_date < now {
+                InstallmentStatus::Overdue
+            } else if !next_found {
+                next_found = true;
+                InstallmentStatus::Due
+            } else {
+                InstallmentStatus::Upcoming
+            };
+            installments.push_back(installment);
+        }
+        schedule.installments = installments;
+
+        Ok(schedule)
+    }
+
+    /// Calculate early repayment amount
+    ///
+    /// Quotes the payment that settles the loan now: outstanding principal,
+    /// interest accrued to date, unpaid fees including late charges not yet
+    /// booked, and the prepayment fee if the loan has not matured.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    ///
+    /// # Returns
+    /// Total amount needed for early repayment
+    pub fn calculate_early_repayment(env: Env, loan_id: u64) -> Result<i128, ContractError> {
+        // Get loan
+        let storage_key = format_loan_storage_key(loan_id);
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::LoanNotFound)?;
+
+        if loan.status != LoanStatus::Active && loan.status != LoanStatus::Restructured {
+            return Err(ContractError::LoanNotActive);
+        }
+
+        let now = env.ledger().timestamp();
+        let late_charges = match env
+            .storage()
+            .persistent()
+            .get::<_, RepaymentSchedule>(&format_schedule_key(loan_id))
+        {
+            Some(mut schedule) => accrue_late_charges(&env, &loan, &mut schedule, now)?,
+            None => 0,
+        };
+        let accrued_interest = interest_for_window(
+            &env,
+            &loan,
+            loan.outstanding_balance,
+            loan.last_payment_date,
+            now,
+        )?;
+        let prepayment_fee = if now < loan.maturity_date {
+            apply_bps(
+                loan.outstanding_balance,
+                read_loan_fees(&env).prepayment_penalty,
+            )?
+        } else {
+            0
+        };
+
+        Ok(loan.outstanding_balance
+            + accrued_interest
+            + loan.default_fees
+            + loan.fees
+            + late_charges
+            + prepayment_fee)
+    }
+
+    /// Update LTV parameters
+    ///
+    /// # Arguments
+    /// * `min_ltv` - Minimum LTV ratio (basis points)
+    /// * `max_ltv` - Maximum LTV ratio (basis points)
+    pub fn update_ltv_parameters(
+        env: Env,
+        min_ltv: u32,
+        max_ltv: u32,
+    ) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        admin.require_auth();
+
+        if min_ltv >= max_ltv || max_ltv > 10000 {
+            return Err(ContractError::InvalidLoanData);
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("min_ltv"), &min_ltv);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("max_ltv"), &max_ltv);
+
+        env.events()
+            .publish((symbol_short!("ltv_updt"),), (min_ltv, max_ltv));
+
+        Ok(())
+    }
+
+    /// Update collateral valuation parameters
+    ///
+    /// # Arguments
+    /// * `max_valuation_age` - Oldest registry valuation accepted (seconds)
+    /// * `margin_call_ltv` - LTV above which a margin call is raised (basis points)
+    pub fn update_valuation_parameters(
+        env: Env,
+        max_valuation_age: u64,
+        margin_call_ltv: u32,
+    ) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        admin.require_auth();
+
+        if max_valuation_age == 0 || margin_call_ltv == 0 {
+            return Err(ContractError::InvalidLoanData);
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("max_vage"), &max_valuation_age);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("mc_ltv"), &margin_call_ltv);
+
+        env.events().publish(
+            (symbol_short!("vpar_updt"),),
+            (max_valuation_age, margin_call_ltv),
+        );
+
+        Ok(())
+    }
+
+    /// Update the fee charged on the outstanding balance at default
+    ///
+    /// # Arguments
+    /// * `fee_rate` - Default fee (basis points)
+    pub fn set_default_fee_rate(env: Env, fee_rate: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        admin.require_auth();
+
+        if fee_rate > 10000 {
+            return Err(ContractError::FeeCalculationError);
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("def_fee"), &fee_rate);
+
+        env.events()
+            .publish((symbol_short!("dfee_updt"),), (fee_rate,));
+
+        Ok(())
+    }
+
+    /// Update the loan fee schedule
+    ///
+    /// Fees are charged on top of interest into the loan's fee bucket; the
+    /// default fee also replaces the rate set by `set_default_fee_rate`.
+    ///
+    /// # Arguments
+    /// * `fees` - Fee rates (basis points)
+    pub fn set_loan_fees(env: Env, fees: LoanFees) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        admin.require_auth();
+
+        if fees.origination_fee > 10000
+            || fees.servicing_fee > 10000
+            || fees.prepayment_penalty > 10000
+            || fees.late_payment_fee > 10000
+            || fees.default_fee > 10000
+            || fees.penalty_rate > 100000
+        {
+            return Err(ContractError::FeeCalculationError);
+        }
+
+        env.storage().instance().set(&symbol_short!("fees"), &fees);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("def_fee"), &fees.default_fee);
+
+        env.events().publish((symbol_short!("fees_updt"),), fees);
+
+        Ok(())
+    }
+
+    /// Get the loan fee schedule
+    pub fn get_loan_fees(env: Env) -> LoanFees {
+        read_loan_fees(&env)
+    }
+
+    /// Set the reputation registry consulted by reputation covenants
+    ///
+    /// # Arguments
+    /// * `reputation_registry` - Reputation registry contract address
+    pub fn set_reputation_registry(
+        env: Env,
+        reputation_registry: Address,
+    ) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("rep_reg"), &reputation_registry);
+
+        Ok(())
+    }
+
+    /// Set the lending pool that can fund loans
+    ///
+    /// # Arguments
+    /// * `lending_pool` - Lending pool contract address
+    pub fn set_lending_pool(env: Env, lending_pool: Address) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("lend_pool"), &lending_pool);
+
+        Ok(())
+    }
+
+    /// Set the risk assessment contract notified of defaults
+    ///
+    /// # Arguments
+    /// * `risk_assessment` - Risk assessment contract address
+    pub fn set_risk_assessment(env: Env, risk_assessment: Address) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("risk_mgr"), &risk_assessment);
+
+        Ok(())
+    }
+
+    /// Set the oracle adapter that prices cross-currency collateral
+    ///
+    /// # Arguments
+    /// * `oracle_adapter` - Oracle adapter contract address
+    pub fn set_oracle_adapter(env: Env, oracle_adapter: Address) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("fx_orc"), &oracle_adapter);
+
+        Ok(())
+    }
+
+    /// Set how long a borrower has to meet a cross-currency margin call
+    ///
+    /// # Arguments
+    /// * `period` - Seconds between the call and its deadline
+    pub fn set_margin_call_period(env: Env, period: u64) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        admin.require_auth();
+
+        if period == 0 {
+            return Err(ContractError::InvalidLoanData);
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("mc_period"), &period);
+
+        Ok(())
+    }
+
+    /// Set how old an FX rate may be before cross-currency loans reject it
+    ///
+    /// # Arguments
+    /// * `max_age` - Maximum age of an FX rate in seconds
+    pub fn set_max_fx_age(env: Env, max_age: u64) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        admin.require_auth();
+
+        if max_age == 0 {
+            return Err(ContractError::InvalidLoanData);
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("max_fxage"), &max_age);
+
+        Ok(())
+    }
+
+    /// Re-evaluate a cross-currency loan's coverage at the latest FX rate
+    ///
+    /// Permissionless, so keepers can react to currency moves. When the LTV
+    /// rises above the margin call threshold the borrower is called to top up
+    /// collateral (or repay) by a deadline; a call still unmet after its
+    /// deadline makes the loan callable by the lender.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    ///
+    /// # Returns
+    /// Current LTV in the loan currency (basis points)
+    ///
+    /// # Events
+    /// Emits `FxMarginCall`, `MarginCallMet` or `MarginCallExpired` events as
+    /// the call changes state
+    pub fn check_fx_coverage(env: Env, loan_id: u64) -> Result<u32, ContractError> {
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+
+        if loan.status != LoanStatus::Active && loan.status != LoanStatus::Restructured {
+            return Err(ContractError::LoanNotActive);
+        }
+
+        let mut cross_currency: CrossCurrencyLoan = env
+            .storage()
+            .persistent()
+            .get(&format_cross_currency_key(loan_id))
+            .ok_or(ContractError::InvalidCurrency)?;
+
+        let now = env.ledger().timestamp();
+        let fx_rate = fetch_fx_rate(
+            &env,
+            &cross_currency.base_currency,
+            &cross_currency.loan_currency,
+        )?;
+        let collateral_value = convert_currency(
+            current_collateral_value(&env, loan.collateral_id)?,
+            fx_rate.rate,
+        )?;
+        if collateral_value <= 0 {
+            return Err(ContractError::CollateralValueError);
+        }
+
+        let ltv = loan
+            .outstanding_balance
+            .checked_mul(10000)
+            .ok_or(ContractError::CollateralValueError)?
+            / collateral_value;
+        let ltv = ltv.min(u32::MAX as i128) as u32;
+
+        cross_currency.exchange_rate = fx_rate.rate;
+        cross_currency.updated_at = now;
+        env.storage()
+            .persistent()
+            .set(&format_cross_currency_key(loan_id), &cross_currency);
+
+        let margin_call_ltv: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("mc_ltv"))
+            .unwrap_or(8500);
+
+        let open_call = env
+            .storage()
+            .persistent()
+            .get::<_, MarginCall>(&format_margin_call_key(loan_id))
+            .filter(|call| call.status == MarginCallStatus::Open);
+
+        match open_call {
+            Some(mut call) => {
+                if ltv <= margin_call_ltv {
+                    call.status = MarginCallStatus::Met;
+                    env.events()
+                        .publish((symbol_short!("mcall_met"),), (loan_id, ltv));
+                } else if now > call.deadline {
+                    call.status = MarginCallStatus::Expired;
+                    loan.callable = true;
+                    env.events()
+                        .publish((symbol_short!("mcall_exp"),), (loan_id, ltv));
+                }
+                env.storage()
+                    .persistent()
+                    .set(&format_margin_call_key(loan_id), &call);
+            }
+            None if ltv > margin_call_ltv => {
+                let max_ltv: u32 = env
+                    .storage()
+                    .instance()
+                    .get(&symbol_short!("max_ltv"))
+                    .unwrap_or(8000);
+                let required_value = loan
+                    .outstanding_balance
+                    .checked_mul(10000)
+                    .ok_or(ContractError::CollateralValueError)?
+                    / max_ltv as i128;
+                let period: u64 = env
+                    .storage()
+                    .instance()
+                    .get(&symbol_short!("mc_period"))
+                    .unwrap_or(86400 * 3);
+
+                let call = MarginCall {
+                    loan_id,
+                    ltv,
+                    shortfall: required_value - collateral_value,
+                    issued_at: now,
+                    deadline: now + period,
+                    status: MarginCallStatus::Open,
+                };
+                env.storage()
+                    .persistent()
+                    .set(&format_margin_call_key(loan_id), &call);
+
+                env.events().publish(
+                    (symbol_short!("fx_mcall"),),
+                    (loan_id, ltv, call.shortfall, call.deadline),
+                );
+            }
+            None => {}
+        }
+
+        loan.collateral_value = collateral_value;
+        loan.ltv = ltv;
+        loan.updated_at = now;
+        save_loan(&env, &loan);
+
+        Ok(ltv)
+    }
+
+    /// Get the currency terms of a cross-currency loan
+    pub fn get_cross_currency_loan(env: Env, loan_id: u64) -> Option<CrossCurrencyLoan> {
+        env.storage()
+            .persistent()
+            .get(&format_cross_currency_key(loan_id))
+    }
+
+    /// Get the latest margin call raised on a cross-currency loan
+    pub fn get_margin_call(env: Env, loan_id: u64) -> Option<MarginCall> {
+        env.storage()
+            .persistent()
+            .get(&format_margin_call_key(loan_id))
+    }
+
+    /// Re-evaluate a loan's LTV against the latest registry valuation
+    ///
+    /// Permissionless, so keepers can monitor loans as collateral values move.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    ///
+    /// # Returns
+    /// Current LTV (basis points)
+    ///
+    /// # Events
+    /// Emits `MarginCall` event if LTV is above the margin call threshold
+    pub fn evaluate_ltv(env: Env, loan_id: u64) -> Result<u32, ContractError> {
+        // Get loan
+        let storage_key = format_loan_storage_key(loan_id);
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::LoanNotFound)?;
+
+        if loan.status != LoanStatus::Active && loan.status != LoanStatus::Restructured {
+            return Err(ContractError::LoanNotActive);
+        }
+
+        let collateral_value = loan_collateral_value(&env, &loan)?;
+        let ltv = loan
+            .outstanding_balance
+            .checked_mul(10000)
+            .ok_or(ContractError::CollateralValueError)?
+            / collateral_value;
+        let ltv = ltv.min(u32::MAX as i128) as u32;
+
+        loan.collateral_value = collateral_value;
+        loan.ltv = ltv;
+        loan.updated_at = env.ledger().timestamp();
+        save_loan(&env, &loan);
+
+        let margin_call_ltv: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("mc_ltv"))
+            .unwrap_or(8500);
+
+        if ltv > margin_call_ltv {
+            env.events()
+                .publish((symbol_short!("margin"),), (loan_id, ltv, margin_call_ltv));
+        }
+
+        Ok(ltv)
+    }
+
+    /// Release part of a pledged warehouse receipt back to the borrower
+    ///
+    /// This contract is the receipt's secured party, so the registry's
+    /// consent check is satisfied here once the lender has approved. The
+    /// borrower and custodian still authorize the release in the registry.
+    /// The loan must stay within the maximum LTV against the reduced
+    /// valuation, otherwise the whole release is reverted.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    /// * `quantity` - Quantity of the commodity to release
+    ///
+    /// # Returns
+    /// LTV after the release (basis points)
+    ///
+    /// # Events
+    /// Emits `ReceiptReleased` event
+    pub fn release_receipt_collateral(
+        env: Env,
+        loan_id: u64,
+        quantity: u64,
+    ) -> Result<u32, ContractError> {
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+
+        if loan.status != LoanStatus::Active && loan.status != LoanStatus::Restructured {
+            return Err(ContractError::LoanNotActive);
+        }
+        require_lender(&env, &loan)?;
+
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("col_reg"))
+            .ok_or(ContractError::CollateralNotFound)?;
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [loan.collateral_id.into_val(&env), quantity.into_val(&env)],
+        );
+        env.invoke_contract::<Val>(&registry, &Symbol::new(&env, "release_from_receipt"), args);
+
+        // Re-check coverage against the reduced valuation
+        let collateral_value = loan_collateral_value(&env, &loan)?;
+        let ltv = loan
+            .outstanding_balance
+            .checked_mul(10000)
+            .ok_or(ContractError::CollateralValueError)?
+            / collateral_value;
+        let max_ltv: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("max_ltv"))
+            .unwrap_or(8000);
+        if ltv > max_ltv as i128 {
+            return Err(ContractError::InsufficientCollateral);
+        }
+        let ltv = ltv as u32;
+
+        loan.collateral_value = collateral_value;
+        loan.ltv = ltv;
+        loan.updated_at = env.ledger().timestamp();
+        save_loan(&env, &loan);
+
+        env.events()
+            .publish((symbol_short!("rcpt_rel"),), (loan_id, quantity, ltv));
+
+        Ok(ltv)
+    }
+
+    /// Attach a covenant to a loan
+    ///
+    /// The borrower offers covenants on a pending loan, so the lender accepts
+    /// them by funding; once funded, both parties must agree.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    /// * `covenant_type` - Condition to monitor
+    /// * `threshold` - Limit for the condition (see `CovenantType`)
+    /// * `action` - Consequence of a breach
+    ///
+    /// # Returns
+    /// Covenant ID
+    ///
+    /// # Events
+    /// Emits `CovenantAdded` event
+    pub fn add_covenant(
+        env: Env,
+        loan_id: u64,
+        covenant_type: CovenantType,
+        threshold: i128,
+        action: BreachAction,
+    ) -> Result<u64, ContractError> {
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+
+        loan.borrower.require_auth();
+        match loan.status {
+            LoanStatus::Pending => {}
+            LoanStatus::Active | LoanStatus::Restructured => {
+                require_lender(&env, &loan)?;
+            }
+            _ => return Err(ContractError::LoanNotActive),
+        }
+
+        let valid_threshold = match covenant_type {
+            CovenantType::RequiredInsurance => threshold == 0,
+            _ => threshold > 0,
+        };
+        let valid_action = match action {
+            BreachAction::RateIncrease(bps) => bps > 0 && bps <= 10000,
+            _ => true,
+        };
+        if !valid_threshold || !valid_action {
+            return Err(ContractError::InvalidCovenant);
+        }
+
+        let covenant_id: u64 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("next_cov"))
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("next_cov"), &(covenant_id + 1));
+
+        let now = env.ledger().timestamp();
+        let covenant = LoanCovenant {
+            id: covenant_id,
+            loan_id,
+            covenant_type,
+            threshold,
+            action,
+            status: CovenantStatus::Compliant,
+            last_value: 0,
+            breached_at: 0,
+            created_at: now,
+            updated_at: now,
+        };
+        env.storage()
+            .persistent()
+            .set(&format_covenant_key(covenant_id), &covenant);
+
+        let index_key = format_loan_covenants_key(loan_id);
+        let mut covenant_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .unwrap_or(Vec::new(&env));
+        covenant_ids.push_back(covenant_id);
+        env.storage().persistent().set(&index_key, &covenant_ids);
+
+        env.events().publish(
+            (symbol_short!("cov_add"),),
+            (loan_id, covenant_id, covenant_type, threshold),
+        );
+
+        Ok(covenant_id)
+    }
+
+    /// Waive a covenant (lender only)
+    ///
+    /// A breached covenant's consequences are reversed before it is waived.
+    ///
+    /// # Arguments
+    /// * `covenant_id` - ID of the covenant
+    ///
+    /// # Events
+    /// Emits `CovenantWaived` event
+    pub fn waive_covenant(env: Env, covenant_id: u64) -> Result<(), ContractError> {
+        let covenant_key = format_covenant_key(covenant_id);
+        let mut covenant: LoanCovenant = env
+            .storage()
+            .persistent()
+            .get(&covenant_key)
+            .ok_or(ContractError::CovenantNotFound)?;
+
+        let storage_key = format_loan_storage_key(covenant.loan_id);
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::LoanNotFound)?;
+
+        require_lender(&env, &loan)?;
+
+        if covenant.status == CovenantStatus::Waived {
+            return Err(ContractError::InvalidCovenant);
+        }
+
+        let now = env.ledger().timestamp();
+        if covenant.status == CovenantStatus::Breached {
+            apply_breach_action(&env, &mut loan, covenant.action, false, now)?;
+            save_loan(&env, &loan);
+        }
+
+        covenant.status = CovenantStatus::Waived;
+        covenant.updated_at = now;
+        env.storage().persistent().set(&covenant_key, &covenant);
+
+        env.events().publish(
+            (symbol_short!("cov_waive"),),
+            (covenant.loan_id, covenant_id),
+        );
+
+        Ok(())
+    }
+
+    /// Evaluate every covenant of a loan
+    ///
+    /// Permissionless, so keepers can monitor covenants. Collateral coverage
+    /// and insurance are read from the collateral registry, reputation from
+    /// the reputation registry and days past due from the loan itself.
+    /// Covenants that start failing are marked breached and their action is
+    /// applied; breached covenants that pass again are cured.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    ///
+    /// # Returns
+    /// Number of covenants currently breached
+    ///
+    /// # Events
+    /// Emits `CovenantBreached` or `CovenantCured` events on status changes
+    pub fn check_covenants(env: Env, loan_id: u64) -> Result<u32, ContractError> {
+        let storage_key = format_loan_storage_key(loan_id);
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::LoanNotFound)?;
+
+        if loan.status != LoanStatus::Active && loan.status != LoanStatus::Restructured {
+            return Err(ContractError::LoanNotActive);
+        }
+
+        let covenant_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&format_loan_covenants_key(loan_id))
+            .unwrap_or(Vec::new(&env));
+
+        let now = env.ledger().timestamp();
+        let mut breached: u32 = 0;
+        for covenant_id in covenant_ids.iter() {
+            let covenant_key = format_covenant_key(covenant_id);
+            let mut covenant: LoanCovenant = env
+                .storage()
+                .persistent()
+                .get(&covenant_key)
+                .ok_or(ContractError::CovenantNotFound)?;
+
+            if covenant.status == CovenantStatus::Waived {
+                continue;
+            }
+
+            let (value, compliant) = evaluate_covenant(&env, &loan, &covenant)?;
+            covenant.last_value = value;
+            covenant.updated_at = now;
+
+            match (covenant.status, compliant) {
+                (CovenantStatus::Compliant, false) => {
+                    covenant.status = CovenantStatus::Breached;
+                    covenant.breached_at = now;
+                    apply_breach_action(&env, &mut loan, covenant.action, true, now)?;
+                    env.events().publish(
+                        (symbol_short!("cov_brch"),),
+                        (loan_id, covenant_id, covenant.covenant_type, value),
+                    );
+                }
+                (CovenantStatus::Breached, true) => {
+                    covenant.status = CovenantStatus::Compliant;
+                    apply_breach_action(&env, &mut loan, covenant.action, false, now)?;
+                    env.events().publish(
+                        (symbol_short!("cov_cure"),),
+                        (loan_id, covenant_id, covenant.covenant_type, value),
+                    );
+                }
+                _ => {}
+            }
+
+            if covenant.status == CovenantStatus::Breached {
+                breached += 1;
+            }
+            env.storage().persistent().set(&covenant_key, &covenant);
+        }
+
+        loan.updated_at = now;
+        save_loan(&env, &loan);
+
+        Ok(breached)
+    }
+
+    /// Call a loan made callable by a covenant breach (lender only)
+    ///
+    /// The whole outstanding balance falls due immediately; the loan
+    /// defaults if it is not repaid within its grace period.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    ///
+    /// # Events
+    /// Emits `LoanCalled` event
+    pub fn call_loan(env: Env, loan_id: u64) -> Result<(), ContractError> {
+        let storage_key = format_loan_storage_key(loan_id);
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(ContractError::LoanNotFound)?;
+
+        require_lender(&env, &loan)?;
+
+        if loan.status != LoanStatus::Active && loan.status != LoanStatus::Restructured {
+            return Err(ContractError::LoanNotActive);
+        }
+        if !loan.callable {
+            return Err(ContractError::LoanNotCallable);
+        }
+
+        let now = env.ledger().timestamp();
+        loan.maturity_date = now;
+        loan.next_payment_date = store_schedule(&env, &loan, loan.outstanding_balance, now, now)?;
+        loan.callable = false;
+        loan.updated_at = now;
+        save_loan(&env, &loan);
+
+        env.events().publish(
+            (symbol_short!("loan_call"),),
+            (loan_id, loan.outstanding_balance, loan.next_payment_date),
+        );
+
+        Ok(())
+    }
+
+    /// Get the covenants attached to a loan
+    pub fn get_covenants(env: Env, loan_id: u64) -> Vec<LoanCovenant> {
+        let covenant_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&format_loan_covenants_key(loan_id))
+            .unwrap_or(Vec::new(&env));
+
+        let mut covenants = Vec::new(&env);
+        for covenant_id in covenant_ids.iter() {
+            if let Some(covenant) = env
+                .storage()
+                .persistent()
+                .get::<_, LoanCovenant>(&format_covenant_key(covenant_id))
+            {
+                covenants.push_back(covenant);
+            }
+        }
+        covenants
+    }
+
+    /// Set the governance address allowed to move the floating base rate
+    /// (admin only)
+    ///
+    /// # Arguments
+    /// * `governance` - Governance contract address
+    pub fn set_governance(env: Env, governance: Address) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("gov"), &governance);
+
+        Ok(())
+    }
+
+    /// Get governance address
+    pub fn get_governance(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("gov"))
+    }
+
+    /// Set the base rate floating-rate loans reprice from (governance only)
+    ///
+    /// Takes effect for each loan at its next reset.
+    ///
+    /// # Arguments
+    /// * `base_rate` - Annual base rate in basis points
+    ///
+    /// # Events
+    /// Emits `BaseRateSet` event
+    pub fn set_base_rate(env: Env, base_rate: u32) -> Result<(), ContractError> {
+        let governance: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("gov"))
+            .ok_or(ContractError::Unauthorized)?;
+
+        governance.require_auth();
+
+        if base_rate > 100000 {
+            return Err(ContractError::InvalidInterestRate);
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("base_rate"), &base_rate);
+
+        env.events()
+            .publish((symbol_short!("base_rate"),), base_rate);
+
+        Ok(())
+    }
+
+    /// Get the floating base rate in basis points
+    pub fn get_base_rate(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("base_rate"))
+            .unwrap_or(0)
+    }
+
+    /// Set the floor, cap and reset period of a pending floating-rate loan
+    /// (borrower only)
+    ///
+    /// Loans funded without terms reset monthly between 0% and the maximum
+    /// rate.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    /// * `rate_floor` - Minimum rate in basis points
+    /// * `rate_cap` - Maximum rate in basis points
+    /// * `reset_period` - Seconds between rate resets
+    pub fn set_floating_rate_terms(
+        env: Env,
+        loan_id: u64,
+        rate_floor: u32,
+        rate_cap: u32,
+        reset_period: u64,
+    ) -> Result<(), ContractError> {
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+
+        loan.borrower.require_auth();
+
+        if loan.status != LoanStatus::Pending {
+            return Err(ContractError::LoanNotPending);
+        }
+        if loan.interest_type != InterestType::Floating {
+            return Err(ContractError::InvalidLoanData);
+        }
+        if rate_floor > rate_cap || rate_cap > 100000 {
+            return Err(ContractError::InvalidInterestRate);
+        }
+        if reset_period == 0 {
+            return Err(ContractError::InvalidTerm);
+        }
+
+        env.storage().persistent().set(
+            &format_floating_terms_key(loan_id),
+            &FloatingRateTerms {
+                loan_id,
+                rate_floor,
+                rate_cap,
+                reset_period,
+                next_reset: 0,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Reset a floating-rate loan to the current base rate plus the
+    /// borrower's credit spread, within its floor and cap
+    ///
+    /// Permissionless, so keepers can reset loans once a period has elapsed.
+    /// Interest accrued before the reset keeps the old rate.
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    ///
+    /// # Returns
+    /// The new interest rate in basis points
+    ///
+    /// # Events
+    /// Emits `RateReset` event
+    pub fn reset_floating_rate(env: Env, loan_id: u64) -> Result<u32, ContractError> {
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+
+        if loan.interest_type != InterestType::Floating {
+            return Err(ContractError::InvalidLoanData);
+        }
+        if loan.status != LoanStatus::Active && loan.status != LoanStatus::Restructured {
+            return Err(ContractError::LoanNotActive);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut terms = read_floating_terms(&env, loan_id);
+        if now < terms.next_reset {
+            return Err(ContractError::RateResetNotDue);
+        }
+
+        reset_rate(&env, &mut loan, &terms, now, "floating reset")?;
+        loan.updated_at = now;
+        save_loan(&env, &loan);
+
+        // Skip any periods missed since the last reset
+        while terms.next_reset <= now {
+            terms.next_reset += terms.reset_period;
+        }
+        env.storage()
+            .persistent()
+            .set(&format_floating_terms_key(loan_id), &terms);
+
+        Ok(loan.interest_rate)
+    }
+
+    /// Get a floating-rate loan's reset terms
+    pub fn get_floating_rate_terms(env: Env, loan_id: u64) -> Option<FloatingRateTerms> {
+        env.storage()
+            .persistent()
+            .get(&format_floating_terms_key(loan_id))
+    }
+
+    /// Get the rate resets recorded for a loan, oldest first
+    pub fn get_rate_history(env: Env, loan_id: u64) -> Vec<InterestRateAdjustment> {
+        read_rate_history(&env, loan_id)
+    }
+
+    /// Open a revolving credit line secured by collateral or a basket
+    ///
+    /// The security is locked with this contract as secured party and the
+    /// limit must fit within its lendable value at the maximum LTV. The line
+    /// opens for draws once a lender commits the limit.
+    ///
+    /// # Arguments
+    /// * `borrower` - Address of the borrower
+    /// * `security` - Collateral or basket securing the line
+    /// * `asset` - Token the line is drawn and repaid in
+    /// * `limit` - Maximum drawn balance
+    /// * `interest_rate` - Annual rate on the drawn balance (basis points)
+    /// * `commitment_fee_rate` - Annual fee on the undrawn limit (basis points)
+    /// * `term` - Availability period in seconds
+    ///
+    /// # Returns
+    /// Credit line ID
+    ///
+    /// # Events
+    /// Emits `CreditLineOpened` event
+    pub fn open_credit_line(
+        env: Env,
+        borrower: Address,
+        security: CreditSecurity,
+        asset: Address,
+        limit: i128,
+        interest_rate: u32,
+        commitment_fee_rate: u32,
+        term: u64,
+    ) -> Result<u64, ContractError> {
+        borrower.require_auth();
+
+        if limit <= 0 || commitment_fee_rate > 10000 {
+            return Err(ContractError::InvalidCreditLine);
+        }
+        if interest_rate > 100000 {
+            return Err(ContractError::InvalidInterestRate);
+        }
+        validate_term(&env, term)?;
+
+        if limit > borrowing_base(&env, security)? {
+            return Err(ContractError::InsufficientCollateral);
+        }
+
+        let line_id: u64 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("next_line"))
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("next_line"), &(line_id + 1));
+
+        lock_credit_security(&env, security, line_id);
+
+        let now = env.ledger().timestamp();
+        let line = CreditLine {
+            id: line_id,
+            borrower: borrower.clone(),
+            lender: None,
+            asset,
+            security,
+            limit,
+            drawn: 0,
+            interest_rate,
+            commitment_fee_rate,
+            accrued_interest: 0,
+            accrued_fees: 0,
+            total_interest_paid: 0,
+            total_fees_paid: 0,
+            term,
+            maturity_date: now + term,
+            grace_period: DEFAULT_GRACE_PERIOD,
+            last_accrual: now,
+            status: CreditLineStatus::Pending,
+            created_at: now,
+            updated_at: now,
+        };
+        env.storage()
+            .persistent()
+            .set(&format_credit_line_key(line_id), &line);
+
+        env.events().publish(
+            (symbol_short!("line_open"),),
+            (line_id, borrower, limit, interest_rate),
+        );
+
+        Ok(line_id)
+    }
+
+    /// Commit the limit of a pending credit line (lender)
+    ///
+    /// The full limit is transferred into the facility; the availability
+    /// period starts now.
+    ///
+    /// # Arguments
+    /// * `line_id` - ID of the credit line
+    /// * `lender` - Lender committing the limit
+    ///
+    /// # Events
+    /// Emits `CreditLineCommitted` event
+    pub fn commit_credit_line(
+        env: Env,
+        line_id: u64,
+        lender: Address,
+    ) -> Result<(), ContractError> {
+        lender.require_auth();
+
+        let mut line = read_credit_line(&env, line_id)?;
+        if line.status != CreditLineStatus::Pending {
+            return Err(ContractError::InvalidCreditLine);
+        }
+
+        token::Client::new(&env, &line.asset).transfer(
+            &lender,
+            &env.current_contract_address(),
+            &line.limit,
+        );
+
+        let now = env.ledger().timestamp();
+        line.lender = Some(lender.clone());
+        line.status = CreditLineStatus::Open;
+        line.maturity_date = now + line.term;
+        line.last_accrual = now;
+        line.updated_at = now;
+        env.storage()
+            .persistent()
+            .set(&format_credit_line_key(line_id), &line);
+
+        env.events()
+            .publish((symbol_short!("line_cmt"),), (line_id, lender, line.limit));
+
+        Ok(())
+    }
+
+    /// Draw from an open credit line (borrower only)
+    ///
+    /// The drawn balance may not exceed the limit or the security's current
+    /// borrowing base.
+    ///
+    /// # Arguments
+    /// * `line_id` - ID of the credit line
+    /// * `amount` - Amount to draw
+    ///
+    /// # Events
+    /// Emits `CreditLineDrawn` event
+    pub fn draw_credit_line(env: Env, line_id: u64, amount: i128) -> Result<(), ContractError> {
+        let mut line = read_credit_line(&env, line_id)?;
+        line.borrower.require_auth();
+
+        match line.status {
+            CreditLineStatus::Open => {}
+            CreditLineStatus::Frozen => return Err(ContractError::CreditLineFrozen),
+            _ => return Err(ContractError::InvalidCreditLine),
+        }
+
+        let now = env.ledger().timestamp();
+        if amount <= 0 || now >= line.maturity_date {
+            return Err(ContractError::InvalidCreditLine);
+        }
+
+        let drawn = line.drawn + amount;
+        if drawn > line.limit || drawn > borrowing_base(&env, line.security)? {
+            return Err(ContractError::CreditLimitExceeded);
+        }
+
+        accrue_credit_line(&mut line, now)?;
+        line.drawn = drawn;
+        line.updated_at = now;
+        env.storage()
+            .persistent()
+            .set(&format_credit_line_key(line_id), &line);
+
+        token::Client::new(&env, &line.asset).transfer(
+            &env.current_contract_address(),
+            &line.borrower,
+            &amount,
+        );
+
+        env.events()
+            .publish((symbol_short!("line_draw"),), (line_id, amount, drawn));
+
+        Ok(())
+    }
+
+    /// Repay a credit line
+    ///
+    /// Payments settle commitment fees, then interest, then the drawn
+    /// balance. Fees and interest go to the lender, less the protocol fee on
+    /// interest; repaid principal returns to the facility for redrawing.
+    ///
+    /// # Arguments
+    /// * `line_id` - ID of the credit line
+    /// * `amount` - Repayment amount
+    /// * `payer` - Address making the repayment
+    ///
+    /// # Events
+    /// Emits `CreditLineRepaid` event
+    pub fn repay_credit_line(
+        env: Env,
+        line_id: u64,
+        amount: i128,
+        payer: Address,
+    ) -> Result<(), ContractError> {
+        payer.require_auth();
+
+        let mut line = read_credit_line(&env, line_id)?;
+        if line.status == CreditLineStatus::Pending || line.status == CreditLineStatus::Closed {
+            return Err(ContractError::InvalidCreditLine);
+        }
+        if amount <= 0 {
+            return Err(ContractError::InvalidRepaymentAmount);
+        }
+
+        let now = env.ledger().timestamp();
+        accrue_credit_line(&mut line, now)?;
+        if amount > line.accrued_fees + line.accrued_interest + line.drawn {
+            return Err(ContractError::RepaymentExceedsBalance);
+        }
+
+        let fee_payment = cmp::min(amount, line.accrued_fees);
+        let interest_payment = cmp::min(amount - fee_payment, line.accrued_interest);
+        let principal_payment = amount - fee_payment - interest_payment;
+
+        line.accrued_fees -= fee_payment;
+        line.accrued_interest -= interest_payment;
+        line.drawn -= principal_payment;
+        line.total_fees_paid += fee_payment;
+        line.total_interest_paid += interest_payment;
+        line.updated_at = now;
+        env.storage()
+            .persistent()
+            .set(&format_credit_line_key(line_id), &line);
+
+        let lender = line
+            .lender
+            .clone()
+            .ok_or(ContractError::InvalidCreditLine)?;
+        let (treasury, fee) = protocol_fee(&env, interest_payment)?;
+        let token_client = token::Client::new(&env, &line.asset);
+        let lender_share = fee_payment + interest_payment - fee;
+        if lender_share > 0 {
+            token_client.transfer(&payer, &lender, &lender_share);
+        }
+        if principal_payment > 0 {
+            token_client.transfer(&payer, &env.current_contract_address(), &principal_payment);
+        }
+        deposit_protocol_fee(&env, &treasury, &line.asset, &payer, fee, line_id);
+
+        env.events()
+            .publish((symbol_short!("line_repy"),), (line_id, amount, line.drawn));
+
+        Ok(())
+    }
+
+    /// Attach a covenant to a credit line
+    ///
+    /// A breached covenant freezes the line. Days-past-due covenants do not
+    /// apply to lines, and insurance covenants need single-collateral security.
+    ///
+    /// # Arguments
+    /// * `line_id` - ID of the credit line
+    /// * `covenant_type` - Condition to monitor
+    /// * `threshold` - Limit for the condition (see `CovenantType`)
+    ///
+    /// # Returns
+    /// Covenant ID
+    ///
+    /// # Events
+    /// Emits `CovenantAdded` event
+    pub fn add_credit_line_covenant(
+        env: Env,
+        line_id: u64,
+        covenant_type: CovenantType,
+        threshold: i128,
+    ) -> Result<u64, ContractError> {
+        let line = read_credit_line(&env, line_id)?;
+
+        line.borrower.require_auth();
+        match (line.status, &line.lender) {
+            (CreditLineStatus::Pending, _) => {}
+            (CreditLineStatus::Open | CreditLineStatus::Frozen, Some(lender)) => {
+                lender.require_auth()
+            }
+            _ => return Err(ContractError::InvalidCreditLine),
+        }
+
+        let valid = match (covenant_type, line.security) {
+            (CovenantType::MaxDaysPastDue, _) => false,
+            (CovenantType::RequiredInsurance, CreditSecurity::Collateral(_)) => threshold == 0,
+            (CovenantType::RequiredInsurance, CreditSecurity::Basket(_)) => false,
+            _ => threshold > 0,
+        };
+        if !valid {
+            return Err(ContractError::InvalidCovenant);
+        }
+
+        let covenant_id: u64 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("next_cov"))
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("next_cov"), &(covenant_id + 1));
+
+        // Line covenants carry the line ID in `loan_id`
+        let now = env.ledger().timestamp();
+        env.storage().persistent().set(
+            &format_covenant_key(covenant_id),
+            &LoanCovenant {
+                id: covenant_id,
+                loan_id: line_id,
+                covenant_type,
+                threshold,
+                action: BreachAction::Notify,
+                status: CovenantStatus::Compliant,
+                last_value: 0,
+                breached_at: 0,
+                created_at: now,
+                updated_at: now,
+            },
+        );
+
+        let index_key = format_line_covenants_key(line_id);
+        let mut covenant_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .unwrap_or(Vec::new(&env));
+        covenant_ids.push_back(covenant_id);
+        env.storage().persistent().set(&index_key, &covenant_ids);
+
+        env.events().publish(
+            (symbol_short!("cov_add"),),
+            (line_id, covenant_id, covenant_type, threshold),
+        );
+
+        Ok(covenant_id)
+    }
+
+    /// Re-evaluate a credit line's collateral coverage and covenants
+    ///
+    /// Permissionless, so keepers can monitor lines. The line freezes when
+    /// the drawn balance exceeds the security's borrowing base or a covenant
+    /// is breached, and reopens once both recover. A line still owing
+    /// anything once the grace period after maturity has passed defaults.
+    ///
+    /// # Arguments
+    /// * `line_id` - ID of the credit line
+    ///
+    /// # Returns
+    /// Current status of the line
+    ///
+    /// # Events
+    /// Emits `CreditLineFrozen` or `CreditLineUnfrozen` on status changes,
+    /// `CovenantBreached` or `CovenantCured` for covenant changes, and
+    /// `CreditLineDefaulted` on default
+    pub fn check_credit_line(env: Env, line_id: u64) -> Result<CreditLineStatus, ContractError> {
+        let mut line = read_credit_line(&env, line_id)?;
+        if line.status != CreditLineStatus::Open && line.status != CreditLineStatus::Frozen {
+            return Err(ContractError::InvalidCreditLine);
+        }
+
+        let now = env.ledger().timestamp();
+        if now > line.maturity_date + line.grace_period {
+            accrue_credit_line(&mut line, now)?;
+            let owed = line.drawn + line.accrued_interest + line.accrued_fees;
+            if owed > 0 {
+                line.status = CreditLineStatus::Defaulted;
+                line.updated_at = now;
+                env.storage()
+                    .persistent()
+                    .set(&format_credit_line_key(line_id), &line);
+
+                env.events()
+                    .publish((symbol_short!("line_def"),), (line_id, owed));
+
+                return Ok(CreditLineStatus::Defaulted);
+            }
+        }
+
+        let base = borrowing_base(&env, line.security)?;
+        let mut compliant = line.drawn <= base;
+
+        let covenant_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&format_line_covenants_key(line_id))
+            .unwrap_or(Vec::new(&env));
+        for covenant_id in covenant_ids.iter() {
+            let covenant_key = format_covenant_key(covenant_id);
+            let mut covenant: LoanCovenant = env
+                .storage()
+                .persistent()
+                .get(&covenant_key)
+                .ok_or(ContractError::CovenantNotFound)?;
+
+            if covenant.status == CovenantStatus::Waived {
+                continue;
+            }
+
+            let (value, holds) = evaluate_line_covenant(&env, &line, &covenant)?;
+            covenant.last_value = value;
+            covenant.updated_at = now;
+            match (covenant.status, holds) {
+                (CovenantStatus::Compliant, false) => {
+                    covenant.status = CovenantStatus::Breached;
+                    covenant.breached_at = now;
+                    env.events().publish(
+                        (symbol_short!("cov_brch"),),
+                        (line_id, covenant_id, covenant.covenant_type, value),
+                    );
+                }
+                (CovenantStatus::Breached, true) => {
+                    covenant.status = CovenantStatus::Compliant;
+                    env.events().publish(
+                        (symbol_short!("cov_cure"),),
+                        (line_id, covenant_id, covenant.covenant_type, value),
+                    );
+                }
+                _ => {}
+            }
+            compliant &= holds;
+            env.storage().persistent().set(&covenant_key, &covenant);
+        }
+
+        let status = if compliant {
+            CreditLineStatus::Open
+        } else {
+            CreditLineStatus::Frozen
+        };
+        if status != line.status {
+            line.status = status;
+            line.updated_at = now;
+            env.storage()
+                .persistent()
+                .set(&format_credit_line_key(line_id), &line);
+
+            let topic = if compliant {
+                symbol_short!("line_unfz")
+            } else {
+                symbol_short!("line_frz")
+            };
+            env.events().publish((topic,), (line_id, line.drawn, base));
+        }
+
+        Ok(status)
+    }
+
+    /// Close a credit line and release its security
+    ///
+    /// The borrower may close a line at any time and the lender after
+    /// maturity, once nothing is drawn or owed. The committed limit is
+    /// returned to the lender.
+    ///
+    /// # Arguments
+    /// * `line_id` - ID of the credit line
+    /// * `caller` - Borrower or lender closing the line
+    ///
+    /// # Events
+    /// Emits `CreditLineClosed` event
+    pub fn close_credit_line(env: Env, line_id: u64, caller: Address) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let mut line = read_credit_line(&env, line_id)?;
+        if line.status == CreditLineStatus::Closed {
+            return Err(ContractError::InvalidCreditLine);
+        }
+
+        let now = env.ledger().timestamp();
+        let is_lender = line.lender.as_ref() == Some(&caller);
+        if caller != line.borrower && !(is_lender && now >= line.maturity_date) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if let Some(lender) = line.lender.clone() {
+            accrue_credit_line(&mut line, now)?;
+            if line.drawn > 0 || line.accrued_interest > 0 || line.accrued_fees > 0 {
+                return Err(ContractError::CreditLineOutstanding);
+            }
+            token::Client::new(&env, &line.asset).transfer(
+                &env.current_contract_address(),
+                &lender,
+                &line.limit,
+            );
+        }
+
+        unlock_credit_security(&env, line.security, &line.borrower);
+
+        line.status = CreditLineStatus::Closed;
+        line.updated_at = now;
+        env.storage()
+            .persistent()
+            .set(&format_credit_line_key(line_id), &line);
+
+        env.events()
+            .publish((symbol_short!("line_cls"),), (line_id, caller));
+
+        Ok(())
+    }
+
+    /// Seize a defaulted credit line's security (lender only)
+    ///
+    /// The security passes to the lender through the collateral registry,
+    /// the undrawn commitment is returned, and the amount owed is reported
+    /// as a default to risk assessment and the reputation registry. The
+    /// line is closed.
+    ///
+    /// # Arguments
+    /// * `line_id` - ID of the credit line
+    ///
+    /// # Returns
+    /// Amount owed at seizure
+    ///
+    /// # Events
+    /// Emits `CreditLineSeized` event
+    pub fn seize_credit_security(env: Env, line_id: u64) -> Result<i128, ContractError> {
+        let mut line = read_credit_line(&env, line_id)?;
+        let lender = line
+            .lender
+            .clone()
+            .ok_or(ContractError::InvalidCreditLine)?;
+        lender.require_auth();
+
+        if line.status != CreditLineStatus::Defaulted {
+            return Err(ContractError::LoanNotDefaulted);
+        }
+
+        let now = env.ledger().timestamp();
+        accrue_credit_line(&mut line, now)?;
+        let exposure = line.drawn + line.accrued_interest + line.accrued_fees;
+
+        // Registry transfers the security to the lender
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("col_reg"))
+            .ok_or(ContractError::CollateralNotFound)?;
+        let (function, security_id) = match line.security {
+            CreditSecurity::Collateral(collateral_id) => ("seize_collateral", collateral_id),
+            CreditSecurity::Basket(basket_id) => ("seize_basket", basket_id),
+        };
+        let args: Vec<Val> =
+            Vec::from_array(&env, [security_id.into_val(&env), lender.into_val(&env)]);
+        env.invoke_contract::<Val>(&registry, &Symbol::new(&env, function), args);
+
+        let undrawn = line.limit - line.drawn;
+        if undrawn > 0 {
+            token::Client::new(&env, &line.asset).transfer(
+                &env.current_contract_address(),
+                &lender,
+                &undrawn,
+            );
+        }
+
+        if let Some(risk_assessment) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&symbol_short!("risk_mgr"))
+        {
+            let args: Vec<Val> = Vec::from_array(
+                &env,
+                [
+                    line.borrower.into_val(&env),
+                    line_id.into_val(&env),
+                    exposure.into_val(&env),
+                ],
+            );
+            env.invoke_contract::<Val>(
+                &risk_assessment,
+                &Symbol::new(&env, "record_default"),
+                args,
+            );
+        }
+        report_reputation(&env, &line.borrower, ReputationOutcome::Default, exposure);
+
+        line.status = CreditLineStatus::Closed;
+        line.updated_at = now;
+        env.storage()
+            .persistent()
+            .set(&format_credit_line_key(line_id), &line);
+
+        env.events()
+            .publish((symbol_short!("line_seiz"),), (line_id, lender, exposure));
+
+        Ok(exposure)
+    }
+
+    /// Get a credit line with interest and fees accrued to now
+    pub fn get_credit_line(env: Env, line_id: u64) -> Result<CreditLine, ContractError> {
+        let mut line = read_credit_line(&env, line_id)?;
+        if line.status != CreditLineStatus::Pending && line.status != CreditLineStatus::Closed {
+            accrue_credit_line(&mut line, env.ledger().timestamp())?;
+        }
+        Ok(line)
+    }
+
+    /// Get the covenants attached to a credit line
+    pub fn get_credit_line_covenants(env: Env, line_id: u64) -> Vec<LoanCovenant> {
+        let covenant_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&format_line_covenants_key(line_id))
+            .unwrap_or(Vec::new(&env));
+
+        let mut covenants = Vec::new(&env);
+        for covenant_id in covenant_ids.iter() {
+            if let Some(covenant) = env
+                .storage()
+                .persistent()
+                .get::<_, LoanCovenant>(&format_covenant_key(covenant_id))
+            {
+                covenants.push_back(covenant);
+            }
+        }
+        covenants
+    }
+
+    /// Get loan portfolio analytics
+    ///
+    /// Read from running aggregates maintained as loans change.
+    ///
+    /// # Arguments
+    /// * `borrower` - Address of borrower (optional for all loans)
+    ///
+    /// # Returns
+    /// Portfolio analytics
+    pub fn get_portfolio_analytics(
+        env: Env,
+        borrower: Option<Address>,
+    ) -> Result<PortfolioAnalytics, ContractError> {
+        let scope = match borrower {
+            Some(borrower) => PortfolioScope::Borrower(borrower),
+            None => PortfolioScope::All,
+        };
+        Ok(read_portfolio(&env, &scope).analytics())
+    }
+
+    /// Get portfolio analytics for loans secured by one collateral type
+    ///
+    /// # Arguments
+    /// * `collateral_type` - Asset type of the collateral
+    ///
+    /// # Returns
+    /// Portfolio analytics
+    pub fn get_collateral_type_analytics(
+        env: Env,
+        collateral_type: CollateralType,
+    ) -> PortfolioAnalytics {
+        read_portfolio(&env, &PortfolioScope::CollateralType(collateral_type)).analytics()
+    }
+
+    /// Get the running portfolio totals behind the analytics
+    pub fn get_portfolio_aggregate(env: Env, scope: PortfolioScope) -> PortfolioAggregate {
+        read_portfolio(&env, &scope)
+    }
+
+    /// Get portfolio risk metrics
+    ///
+    /// # Arguments
+    /// * `borrower` - Address of borrower (optional for all loans)
+    ///
+    /// # Returns
+    /// Default probability, loss given default and expected loss derived
+    /// from recorded defaults and recoveries
+    pub fn get_risk_metrics(env: Env, borrower: Option<Address>) -> RiskMetrics {
+        let scope = match borrower {
+            Some(borrower) => PortfolioScope::Borrower(borrower),
+            None => PortfolioScope::All,
+        };
+        read_portfolio(&env, &scope).risk_metrics()
+    }
+
+    /// Get the payment performance of a loan
+    ///
+    /// # Arguments
+    /// * `loan_id` - ID of the loan
+    ///
+    /// # Returns
+    /// Payment counts and scores, with days past due evaluated now
+    pub fn get_performance_metrics(
+        env: Env,
+        loan_id: u64,
+    ) -> Result<PerformanceMetrics, ContractError> {
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+
+        let mut metrics: PerformanceMetrics = env
+            .storage()
+            .persistent()
+            .get(&format_performance_key(loan_id))
+            .unwrap_or(PerformanceMetrics::new(loan_id));
+
+        if loan.status == LoanStatus::Active
+            || loan.status == LoanStatus::Restructured
+            || loan.status == LoanStatus::Defaulted
+        {
+            metrics.days_past_due = env
+                .ledger()
+                .timestamp()
+                .saturating_sub(loan.next_payment_date)
+                / 86400;
+        }
+
+        Ok(metrics)
+    }
+}
+
+// Helper functions
+
+/// Store a loan and fold the change into the running portfolio aggregates
+fn save_loan(env: &Env, loan: &Loan) {
+    let storage_key = format_loan_storage_key(loan.id);
+    let previous: Option<Loan> = env.storage().persistent().get(&storage_key);
+    env.storage().persistent().set(&storage_key, loan);
+
+    for scope in [
+        PortfolioScope::All,
+        PortfolioScope::Borrower(loan.borrower.clone()),
+        PortfolioScope::CollateralType(loan.collateral_type),
+    ] {
+        let mut aggregate = read_portfolio(env, &scope);
+        aggregate.update(previous.as_ref(), loan);
+        env.storage()
+            .persistent()
+            .set(&format_portfolio_key(&scope), &aggregate);
+    }
+}
+
+fn read_portfolio(env: &Env, scope: &PortfolioScope) -> PortfolioAggregate {
+    env.storage()
+        .persistent()
+        .get(&format_portfolio_key(scope))
+        .unwrap_or_default()
+}
+
+/// Record a payment outcome in a loan's performance metrics
+fn update_performance(env: &Env, loan_id: u64, record: impl FnOnce(&mut PerformanceMetrics)) {
+    let key = format_performance_key(loan_id);
+    let mut metrics: PerformanceMetrics = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(PerformanceMetrics::new(loan_id));
+    record(&mut metrics);
+    metrics.refresh();
+    env.storage().persistent().set(&key, &metrics);
+}
+
+/// Lock collateral in the registry with this contract as secured party.
+fn lock_loan_collateral(env: &Env, collateral_id: u64, loan_id: u64) {
+    let registry: Address = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("col_reg"))
+        .unwrap();
+
+    let args: Vec<Val> = Vec::from_array(
+        env,
+        [
+            collateral_id.into_val(env),
+            loan_id.into_val(env),
+            env.current_contract_address().into_val(env),
+        ],
+    );
+    env.invoke_contract::<Val>(&registry, &Symbol::new(env, "lock_collateral_for"), args);
+}
+
+/// Release collateral held by this contract in the registry.
+///
+/// Partial liquidators keep their claims: once any share has been
+/// liquidated, the owner only gets the rest back, as a claim of its own.
+fn release_loan_collateral(env: &Env, collateral_id: u64, owner: &Address) {
+    let registry: Address = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("col_reg"))
+        .unwrap();
+
+    let args: Vec<Val> = Vec::from_array(env, [collateral_id.into_val(env)]);
+    let liquidated: u32 = env.invoke_contract(
+        &registry,
+        &Symbol::new(env, "get_liquidated_share"),
+        args.clone(),
+    );
+    if liquidated == 0 {
+        env.invoke_contract::<Val>(&registry, &Symbol::new(env, "unlock_collateral"), args);
+        return;
+    }
+
+    let args: Vec<Val> = Vec::from_array(
+        env,
+        [
+            collateral_id.into_val(env),
+            owner.into_val(env),
+            10000u32.into_val(env),
+        ],
+    );
+    env.invoke_contract::<u32>(&registry, &Symbol::new(env, "liquidate_collateral"), args);
+}
+
+fn read_credit_line(env: &Env, line_id: u64) -> Result<CreditLine, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&format_credit_line_key(line_id))
+        .ok_or(ContractError::CreditLineNotFound)
+}
+
+/// Accrue interest on the drawn balance and commitment fees on the undrawn
+/// limit; commitment fees stop at maturity
+fn accrue_credit_line(line: &mut CreditLine, now: u64) -> Result<(), ContractError> {
+    if now <= line.last_accrual {
+        return Ok(());
+    }
+
+    let (elapsed, year) = accrual_seconds(DayCountConvention::Act365, line.last_accrual, now);
+    line.accrued_interest += simple_interest(line.drawn, line.interest_rate, elapsed, year)
+        .ok_or(ContractError::InterestCalculationError)?;
+
+    let fee_end = now.min(line.maturity_date);
+    if fee_end > line.last_accrual {
+        let (elapsed, year) =
+            accrual_seconds(DayCountConvention::Act365, line.last_accrual, fee_end);
+        line.accrued_fees += simple_interest(
+            line.limit - line.drawn,
+            line.commitment_fee_rate,
+            elapsed,
+            year,
+        )
+        .ok_or(ContractError::InterestCalculationError)?;
+    }
+
+    line.last_accrual = now;
+    Ok(())
+}
+
+/// Amount a credit line's security supports at the maximum LTV
+fn borrowing_base(env: &Env, security: CreditSecurity) -> Result<i128, ContractError> {
+    let max_ltv: u32 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("max_ltv"))
+        .unwrap_or(8000);
+
+    Ok(credit_security_value(env, security)?
+        .checked_mul(max_ltv as i128)
+        .ok_or(ContractError::CollateralValueError)?
+        / 10000)
+}
+
+/// Lendable value of a credit line's security
+fn credit_security_value(env: &Env, security: CreditSecurity) -> Result<i128, ContractError> {
+    match security {
+        CreditSecurity::Collateral(collateral_id) => current_collateral_value(env, collateral_id),
+        CreditSecurity::Basket(basket_id) => {
+            let registry: Address = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("col_reg"))
+                .ok_or(ContractError::Unauthorized)?;
+
+            let args: Vec<Val> = Vec::from_array(env, [basket_id.into_val(env)]);
+            let valuation = match env.try_invoke_contract::<BasketValuation, soroban_sdk::Error>(
+                &registry,
+                &Symbol::new(env, "get_basket_valuation"),
+                args,
+            ) {
+                Ok(Ok(valuation)) => valuation,
+                _ => return Err(ContractError::CollateralValueError),
+            };
+
+            // Every member must pass the checks single collateral does
+            if !valuation.verified
+                || env.ledger().timestamp() > valuation.oldest_valuation + max_valuation_age(env)
+                || valuation.adjusted_value <= 0
+            {
+                return Err(ContractError::CollateralValueError);
+            }
+
+            Ok(valuation.adjusted_value)
+        }
+    }
+}
+
+/// Lock a credit line's security with this contract as secured party.
+fn lock_credit_security(env: &Env, security: CreditSecurity, line_id: u64) {
+    match security {
+        CreditSecurity::Collateral(collateral_id) => {
+            lock_loan_collateral(env, collateral_id, line_id)
+        }
+        CreditSecurity::Basket(basket_id) => {
+            let registry: Address = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("col_reg"))
+                .unwrap();
+
+            let args: Vec<Val> = Vec::from_array(
+                env,
+                [
+                    basket_id.into_val(env),
+                    line_id.into_val(env),
+                    env.current_contract_address().into_val(env),
+                ],
+            );
+            env.invoke_contract::<Val>(&registry, &Symbol::new(env, "lock_basket_for"), args);
+        }
+    }
+}
+
+/// Release a credit line's security in the collateral registry.
+fn unlock_credit_security(env: &Env, security: CreditSecurity, borrower: &Address) {
+    match security {
+        CreditSecurity::Collateral(collateral_id) => {
+            release_loan_collateral(env, collateral_id, borrower)
+        }
+        CreditSecurity::Basket(basket_id) => {
+            let registry: Address = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("col_reg"))
+                .unwrap();
+
+            let args: Vec<Val> = Vec::from_array(env, [basket_id.into_val(env)]);
+            env.invoke_contract::<Val>(&registry, &Symbol::new(env, "unlock_basket"), args);
+        }
+    }
+}
+
+/// Load a securitization
+fn read_securitization(
+    env: &Env,
+    securitization_id: u64,
+) -> Result<LoanSecuritization, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&format_securitization_key(securitization_id))
+        .ok_or(ContractError::LoanNotFound)
+}
+
+/// Load a securitization tranche
+fn read_tranche(
+    env: &Env,
+    securitization_id: u64,
+    class: TrancheClass,
+) -> Result<Tranche, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&format_tranche_key(securitization_id, class))
+        .ok_or(ContractError::LoanNotFound)
+}
+
+/// Load a holder's tranche position, or an empty one settled to date
+fn read_tranche_position(env: &Env, tranche: &Tranche, holder: &Address) -> TranchePosition {
+    env.storage()
+        .persistent()
+        .get(&format_tranche_position_key(
+            tranche.securitization_id,
+            tranche.class,
+            holder,
+        ))
+        .unwrap_or(TranchePosition {
+            securitization_id: tranche.securitization_id,
+            class: tranche.class,
+            holder: holder.clone(),
+            units: 0,
+            payout_checkpoint: tranche.payout_per_unit,
+            claimable: 0,
+        })
+}
+
+/// Move a position's payouts since its last checkpoint into its claim
+fn settle_tranche_position(
+    tranche: &Tranche,
+    position: &mut TranchePosition,
+) -> Result<(), ContractError> {
+    let earned = position
+        .units
+        .checked_mul(tranche.payout_per_unit - position.payout_checkpoint)
+        .ok_or(ContractError::InvalidLoanData)?
+        / SCALE;
+    position.claimable += earned;
+    position.payout_checkpoint = tranche.payout_per_unit;
+    Ok(())
+}
+
+/// The issued securitization a loan's collections belong to, if any
+fn issued_securitization(env: &Env, loan_id: u64) -> Option<LoanSecuritization> {
+    let securitization_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&format_securitized_loan_key(loan_id))?;
+    read_securitization(env, securitization_id)
+        .ok()
+        .filter(|securitization| securitization.status != SecuritizationStatus::Pending)
+}
+
+/// Drop loans that stopped performing while a securitization took
+/// subscriptions and return the live balance of the rest
+fn reprice_securitization(
+    env: &Env,
+    securitization: &LoanSecuritization,
+) -> Result<i128, ContractError> {
+    let pool_key = format_loan_pool_key(securitization.portfolio_id);
+    let mut portfolio: LoanPortfolio = env
+        .storage()
+        .persistent()
+        .get(&pool_key)
+        .ok_or(ContractError::LoanNotFound)?;
+
+    let mut loan_ids = Vec::new(env);
+    let mut total_outstanding: i128 = 0;
+    for loan_id in portfolio.loan_ids.iter() {
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+        if loan.status == LoanStatus::Active || loan.status == LoanStatus::Restructured {
+            loan_ids.push_back(loan_id);
+            total_outstanding += loan.outstanding_balance;
+        } else {
+            env.storage()
+                .persistent()
+                .remove(&format_securitized_loan_key(loan_id));
+        }
+    }
+
+    portfolio.loan_ids = loan_ids;
+    portfolio.total_outstanding = total_outstanding;
+    portfolio.updated_at = env.ledger().timestamp();
+    env.storage().persistent().set(&pool_key, &portfolio);
+    Ok(total_outstanding)
+}
+
+/// Accrue a tranche's coupon on its outstanding balance
+fn accrue_tranche_coupon(tranche: &mut Tranche, now: u64) -> Result<(), ContractError> {
+    let (elapsed, year) =
+        accrual_seconds(DayCountConvention::Act365, tranche.interest_accrued_at, now);
+    tranche.interest_due += simple_interest(tranche.balance, tranche.coupon_rate, elapsed, year)
+        .ok_or(ContractError::InterestCalculationError)?;
+    tranche.interest_accrued_at = now;
+    Ok(())
+}
+
+/// Credit a payout to a tranche's holders in proportion to their units
+fn pay_tranche(tranche: &mut Tranche, amount: i128) -> Result<(), ContractError> {
+    if amount <= 0 {
+        return Ok(());
+    }
+    tranche.distributed += amount;
+    tranche.payout_per_unit += amount
+        .checked_mul(SCALE)
+        .ok_or(ContractError::InvalidLoanData)?
+        / tranche.size;
+    Ok(())
+}
+
+/// Take collections from `payer` into a securitization and pay them down the
+/// waterfall: senior coupon, senior principal, then the junior tranche
+fn collect_into_securitization(
+    env: &Env,
+    securitization: &LoanSecuritization,
+    payer: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    if amount > 0 {
+        token::Client::new(env, &securitization.asset).transfer(
+            payer,
+            &env.current_contract_address(),
+            &amount,
+        );
+    }
+
+    let now = env.ledger().timestamp();
+    let mut senior = read_tranche(env, securitization.id, TrancheClass::Senior)?;
+    let mut junior = read_tranche(env, securitization.id, TrancheClass::Junior)?;
+    accrue_tranche_coupon(&mut senior, now)?;
+
+    let mut remaining = cmp::max(amount, 0);
+    let senior_interest = cmp::min(remaining, senior.interest_due);
+    senior.interest_due -= senior_interest;
+    remaining -= senior_interest;
+
+    let senior_principal = cmp::min(remaining, senior.balance);
+    senior.balance -= senior_principal;
+    remaining -= senior_principal;
+    pay_tranche(&mut senior, senior_interest + senior_principal)?;
+
+    junior.balance -= cmp::min(remaining, junior.balance);
+    pay_tranche(&mut junior, remaining)?;
+
+    env.storage().persistent().set(
+        &format_tranche_key(securitization.id, TrancheClass::Senior),
+        &senior,
+    );
+    env.storage().persistent().set(
+        &format_tranche_key(securitization.id, TrancheClass::Junior),
+        &junior,
+    );
+
+    env.events().publish(
+        (symbol_short!("waterfall"),),
+        (
+            securitization.id,
+            senior_interest,
+            senior_principal,
+            remaining,
+        ),
+    );
+
+    refresh_securitization_status(env, securitization.id, false)
+}
+
+/// Write a defaulted loan's balance off the junior tranche first, then the
+/// senior tranche
+fn allocate_securitization_loss(
+    env: &Env,
+    securitization: LoanSecuritization,
+    loss: i128,
+) -> Result<(), ContractError> {
+    let now = env.ledger().timestamp();
+    let mut senior = read_tranche(env, securitization.id, TrancheClass::Senior)?;
+    let mut junior = read_tranche(env, securitization.id, TrancheClass::Junior)?;
+    accrue_tranche_coupon(&mut senior, now)?;
+
+    let junior_loss = cmp::min(loss, junior.balance);
+    junior.balance -= junior_loss;
+    junior.losses += junior_loss;
+
+    let senior_loss = cmp::min(loss - junior_loss, senior.balance);
+    senior.balance -= senior_loss;
+    senior.losses += senior_loss;
+
+    env.storage().persistent().set(
+        &format_tranche_key(securitization.id, TrancheClass::Senior),
+        &senior,
+    );
+    env.storage().persistent().set(
+        &format_tranche_key(securitization.id, TrancheClass::Junior),
+        &junior,
+    );
+
+    env.events().publish(
+        (symbol_short!("sec_loss"),),
+        (securitization.id, junior_loss, senior_loss),
+    );
+
+    refresh_securitization_status(env, securitization.id, senior_loss > 0)
+}
+
+/// Refresh a securitization's pool balance, marking it defaulted once the
+/// senior tranche takes a loss or completed once every pooled loan is repaid
+/// or closed
+fn refresh_securitization_status(
+    env: &Env,
+    securitization_id: u64,
+    senior_loss: bool,
+) -> Result<(), ContractError> {
+    let mut securitization = read_securitization(env, securitization_id)?;
+    let portfolio: LoanPortfolio = env
+        .storage()
+        .persistent()
+        .get(&format_loan_pool_key(securitization.portfolio_id))
+        .ok_or(ContractError::LoanNotFound)?;
+
+    let mut total_outstanding: i128 = 0;
+    let mut settled = true;
+    for loan_id in portfolio.loan_ids.iter() {
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&format_loan_storage_key(loan_id))
+            .ok_or(ContractError::LoanNotFound)?;
+        if loan.status != LoanStatus::Paid && loan.status != LoanStatus::Closed {
+            settled = false;
+        }
+        // Written-off balances no longer back the tranches
+        if loan.status != LoanStatus::Defaulted {
+            total_outstanding += loan.outstanding_balance;
+        }
+    }
+
+    let now = env.ledger().timestamp();
+    env.storage().persistent().set(
+        &format_loan_pool_key(portfolio.id),
+        &LoanPortfolio {
+            total_outstanding,
+            updated_at: now,
+            ..portfolio
+        },
+    );
+
+    if senior_loss {
+        securitization.status = SecuritizationStatus::Defaulted;
+    } else if settled && securitization.status == SecuritizationStatus::Active {
+        securitization.status = SecuritizationStatus::Completed;
+    }
+    securitization.updated_at = now;
+    env.storage().persistent().set(
+        &format_securitization_key(securitization_id),
+        &securitization,
+    );
+    Ok(())
+}
+
+/// Loan fee schedule, with the current default fee rate
+fn read_loan_fees(env: &Env) -> LoanFees {
+    let mut fees: LoanFees = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("fees"))
+        .unwrap_or(LoanFees {
+            origination_fee: 0,
+            servicing_fee: 0,
+            prepayment_penalty: 0,
+            late_payment_fee: 0,
+            default_fee: 0,
+            penalty_rate: 0,
+        });
+    fees.default_fee = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("def_fee"))
+        .unwrap_or(0);
+    fees
+}
+
+/// `amount * bps / 10000`
+fn apply_bps(amount: i128, bps: u32) -> Result<i128, ContractError> {
+    Ok(amount
+        .checked_mul(bps as i128)
+        .ok_or(ContractError::FeeCalculationError)?
+        / 10000)
+}
+
+/// Add the origination fee to a newly funded loan's first installment
+fn charge_origination_fee(env: &Env, loan: &mut Loan) -> Result<(), ContractError> {
+    let fee = apply_bps(loan.principal, read_loan_fees(env).origination_fee)?;
+    if fee <= 0 {
+        return Ok(());
+    }
+
+    let schedule_key = format_schedule_key(loan.id);
+    let mut schedule: RepaymentSchedule = env
+        .storage()
+        .persistent()
+        .get(&schedule_key)
+        .ok_or(ContractError::LoanNotFound)?;
+    if let Some(mut first) = schedule.installments.get(0) {
+        first.fees += fee;
+        first.amount += fee;
+        schedule.installments.set(0, first);
+    }
+    schedule.total_amount += fee;
+    env.storage().persistent().set(&schedule_key, &schedule);
+
+    loan.fees += fee;
+    env.events()
+        .publish((symbol_short!("fee_orig"),), (loan.id, fee));
+    Ok(())
+}
+
+/// Charge a late fee on each installment the first time it is seen overdue,
+/// and penalty interest on overdue amounts since the loan's last accrual
+///
+/// Charges are added to the installments they arise on.
+///
+/// # Returns
+/// The total charged
+fn accrue_late_charges(
+    env: &Env,
+    loan: &Loan,
+    schedule: &mut RepaymentSchedule,
+    now: u64,
+) -> Result<i128, ContractError> {
+    let fees = read_loan_fees(env);
+    let mut charged: i128 = 0;
+    let mut installments = Vec::new(env);
+    for mut installment in schedule.installments.iter() {
+        if !installment.paid && installment.due_date < now {
+            let unpaid = installment.amount - installment.paid_amount;
+            let mut charge = 0;
+            if installment.status != InstallmentStatus::Overdue {
+                installment.status = InstallmentStatus::Overdue;
+                charge += apply_bps(unpaid, fees.late_payment_fee)?;
+            }
+            let (elapsed, year) = accrual_seconds(
+                loan.day_count,
+                installment.due_date.max(loan.penalty_accrued_at),
+                now,
+            );
+            charge += simple_interest(unpaid, fees.penalty_rate, elapsed, year)
+                .ok_or(ContractError::InterestCalculationError)?;
+
+            installment.fees += charge;
+            installment.amount += charge;
+            charged += charge;
+        }
+        installments.push_back(installment);
+    }
+    schedule.installments = installments;
+    schedule.total_amount += charged;
+    Ok(charged)
+}
+
+/// Book late charges on a loan's overdue installments into its fee bucket
+fn apply_late_charges(env: &Env, loan: &mut Loan, now: u64) -> Result<(), ContractError> {
+    let schedule_key = format_schedule_key(loan.id);
+    let Some(mut schedule) = env
+        .storage()
+        .persistent()
+        .get::<_, RepaymentSchedule>(&schedule_key)
+    else {
+        return Ok(());
+    };
+
+    let charged = accrue_late_charges(env, loan, &mut schedule, now)?;
+    env.storage().persistent().set(&schedule_key, &schedule);
+    loan.penalty_accrued_at = now;
+
+    if charged > 0 {
+        loan.fees += charged;
+        env.events()
+            .publish((symbol_short!("late_fee"),), (loan.id, charged));
+    }
+    Ok(())
+}
+
+/// Pull a repayment from the payer, sending the protocol fee on the interest
+/// portion to the treasury, any insurance premium to the insurer, and the
+/// rest to the lender, syndicate or securitization. Returns the fee.
+fn route_repayment(
+    env: &Env,
+    loan: &Loan,
+    payer: &Address,
+    amount: i128,
+    interest_payment: i128,
+) -> Result<i128, ContractError> {
+    let lender = loan.lender.clone().ok_or(ContractError::LoanNotActive)?;
+    if let Some(pool) = lending_pool_of(env, loan) {
+        require_pool_asset(env, &pool, &loan.asset)?;
+    }
+    let (treasury, fee) = protocol_fee(env, interest_payment)?;
+    let premium = collect_insurance_premium(env, loan, payer, amount - fee)?;
+    let proceeds = amount - fee - premium;
+
+    if let Some(securitization) = issued_securitization(env, loan.id) {
+        collect_into_securitization(env, &securitization, payer, proceeds)?;
+    } else {
+        match read_syndication(env, loan.id) {
+            Some(syndication) => distribute_pro_rata(env, loan, &syndication, payer, proceeds),
+            None => token::Client::new(env, &loan.asset).transfer(payer, &lender, &proceeds),
+        }
+    }
+
+    deposit_protocol_fee(env, &treasury, &loan.asset, payer, fee, loan.id);
+
+    Ok(fee)
+}
+
+/// Activate a funded loan: the loan clock, rate and schedule start now
+fn start_loan(env: &Env, loan: &mut Loan, lender: &Address) -> Result<(), ContractError> {
+    let now = env.ledger().timestamp();
+    loan.lender = Some(lender.clone());
+    loan.status = LoanStatus::Active;
+    loan.start_date = now;
+    loan.maturity_date = now + loan.term;
+    loan.last_payment_date = now;
+    if loan.interest_type == InterestType::Floating {
+        start_floating_rate(env, loan, now)?;
+    }
+    loan.next_payment_date = store_schedule(env, loan, loan.principal, now, loan.maturity_date)?;
+    loan.penalty_accrued_at = now;
+    charge_origination_fee(env, loan)?;
+    loan.updated_at = now;
+    save_loan(env, loan);
+    Ok(())
+}
+
+/// The lending pool, when it is the loan's lender
+fn lending_pool_of(env: &Env, loan: &Loan) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get::<_, Address>(&symbol_short!("lend_pool"))
+        .filter(|pool| loan.lender.as_ref() == Some(pool))
+}
+
+/// The pool can only lend and take repayments in its own token
+fn require_pool_asset(env: &Env, pool: &Address, asset: &Address) -> Result<(), ContractError> {
+    let pool_asset =
+        env.invoke_contract::<Address>(pool, &Symbol::new(env, "get_asset"), Vec::new(env));
+    if pool_asset != *asset {
+        return Err(ContractError::InvalidCurrency);
+    }
+    Ok(())
+}
+
+/// Tell the lending pool how a loan's principal changed
+fn notify_lending_pool(env: &Env, pool: &Address, function: &str, loan_id: u64, principal: i128) {
+    let args: Vec<Val> = Vec::from_array(env, [loan_id.into_val(env), principal.into_val(env)]);
+    env.invoke_contract::<Val>(pool, &Symbol::new(env, function), args);
+}
+
+/// Record a borrower's repayment outcome with the reputation registry, if
+/// configured
+fn report_reputation(env: &Env, borrower: &Address, outcome: ReputationOutcome, volume: i128) {
+    let Some(reputation_registry) = env
+        .storage()
+        .instance()
+        .get::<_, Address>(&symbol_short!("rep_reg"))
+    else {
+        return;
+    };
+
+    let behavior = ReputationBehavior {
+        action_type: ReputationAction::Repayment,
+        outcome,
+        volume,
+        timestamp: env.ledger().timestamp(),
+    };
+    let args: Vec<Val> = Vec::from_array(
+        env,
+        [
+            env.current_contract_address().into_val(env),
+            borrower.into_val(env),
+            behavior.into_val(env),
+        ],
+    );
+    env.invoke_contract::<Val>(
+        &reputation_registry,
+        &Symbol::new(env, "record_behavior"),
+        args,
+    );
+}
+
+/// Pay the insurer its premium out of a repayment's proceeds
+fn collect_insurance_premium(
+    env: &Env,
+    loan: &Loan,
+    payer: &Address,
+    proceeds: i128,
+) -> Result<i128, ContractError> {
+    let Some(mut policy) = read_insurance(env, loan.id) else {
+        return Ok(0);
+    };
+    if policy.status != InsuranceStatus::Active {
+        return Ok(0);
+    }
+
+    let premium = apply_bps(proceeds, policy.premium_rate)?;
+    if premium > 0 {
+        token::Client::new(env, &loan.asset).transfer(payer, &policy.insurer, &premium);
+        policy.premiums_paid += premium;
+        policy.updated_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&format_insurance_key(loan.id), &policy);
+
+        env.events()
+            .publish((symbol_short!("ins_prem"),), (loan.id, policy.id, premium));
+    }
+
+    Ok(premium)
+}
+
+/// Load the credit protection policy on a loan
+fn read_insurance(env: &Env, loan_id: u64) -> Option<LoanInsurance> {
+    env.storage()
+        .persistent()
+        .get(&format_insurance_key(loan_id))
+}
+
+/// Lapse an unclaimed policy once its loan is settled
+fn expire_insurance(env: &Env, loan_id: u64) {
+    if let Some(mut policy) = read_insurance(env, loan_id) {
+        if policy.status == InsuranceStatus::Active {
+            policy.status = InsuranceStatus::Expired;
+            policy.updated_at = env.ledger().timestamp();
+            env.storage()
+                .persistent()
+                .set(&format_insurance_key(loan_id), &policy);
+        }
+    }
+}
+
+/// The treasury and its protocol fee on an interest payment
+fn protocol_fee(env: &Env, interest_payment: i128) -> Result<(Address, i128), ContractError> {
+    let treasury: Address = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("treasury"))
+        .ok_or(ContractError::Unauthorized)?;
+
+    let fee_bps: u32 = env.invoke_contract(
+        &treasury,
+        &Symbol::new(env, "get_fee_bps"),
+        Vec::<Val>::new(env),
+    );
+    let fee = interest_payment
+        .checked_mul(fee_bps as i128)
+        .ok_or(ContractError::FeeCalculationError)?
+        / 10000;
+
+    Ok((treasury, fee))
+}
+
+/// Send a protocol fee from the payer to the treasury
+fn deposit_protocol_fee(
+    env: &Env,
+    treasury: &Address,
+    asset: &Address,
+    payer: &Address,
+    fee: i128,
+    reference_id: u64,
+) {
+    if fee <= 0 {
+        return;
+    }
+
+    token::Client::new(env, asset).transfer(payer, treasury, &fee);
+
+    let deposit_args: Vec<Val> = Vec::from_array(env, [asset.into_val(env), fee.into_val(env)]);
+    env.invoke_contract::<()>(treasury, &Symbol::new(env, "deposit_fee"), deposit_args);
+
+    env.events().publish(
+        (symbol_short!("fee_col"),),
+        (reference_id, fee, asset.clone()),
+    );
+}
+
+/// Query the collateral registry for a collateral's valuation snapshot.
+fn fetch_collateral_valuation(
+    env: &Env,
+    collateral_id: u64,
+) -> Result<CollateralValuation, ContractError> {
+    let registry: Address = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("col_reg"))
+        .ok_or(ContractError::Unauthorized)?;
+
+    let args: Vec<Val> = Vec::from_array(env, [collateral_id.into_val(env)]);
+    match env.try_invoke_contract::<CollateralValuation, soroban_sdk::Error>(
+        &registry,
+        &Symbol::new(env, "get_collateral_valuation"),
+        args,
+    ) {
+        Ok(Ok(valuation)) => Ok(valuation),
+        _ => Err(ContractError::CollateralValueError),
+    }
+}
+
+/// Query the collateral registry for a collateral's asset type.
+fn fetch_collateral_type(env: &Env, collateral_id: u64) -> Result<CollateralType, ContractError> {
+    let registry: Address = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("col_reg"))
+        .ok_or(ContractError::Unauthorized)?;
+
+    let args: Vec<Val> = Vec::from_array(env, [collateral_id.into_val(env)]);
+    match env.try_invoke_contract::<CollateralType, soroban_sdk::Error>(
+        &registry,
+        &Symbol::new(env, "get_asset_type"),
+        args,
+    ) {
+        Ok(Ok(collateral_type)) => Ok(collateral_type),
+        _ => Err(ContractError::CollateralValueError),
+    }
+}
+
+/// Oldest collateral valuation accepted, in seconds
+fn max_valuation_age(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("max_vage"))
+        .unwrap_or(2592000)
+}
+
+/// Lendable value of verified collateral with a fresh valuation.
+fn current_collateral_value(env: &Env, collateral_id: u64) -> Result<i128, ContractError> {
+    let valuation = fetch_collateral_valuation(env, collateral_id)?;
+
+    if !valuation.verified {
+        return Err(ContractError::CollateralNotVerified);
+    }
+
+    if env.ledger().timestamp() > valuation.valuation_timestamp + max_valuation_age(env) {
+        return Err(ContractError::StaleValuation);
+    }
+
+    if valuation.lendable_value <= 0 {
+        return Err(ContractError::CollateralValueError);
+    }
+
+    Ok(valuation.lendable_value)
+}
+
+/// Measure a covenant's condition, returning the value and whether it holds
+fn evaluate_covenant(
+    env: &Env,
+    loan: &Loan,
+    covenant: &LoanCovenant,
+) -> Result<(i128, bool), ContractError> {
+    match covenant.covenant_type {
+        CovenantType::MinCollateralCoverage => {
+            if loan.outstanding_balance <= 0 {
+                return Ok((0, true));
+            }
+            let collateral_value = loan_collateral_value(env, loan)?;
+            let coverage = coverage_ratio(collateral_value, loan.outstanding_balance)?;
+            Ok((coverage, coverage >= covenant.threshold))
+        }
+        CovenantType::MaxDaysPastDue => {
+            let days_past_due = env
+                .ledger()
+                .timestamp()
+                .saturating_sub(loan.next_payment_date)
+                / 86400;
+            let days_past_due = days_past_due as i128;
+            Ok((days_past_due, days_past_due <= covenant.threshold))
+        }
+        CovenantType::MinReputationScore => {
+            let score = fetch_reputation_score(env, &loan.borrower)?;
+            Ok((score as i128, score as i128 >= covenant.threshold))
+        }
+        CovenantType::RequiredInsurance => {
+            let status = fetch_insurance_status(env, loan.collateral_id)?;
+            Ok((status as i128, status == CollateralInsuranceStatus::Insured))
+        }
+    }
+}
+
+/// Measure a credit line covenant against the drawn balance and security
+fn evaluate_line_covenant(
+    env: &Env,
+    line: &CreditLine,
+    covenant: &LoanCovenant,
+) -> Result<(i128, bool), ContractError> {
+    match (covenant.covenant_type, line.security) {
+        (CovenantType::MinCollateralCoverage, _) => {
+            if line.drawn <= 0 {
+                return Ok((0, true));
+            }
+            let coverage = coverage_ratio(credit_security_value(env, line.security)?, line.drawn)?;
+            Ok((coverage, coverage >= covenant.threshold))
+        }
+        (CovenantType::MinReputationScore, _) => {
+            let score = fetch_reputation_score(env, &line.borrower)?;
+            Ok((score as i128, score as i128 >= covenant.threshold))
+        }
+        (CovenantType::RequiredInsurance, CreditSecurity::Collateral(collateral_id)) => {
+            let status = fetch_insurance_status(env, collateral_id)?;
+            Ok((status as i128, status == CollateralInsuranceStatus::Insured))
+        }
+        _ => Err(ContractError::InvalidCovenant),
+    }
+}
+
+/// Collateral value as basis points of an outstanding amount
+fn coverage_ratio(collateral_value: i128, outstanding: i128) -> Result<i128, ContractError> {
+    Ok(collateral_value
+        .checked_mul(10000)
+        .ok_or(ContractError::CollateralValueError)?
+        / outstanding)
+}
+
+/// Query the reputation registry for a borrower's score.
+fn fetch_reputation_score(env: &Env, borrower: &Address) -> Result<u32, ContractError> {
+    let reputation_registry: Address = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("rep_reg"))
+        .ok_or(ContractError::Unauthorized)?;
+
+    Ok(env.invoke_contract(
+        &reputation_registry,
+        &Symbol::new(env, "calculate_reputation_score"),
+        Vec::from_array(env, [borrower.into_val(env)]),
+    ))
+}
+
+/// Query the collateral registry for a collateral's live insurance status.
+fn fetch_insurance_status(
+    env: &Env,
+    collateral_id: u64,
+) -> Result<CollateralInsuranceStatus, ContractError> {
+    let registry: Address = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("col_reg"))
+        .ok_or(ContractError::Unauthorized)?;
+
+    match env.try_invoke_contract::<CollateralInsuranceStatus, soroban_sdk::Error>(
+        &registry,
+        &Symbol::new(env, "get_insurance_status"),
+        Vec::from_array(env, [collateral_id.into_val(env)]),
+    ) {
+        Ok(Ok(status)) => Ok(status),
+        _ => Err(ContractError::CollateralValueError),
+    }
+}
+
+/// Apply a breach action, or reverse it when `breached` is false
+///
+/// Interest accrued at the old rate is capitalized before a rate change.
+fn apply_breach_action(
+    env: &Env,
+    loan: &mut Loan,
+    action: BreachAction,
+    breached: bool,
+    now: u64,
+) -> Result<(), ContractError> {
+    match action {
+        BreachAction::Notify => {}
+        BreachAction::RateIncrease(bps) => {
+            let accrued_interest = interest_for_window(
+                env,
+                loan,
+                loan.outstanding_balance,
+                loan.last_payment_date,
+                now,
+            )?;
+            loan.outstanding_balance += accrued_interest;
+            loan.last_payment_date = now;
+            loan.interest_rate = if breached {
+                loan.interest_rate.saturating_add(bps)
+            } else {
+                loan.interest_rate.saturating_sub(bps)
+            };
+            env.events()
+                .publish((symbol_short!("rate_step"),), (loan.id, loan.interest_rate));
+        }
+        BreachAction::Callable => loan.callable = breached,
+    }
+    Ok(())
+}
+
+/// Load a floating-rate loan's reset terms, falling back to an uncapped
+/// monthly reset when the borrower set none
+fn read_floating_terms(env: &Env, loan_id: u64) -> FloatingRateTerms {
+    env.storage()
+        .persistent()
+        .get(&format_floating_terms_key(loan_id))
+        .unwrap_or(FloatingRateTerms {
+            loan_id,
+            rate_floor: 0,
+            rate_cap: 100000,
+            reset_period: INSTALLMENT_INTERVAL,
+            next_reset: 0,
+        })
+}
+
+/// Load the rate resets recorded for a loan, oldest first
+fn read_rate_history(env: &Env, loan_id: u64) -> Vec<InterestRateAdjustment> {
+    env.storage()
+        .persistent()
+        .get(&format_rate_history_key(loan_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Price a floating-rate loan: the governed base rate plus the credit spread
+/// risk assessment quotes for the borrower, clamped to the loan's floor and cap
+fn floating_rate(env: &Env, loan: &Loan, terms: &FloatingRateTerms) -> Result<u32, ContractError> {
+    let base_rate: u32 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("base_rate"))
+        .unwrap_or(0);
+    let risk_assessment: Address = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("risk_mgr"))
+        .ok_or(ContractError::RiskAssessmentFailed)?;
+
+    let credit_score = match env.try_invoke_contract::<u32, soroban_sdk::Error>(
+        &risk_assessment,
+        &Symbol::new(env, "calculate_credit_score"),
+        Vec::from_array(env, [loan.borrower.into_val(env)]),
+    ) {
+        Ok(Ok(score)) => score,
+        _ => return Err(ContractError::RiskAssessmentFailed),
+    };
+    let spread = match env.try_invoke_contract::<u32, soroban_sdk::Error>(
+        &risk_assessment,
+        &Symbol::new(env, "calculate_interest_rate"),
+        Vec::from_array(
+            env,
+            [
+                credit_score.into_val(env),
+                loan.collateral_type.risk_class().into_val(env),
+            ],
+        ),
+    ) {
+        Ok(Ok(spread)) => spread,
+        _ => return Err(ContractError::RiskAssessmentFailed),
+    };
+
+    Ok(base_rate
+        .saturating_add(spread)
+        .clamp(terms.rate_floor, terms.rate_cap))
+}
+
+/// Reprice a floating-rate loan and record the reset in its rate history
+fn reset_rate(
+    env: &Env,
+    loan: &mut Loan,
+    terms: &FloatingRateTerms,
+    now: u64,
+    reason: &str,
+) -> Result<(), ContractError> {
+    let new_rate = floating_rate(env, loan, terms)?;
+
+    let mut history = read_rate_history(env, loan.id);
+    history.push_back(InterestRateAdjustment {
+        loan_id: loan.id,
+        old_rate: loan.interest_rate,
+        new_rate,
+        effective_date: now,
+        reason: String::from_str(env, reason),
+    });
+    env.storage()
+        .persistent()
+        .set(&format_rate_history_key(loan.id), &history);
+
+    env.events().publish(
+        (symbol_short!("rate_rst"),),
+        (loan.id, loan.interest_rate, new_rate),
+    );
+    loan.interest_rate = new_rate;
+    Ok(())
+}
+
+/// Set a floating-rate loan's first rate at funding and start its reset clock
+fn start_floating_rate(env: &Env, loan: &mut Loan, now: u64) -> Result<(), ContractError> {
+    let mut terms = read_floating_terms(env, loan.id);
+    reset_rate(env, loan, &terms, now, "initial floating rate")?;
+
+    terms.next_reset = now + terms.reset_period;
+    env.storage()
+        .persistent()
+        .set(&format_floating_terms_key(loan.id), &terms);
+    Ok(())
+}
+
+/// Check a new loan's principal, rate and term
+fn validate_loan_terms(
+    env: &Env,
+    principal: i128,
+    interest_rate: u32,
+    term: u64,
+) -> Result<(), ContractError> {
+    if principal <= 0 {
+        return Err(ContractError::InvalidLoanData);
+    }
+
+    if interest_rate > 100000 {
+        // Max 1000% annual rate
+        return Err(ContractError::InvalidInterestRate);
+    }
+
+    validate_term(env, term)
+}
+
+/// Create a pending loan against collateral worth `collateral_value` in the
+/// loan currency, locking the collateral
+fn originate_loan(
+    env: &Env,
+    borrower: Address,
+    collateral_id: u64,
+    asset: Address,
+    principal: i128,
+    interest_rate: u32,
+    term: u64,
+    interest_type: InterestType,
+    repayment_profile: RepaymentProfile,
+    collateral_value: i128,
+) -> Result<u64, ContractError> {
+    // Validate collateral LTV against its lendable value
+    let ltv = principal
+        .checked_mul(10000)
+        .ok_or(ContractError::InvalidLoanData)?
+        / collateral_value;
+
+    let min_ltv: u32 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("min_ltv"))
+        .unwrap_or(5000);
+    let max_ltv: u32 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("max_ltv"))
+        .unwrap_or(8000);
+
+    if ltv < min_ltv as i128 || ltv > max_ltv as i128 {
+        return Err(ContractError::InsufficientCollateral);
+    }
+    let ltv = ltv as u32;
+
+    // Get next loan ID
+    let loan_id: u64 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("nextloan"))
+        .unwrap_or(1u64);
+
+    // Create loan record
+    let mut loan = Loan {
+        id: loan_id,
+        borrower: borrower.clone(),
+        lender: None,
+        asset,
+        principal,
+        outstanding_balance: principal,
+        interest_rate,
+        interest_type,
+        compounding: Compounding::Daily,
+        day_count: DayCountConvention::Act365,
+        repayment_profile,
+        collateral_id,
+        collateral_type: fetch_collateral_type(env, collateral_id)?,
+        collateral_value,
+        ltv,
+        term,
+        start_date: env.ledger().timestamp(),
+        maturity_date: env.ledger().timestamp() + term,
+        status: LoanStatus::Pending,
+        total_interest_paid: 0,
+        total_repaid: 0,
+        last_payment_date: env.ledger().timestamp(),
+        next_payment_date: env.ledger().timestamp() + term,
+        grace_period: DEFAULT_GRACE_PERIOD,
+        default_date: 0,
+        default_fees: 0,
+        fees: 0,
+        total_fees_paid: 0,
+        penalty_accrued_at: 0,
+        recovered_amount: 0,
+        callable: false,
+        created_at: env.ledger().timestamp(),
+        updated_at: env.ledger().timestamp(),
+    };
+
+    // Generate the repayment schedule
+    loan.next_payment_date =
+        store_schedule(env, &loan, principal, loan.start_date, loan.maturity_date)?;
+
+    // Store loan
+    save_loan(env, &loan);
+
+    // Update next loan ID
+    env.storage()
+        .instance()
+        .set(&symbol_short!("nextloan"), &(loan_id + 1));
+
+    // Lock collateral for the life of the loan
+    lock_loan_collateral(env, collateral_id, loan_id);
+
+    // Emit event
+    env.events().publish(
+        (symbol_short!("loan_crea"),),
+        (loan_id, borrower, principal, interest_rate),
+    );
+
+    Ok(loan_id)
+}
+
+/// Convert a base-currency amount at an oracle FX rate
+fn convert_currency(amount: i128, rate: i128) -> Result<i128, ContractError> {
+    amount
+        .checked_mul(rate)
+        .map(|value| value / FX_RATE_SCALE)
+        .ok_or(ContractError::CollateralValueError)
+}
+
+/// Query the oracle adapter for the latest FX rate of a currency pair
+fn fetch_fx_rate(env: &Env, base: &String, quote: &String) -> Result<FxRate, ContractError> {
+    let oracle_adapter: Address = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("fx_orc"))
+        .ok_or(ContractError::InvalidCurrency)?;
+
+    let fx_rate = match env.try_invoke_contract::<FxRate, soroban_sdk::Error>(
+        &oracle_adapter,
+        &Symbol::new(env, "get_fx_rate"),
+        Vec::from_array(env, [base.into_val(env), quote.into_val(env)]),
+    ) {
+        Ok(Ok(fx_rate)) if fx_rate.rate > 0 => fx_rate,
+        _ => return Err(ContractError::InvalidCurrency),
+    };
+
+    let max_fx_age: u64 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("max_fxage"))
+        .unwrap_or(86400);
+    if env.ledger().timestamp() > fx_rate.timestamp + max_fx_age {
+        return Err(ContractError::StaleValuation);
+    }
+
+    Ok(fx_rate)
+}
+
+/// Lendable value of a loan's collateral in the loan currency
+fn loan_collateral_value(env: &Env, loan: &Loan) -> Result<i128, ContractError> {
+    let collateral_value = current_collateral_value(env, loan.collateral_id)?;
+    match env
+        .storage()
+        .persistent()
+        .get::<_, CrossCurrencyLoan>(&format_cross_currency_key(loan.id))
+    {
+        Some(cross_currency) => {
+            let fx_rate = fetch_fx_rate(
+                env,
+                &cross_currency.base_currency,
+                &cross_currency.loan_currency,
+            )?;
+            let converted = convert_currency(collateral_value, fx_rate.rate)?;
+            if converted <= 0 {
+                return Err(ContractError::CollateralValueError);
+            }
+            Ok(converted)
+        }
+        None => Ok(collateral_value),
+    }
+}
+
+/// Check a loan term against the configured bounds
+fn validate_term(env: &Env, term: u64) -> Result<(), ContractError> {
+    let min_term: u64 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("min_term"))
+        .unwrap_or(86400);
+    let max_term: u64 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("max_term"))
+        .unwrap_or(31536000);
+
+    if term < min_term || term > max_term {
+        return Err(ContractError::InvalidTerm);
+    }
+    Ok(())
+}
+
+fn validate_restructuring_terms(
+    env: &Env,
+    terms: &RestructuringTerms,
+) -> Result<(), ContractError> {
+    if let Some(new_rate) = terms.new_interest_rate {
+        if new_rate > 100000 {
+            return Err(ContractError::InvalidInterestRate);
+        }
+    }
+    if let Some(new_term) = terms.new_term {
+        validate_term(env, new_term)?;
+    }
+    if terms.new_principal.is_some_and(|principal| principal <= 0)
+        || terms.forgiveness_amount.is_some_and(|amount| amount < 0)
+    {
+        return Err(ContractError::InvalidRestructuring);
+    }
+    Ok(())
+}
+
+/// Load an open, unexpired proposal and require the counterparty's
+/// authorization (the party that did not propose it)
+fn open_proposal_for_counterparty(
+    env: &Env,
+    proposal_id: u64,
+) -> Result<(RestructuringProposal, Loan), ContractError> {
+    let proposal: RestructuringProposal = env
+        .storage()
+        .persistent()
+        .get(&format_proposal_key(proposal_id))
+        .ok_or(ContractError::ProposalNotFound)?;
+
+    if proposal.status != ProposalStatus::Proposed {
+        return Err(ContractError::ProposalNotFound);
+    }
+    if env.ledger().timestamp() > proposal.deadline {
+        return Err(ContractError::ProposalExpired);
+    }
+
+    let loan: Loan = env
+        .storage()
+        .persistent()
+        .get(&format_loan_storage_key(proposal.loan_id))
+        .ok_or(ContractError::LoanNotFound)?;
+
+    let counterparty = if proposal.proposed_by == loan.borrower {
+        loan.lender.clone().ok_or(ContractError::Unauthorized)?
+    } else {
+        loan.borrower.clone()
+    };
+    counterparty.require_auth();
+
+    Ok((proposal, loan))
+}
+
+fn read_restructuring_history(env: &Env, loan: &Loan) -> RestructuringHistory {
+    env.storage()
+        .persistent()
+        .get(&format_restructuring_history_key(loan.id))
+        .unwrap_or(RestructuringHistory {
+            loan_id: loan.id,
+            borrower: loan.borrower.clone(),
+            restructuring_count: 0,
+            total_forgiveness: 0,
+            last_restructure_date: 0,
+        })
+}
+
+/// Add a restructuring to the loan's history
+fn record_restructuring(env: &Env, loan: &Loan, forgiveness: i128) {
+    let mut history = read_restructuring_history(env, loan);
+    history.restructuring_count += 1;
+    history.total_forgiveness += forgiveness;
+    history.last_restructure_date = env.ledger().timestamp();
+    env.storage()
+        .persistent()
+        .set(&format_restructuring_history_key(loan.id), &history);
+}
+
+/// Require the funding lender's authorization
+///
+/// A lending pool can't sign, so its appointed servicer authorizes in its
+/// place; proceeds still go to the pool.
+fn require_lender(env: &Env, loan: &Loan) -> Result<Address, ContractError> {
+    let lender = loan.lender.clone().ok_or(ContractError::Unauthorized)?;
+    match lending_pool_of(env, loan) {
+        Some(pool) => env
+            .invoke_contract::<Option<Address>>(
+                &pool,
+                &Symbol::new(env, "get_servicer"),
+                Vec::new(env),
+            )
+            .ok_or(ContractError::Unauthorized)?
+            .require_auth(),
+        None => lender.require_auth(),
+    }
+    Ok(lender)
+}
+
+/// Allocate an ID for a forbearance, recovery plan or seizure record
+fn next_recovery_id(env: &Env) -> u64 {
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("next_rcv"))
+        .unwrap_or(1);
+    env.storage()
+        .instance()
+        .set(&symbol_short!("next_rcv"), &(id + 1));
+    id
+}
+
+/// Push every unpaid installment back by `delay`
+///
+/// Returns the due date of the next unpaid installment.
+fn defer_schedule(env: &Env, loan: &Loan, delay: u64) -> Result<u64, ContractError> {
+    let schedule_key = format_schedule_key(loan.id);
+    let mut schedule: RepaymentSchedule = env
+        .storage()
+        .persistent()
+        .get(&schedule_key)
+        .ok_or(ContractError::LoanNotFound)?;
+
+    let mut next_due = loan.maturity_date + delay;
+    let mut installments = Vec::new(env);
+    for mut installment in schedule.installments.iter() {
+        if !installment.paid {
+            installment.due_date += delay;
+            next_due = cmp::min(next_due, installment.due_date);
+        }
+        installments.push_back(installment);
+    }
+    schedule.installments = installments;
+    env.storage().persistent().set(&schedule_key, &schedule);
+
+    Ok(next_due)
+}
+
+fn read_syndication(env: &Env, loan_id: u64) -> Option<LoanSyndication> {
+    env.storage()
+        .persistent()
+        .get(&format_syndication_key(loan_id))
+}
+
+/// Load a syndicate that is still gathering commitments
+fn pending_syndication(env: &Env, loan_id: u64) -> Result<LoanSyndication, ContractError> {
+    let syndication = read_syndication(env, loan_id).ok_or(ContractError::SyndicationNotFound)?;
+    if syndication.status != SyndicationStatus::Pending {
+        return Err(ContractError::InvalidSyndication);
+    }
+    Ok(syndication)
+}
+
+fn set_syndication_status(env: &Env, loan_id: u64, status: SyndicationStatus) {
+    if let Some(mut syndication) = read_syndication(env, loan_id) {
+        syndication.status = status;
+        env.storage()
+            .persistent()
+            .set(&format_syndication_key(loan_id), &syndication);
+    }
+}
+
+/// Split `amount` from `from` across syndicate members by funded share
+///
+/// The last member absorbs rounding; a member paying itself is skipped.
+fn distribute_pro_rata(
+    env: &Env,
+    loan: &Loan,
+    syndication: &LoanSyndication,
+    from: &Address,
+    amount: i128,
+) {
+    let token_client = token::Client::new(env, &loan.asset);
+    let mut remaining = amount;
+    let count = syndication.participants.len();
+    for (i, participant) in syndication.participants.iter().enumerate() {
+        let share: SyndicateShare = match env
+            .storage()
+            .persistent()
+            .get(&format_syndicate_share_key(loan.id, &participant))
+        {
+            Some(share) => share,
+            None => continue,
+        };
+        let portion = if i as u32 + 1 == count {
+            remaining
+        } else {
+            amount * share.funded / syndication.total_amount
+        };
+        remaining -= portion;
+        if portion > 0 && participant != *from {
+            token_client.transfer(from, &participant, &portion);
+        }
+    }
+
+    env.events()
+        .publish((symbol_short!("synd_dist"),), (loan.id, amount));
+}
+
+/// Interest a loan accrues on `balance` over `[start, end)`
+fn interest_for_window(
+    env: &Env,
+    loan: &Loan,
+    balance: i128,
+    start: u64,
+    end: u64,
+) -> Result<i128, ContractError> {
+    // Accrual window under the loan's day-count convention
+    let (elapsed, year) = accrual_seconds(loan.day_count, start, end);
+
+    match loan.interest_type {
+        InterestType::Simple => simple_interest(balance, loan.interest_rate, elapsed, year),
+        InterestType::Compound => {
+            compound_interest(balance, loan.interest_rate, elapsed, year, loan.compounding)
+        }
+        // Fixed interest accrues on the original principal for the whole term
+        InterestType::Fixed => simple_interest(loan.principal, loan.interest_rate, elapsed, year),
+        InterestType::Floating => floating_interest(env, loan, balance, start, end),
+    }
+    .ok_or(ContractError::InterestCalculationError)
+}
+
+/// Simple interest on a floating-rate loan, split at every reset inside the
+/// window so each segment accrues at the rate in force at the time
+fn floating_interest(env: &Env, loan: &Loan, balance: i128, start: u64, end: u64) -> Option<i128> {
+    let mut interest: i128 = 0;
+    let mut segment_start = start;
+
+    for adjustment in read_rate_history(env, loan.id).iter() {
+        if adjustment.effective_date <= segment_start {
+            continue;
+        }
+        if adjustment.effective_date >= end {
+            break;
+        }
+        let (elapsed, year) =
+            accrual_seconds(loan.day_count, segment_start, adjustment.effective_date);
+        interest = interest.checked_add(simple_interest(
+            balance,
+            adjustment.old_rate,
+            elapsed,
+            year,
+        )?)?;
+        segment_start = adjustment.effective_date;
+    }
+
+    let (elapsed, year) = accrual_seconds(loan.day_count, segment_start, end);
+    interest.checked_add(simple_interest(balance, loan.interest_rate, elapsed, year)?)
+}
+
+/// Generate and store the schedule repaying `balance` between `start` and
+/// `maturity` under the loan's repayment profile
+///
+/// Returns the first installment due date.
+fn store_schedule(
+    env: &Env,
+    loan: &Loan,
+    balance: i128,
+    start: u64,
+    maturity: u64,
+) -> Result<u64, ContractError> {
+    let span = maturity.saturating_sub(start).max(1);
+    let count = match loan.repayment_profile {
+        RepaymentProfile::Bullet => 1,
+        _ => span.div_ceil(INSTALLMENT_INTERVAL),
+    };
+
+    let level_payment = if loan.repayment_profile == RepaymentProfile::EqualInstallments {
+        let (elapsed, year) = accrual_seconds(
+            loan.day_count,
+            start,
+            start + INSTALLMENT_INTERVAL.min(span),
+        );
+        annuity_payment(balance, loan.interest_rate, elapsed, year, count as u32)
+            .ok_or(ContractError::InterestCalculationError)?
+    } else {
+        0
+    };
+
+    let mut installments = Vec::new(env);
+    let mut remaining = balance;
+    let mut total_amount: i128 = 0;
+    let mut period_start = start;
+    for i in 1..=count {
+        let due_date = if i == count {
+            maturity.max(start + 1)
+        } else {
+            start + i * INSTALLMENT_INTERVAL
+        };
+        let interest = interest_for_window(env, loan, remaining, period_start, due_date)?;
+        let principal = if i == count {
+            remaining
+        } else if loan.repayment_profile == RepaymentProfile::EqualInstallments {
+            (level_payment - interest).clamp(0, remaining)
+        } else {
+            0
+        };
+
+        installments.push_back(Installment {
+            amount: interest + principal,
+            due_date,
+            paid: false,
+            paid_date: 0,
+            principal,
+            interest,
+            fees: 0,
+            paid_amount: 0,
+            status: InstallmentStatus::Upcoming,
+        });
+        total_amount += interest + principal;
+        remaining -= principal;
+        period_start = due_date;
+    }
+
+    let first_due = installments
+        .first()
+        .map(|installment| installment.due_date)
+        .unwrap_or(maturity);
+
+    let schedule = RepaymentSchedule {
+        loan_id: loan.id,
+        installments,
+        total_amount,
+        paid_amount: 0,
+        status: ScheduleStatus::Active,
+    };
+    env.storage()
+        .persistent()
+        .set(&format_schedule_key(loan.id), &schedule);
+
+    Ok(first_due)
+}
+
+/// Credit a repayment to unpaid installments in due order
+///
+/// Within an installment the payment covers fees, then interest, then
+/// principal. A loan that is paid off settles every remaining installment.
+///
+/// Returns the due date of the next unpaid installment (maturity if none).
+fn credit_schedule(env: &Env, loan: &Loan, amount: i128) -> Result<u64, ContractError> {
+    let schedule_key = format_schedule_key(loan.id);
+    let mut schedule: RepaymentSchedule = env
+        .storage()
+        .persistent()
+        .get(&schedule_key)
+        .ok_or(ContractError::LoanNotFound)?;
+
+    let now = env.ledger().timestamp();
+    let settle_all = loan.status == LoanStatus::Paid;
+    let mut available = amount;
+    let mut next_due = loan.maturity_date;
+    let mut installments = Vec::new(env);
+    for mut installment in schedule.installments.iter() {
+        if !installment.paid {
+            let credit = if settle_all {
+                installment.amount - installment.paid_amount
+            } else {
+                cmp::min(available, installment.amount - installment.paid_amount)
+            };
+            available -= cmp::min(available, credit);
+            installment.paid_amount += credit;
+            schedule.paid_amount += credit;
+
+            if installment.paid_amount >= installment.amount {
+                installment.paid = true;
+                installment.paid_date = now;
+                installment.status = InstallmentStatus::Paid;
+            } else if next_due == loan.maturity_date {
+                next_due = installment.due_date;
+            }
+        }
+        installments.push_back(installment);
+    }
+    schedule.installments = installments;
+
+    if settle_all {
+        schedule.status = ScheduleStatus::Completed;
+    }
+    env.storage().persistent().set(&schedule_key, &schedule);
+
+    Ok(next_due)
+}
+
+fn format_loan_storage_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("loan"), loan_id)
+}
+
+fn format_schedule_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("sched"), loan_id)
+}
+
+fn format_forbearance_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("forbear"), loan_id)
+}
+
+fn format_recovery_plan_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("rcv_plan"), loan_id)
+}
+
+fn format_seizure_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("seizure"), loan_id)
+}
+
+fn format_default_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("default"), loan_id)
+}
+
+fn format_default_fee_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("def_fee"), loan_id)
+}
+
+fn format_proposal_key(proposal_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("rst_prop"), proposal_id)
+}
+
+fn format_open_proposal_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("rst_open"), loan_id)
+}
+
+fn format_restructuring_history_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("rst_hist"), loan_id)
+}
+
+fn format_portfolio_key(scope: &PortfolioScope) -> (soroban_sdk::Symbol, PortfolioScope) {
+    (symbol_short!("portfolio"), scope.clone())
+}
+
+fn format_performance_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("perf"), loan_id)
+}
+
+fn format_liquidation_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("liquidate"), loan_id)
+}
+
+fn format_insurance_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("insure"), loan_id)
+}
+
+fn format_securitization_key(securitization_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("securit"), securitization_id)
+}
+
+fn format_securitized_loan_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("sec_loan"), loan_id)
+}
+
+fn format_loan_pool_key(portfolio_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("pool"), portfolio_id)
+}
+
+fn format_tranche_key(
+    securitization_id: u64,
+    class: TrancheClass,
+) -> (soroban_sdk::Symbol, u64, TrancheClass) {
+    (symbol_short!("tranche"), securitization_id, class)
+}
+
+fn format_tranche_position_key(
+    securitization_id: u64,
+    class: TrancheClass,
+    holder: &Address,
+) -> (soroban_sdk::Symbol, u64, TrancheClass, Address) {
+    (
+        symbol_short!("tr_pos"),
+        securitization_id,
+        class,
+        holder.clone(),
+    )
+}
+
+fn format_cross_currency_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("ccy"), loan_id)
+}
+
+fn format_margin_call_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("mcall"), loan_id)
+}
+
+fn format_floating_terms_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("float"), loan_id)
+}
+
+fn format_rate_history_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("rate_hist"), loan_id)
+}
+
+fn format_credit_line_key(line_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("line"), line_id)
+}
+
+fn format_line_covenants_key(line_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("line_cov"), line_id)
+}
+
+fn format_covenant_key(covenant_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("covenant"), covenant_id)
+}
+
+fn format_loan_covenants_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("loan_cov"), loan_id)
+}
+
+fn format_syndication_key(loan_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("synd"), loan_id)
+}
+
+fn format_syndicate_share_key(
+    loan_id: u64,
+    participant: &Address,
+) -> (soroban_sdk::Symbol, u64, Address) {
+    (symbol_short!("synd_shr"), loan_id, participant.clone())
+}
+
+fn format_repayment_key(loan_id: u64, timestamp: u64) -> (soroban_sdk::Symbol, u64, u64) {
+    (symbol_short!("repay"), loan_id, timestamp)
 }
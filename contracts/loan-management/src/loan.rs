@@ -1,5 +1,7 @@
 //! Loan data structures and types
 
+use crate::interest::{Compounding, DayCountConvention};
+use crate::repayment::RepaymentProfile;
 use soroban_sdk::{contracttype, Address, String};
 
 /// Loan status enumeration
@@ -12,6 +14,7 @@ pub enum LoanStatus {
     Restructured = 3,
     Closed = 4,
     Suspended = 5,
+    Pending = 6,
 }
 
 /// Interest calculation type
@@ -21,6 +24,7 @@ pub enum InterestType {
     Simple = 0,
     Compound = 1,
     Fixed = 2,
+    Floating = 3, // governed base rate plus credit spread, reset every period
 }
 
 /// Main loan structure
@@ -29,11 +33,17 @@ pub enum InterestType {
 pub struct Loan {
     pub id: u64,
     pub borrower: Address,
+    pub lender: Option<Address>, // set when the loan is funded
+    pub asset: Address,          // token the loan is disbursed and repaid in
     pub principal: i128,
     pub outstanding_balance: i128,
     pub interest_rate: u32, // basis points
     pub interest_type: InterestType,
+    pub compounding: Compounding, // applies to `InterestType::Compound`
+    pub day_count: DayCountConvention,
+    pub repayment_profile: RepaymentProfile,
     pub collateral_id: u64,
+    pub collateral_type: CollateralType,
     pub collateral_value: i128,
     pub ltv: u32,  // Loan-to-Value ratio (basis points)
     pub term: u64, // in seconds
@@ -46,10 +56,68 @@ pub struct Loan {
     pub next_payment_date: u64,
     pub grace_period: u64, // in seconds
     pub default_date: u64,
+    pub default_fees: i128, // unpaid default fees, collected before interest
+    pub fees: i128, // unpaid origination, late and penalty charges, collected with default fees
+    pub total_fees_paid: i128,
+    pub penalty_accrued_at: u64, // penalty interest on overdue installments accrued up to here
+    pub recovered_amount: i128,  // repaid or realized after default
+    pub callable: bool,          // a breached covenant lets the lender call the loan
     pub created_at: u64,
     pub updated_at: u64,
 }
 
+/// Collateral valuation snapshot (from CollateralRegistry)
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CollateralValuation {
+    pub collateral_id: u64,
+    pub valuation: i128,
+    pub lendable_value: i128,
+    pub valuation_timestamp: u64,
+    pub verified: bool,
+}
+
+/// Collateral asset type (from CollateralRegistry)
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CollateralType {
+    RealEstate = 0,
+    Equipment = 1,
+    Inventory = 2,
+    Receivables = 3,
+    Securities = 4,
+    Commodities = 5,
+    Vehicles = 6,
+    Intellectual = 7,
+    Other = 8,
+}
+
+impl CollateralType {
+    /// Closest collateral class used by risk assessment pricing
+    pub fn risk_class(self) -> RiskCollateralType {
+        match self {
+            CollateralType::RealEstate => RiskCollateralType::RealEstate,
+            CollateralType::Equipment => RiskCollateralType::Equipment,
+            CollateralType::Inventory => RiskCollateralType::Inventory,
+            CollateralType::Receivables => RiskCollateralType::Invoice,
+            CollateralType::Commodities => RiskCollateralType::Commodity,
+            _ => RiskCollateralType::Other,
+        }
+    }
+}
+
+/// Collateral type (from RiskAssessment)
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RiskCollateralType {
+    Invoice = 0,
+    Commodity = 1,
+    RealEstate = 2,
+    Inventory = 3,
+    Equipment = 4,
+    Other = 5,
+}
+
 /// Loan creation parameters
 #[contracttype]
 #[derive(Clone)]
@@ -60,6 +128,7 @@ pub struct LoanParams {
     pub interest_rate: u32,
     pub term: u64,
     pub interest_type: InterestType,
+    pub repayment_profile: RepaymentProfile,
 }
 
 /// Loan restructuring parameters
@@ -111,6 +180,17 @@ pub struct LoanSyndication {
     pub created_at: u64,
 }
 
+/// A participant's position in a syndicated loan
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SyndicateShare {
+    pub loan_id: u64,
+    pub participant: Address,
+    pub commitment: i128, // amount the arranger invited them to commit
+    pub funded: i128,     // principal drawn from them at funding (or bought)
+    pub accepted: bool,
+}
+
 /// Syndication status
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -122,29 +202,81 @@ pub enum SyndicationStatus {
 }
 
 /// Loan securitization
+///
+/// A pool of performing loans sold to investors through senior and junior
+/// tranches. The issuer keeps servicing the loans as lender of record while
+/// their collections are paid down the tranche waterfall. Until the pool is
+/// fully subscribed the issuer still owns the loans and their collections.
 #[contracttype]
 #[derive(Clone)]
 pub struct LoanSecuritization {
     pub id: u64,
     pub portfolio_id: u64,
     pub issuer: Address,
-    pub total_value: i128,
+    pub asset: Address,
+    pub total_value: i128, // pool balance, re-priced from live balances at activation
     pub tranche_count: u32,
     pub status: SecuritizationStatus,
+    pub subscription_deadline: u64, // anyone may cancel an unfilled pool after this
     pub created_at: u64,
+    pub updated_at: u64,
 }
 
 /// Securitization status
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum SecuritizationStatus {
-    Pending = 0,
+    Pending = 0, // taking subscriptions
     Active = 1,
     Completed = 2,
-    Defaulted = 3,
+    Defaulted = 3, // losses reached the senior tranche
+    Cancelled = 4, // never filled; subscribers reclaim their payments
+}
+
+/// Tranche seniority
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TrancheClass {
+    Senior = 0,
+    Junior = 1,
+}
+
+/// Tranche of a securitization, issued as one unit per unit of face value
+///
+/// Units are positions recorded by this contract rather than a token
+/// contract; they change hands only through `transfer_tranche`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Tranche {
+    pub securitization_id: u64,
+    pub class: TrancheClass,
+    pub size: i128, // face value and units issued
+    pub subscribed: i128,
+    pub balance: i128,      // face value after principal paydowns and losses
+    pub coupon_rate: u32,   // basis points; the junior tranche takes the residual
+    pub interest_due: i128, // unpaid coupon
+    pub interest_accrued_at: u64,
+    pub distributed: i128, // paid into holders' claims
+    pub losses: i128,
+    pub payout_per_unit: i128, // cumulative payouts per unit, scaled by `interest::SCALE`
+}
+
+/// An investor's holding of a tranche
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TranchePosition {
+    pub securitization_id: u64,
+    pub class: TrancheClass,
+    pub holder: Address,
+    pub units: i128,
+    pub payout_checkpoint: i128, // tranche payout per unit when last settled
+    pub claimable: i128,
 }
 
 /// Cross-currency loan
+///
+/// The collateral is valued in `base_currency` and converted into the loan
+/// asset's `loan_currency` at the oracle's FX rate.
 #[contracttype]
 #[derive(Clone)]
 pub struct CrossCurrencyLoan {
@@ -152,9 +284,48 @@ pub struct CrossCurrencyLoan {
     pub loan_id: u64,
     pub base_currency: String,
     pub loan_currency: String,
-    pub exchange_rate: i128,
+    pub exchange_rate: i128, // last observed, scaled by the oracle's FX rate scale
     pub hedge_status: HedgeStatus,
     pub created_at: u64,
+    pub updated_at: u64,
+}
+
+/// Grace period after a missed payment or maturity before default (5 days)
+pub const DEFAULT_GRACE_PERIOD: u64 = 86400 * 5;
+
+/// Fixed-point scale of oracle FX rates (7 decimals)
+pub const FX_RATE_SCALE: i128 = 10_000_000;
+
+/// FX rate for a currency pair (from OracleAdapter)
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FxRate {
+    pub base: String,
+    pub quote: String,
+    pub rate: i128,
+    pub oracle: Address,
+    pub timestamp: u64,
+}
+
+/// Request for the borrower to restore collateral coverage by a deadline
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MarginCall {
+    pub loan_id: u64,
+    pub ltv: u32,        // LTV when the call was issued (basis points)
+    pub shortfall: i128, // collateral value needed to return to the maximum LTV
+    pub issued_at: u64,
+    pub deadline: u64,
+    pub status: MarginCallStatus,
+}
+
+/// Margin call status
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MarginCallStatus {
+    Open = 0,
+    Met = 1,
+    Expired = 2, // the loan became callable
 }
 
 /// Hedge status
@@ -174,19 +345,77 @@ pub struct LoanCovenant {
     pub loan_id: u64,
     pub covenant_type: CovenantType,
     pub threshold: i128,
+    pub action: BreachAction,
     pub status: CovenantStatus,
+    pub last_value: i128, // measured value at the last check
+    pub breached_at: u64,
     pub created_at: u64,
+    pub updated_at: u64,
 }
 
 /// Covenant type
+///
+/// Thresholds: collateral coverage in basis points of the outstanding
+/// balance, days past due, reputation score; insurance takes no threshold.
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum CovenantType {
-    DebtToEquity = 0,
-    InterestCoverage = 1,
-    MinimumCash = 2,
-    MaximumLeverage = 3,
-    AssetCoverage = 4,
+    MinCollateralCoverage = 0,
+    MaxDaysPastDue = 1,
+    MinReputationScore = 2,
+    RequiredInsurance = 3,
+}
+
+/// Consequence of a covenant breach, reversed when the covenant is cured
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BreachAction {
+    Notify,
+    RateIncrease(u32), // basis points added to the interest rate
+    Callable,
+}
+
+/// Reputation behaviour record (from ReputationRegistry)
+#[contracttype]
+#[derive(Clone)]
+pub struct ReputationBehavior {
+    pub action_type: ReputationAction,
+    pub outcome: ReputationOutcome,
+    pub volume: i128,
+    pub timestamp: u64,
+}
+
+/// Reputation action type (from ReputationRegistry)
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReputationAction {
+    Trade = 1,
+    Repayment = 2,
+    Dispute = 3,
+}
+
+/// Reputation outcome (from ReputationRegistry)
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReputationOutcome {
+    Success = 1,
+    Failure = 2,
+    Early = 3,
+    OnTime = 4,
+    Late = 5,
+    Default = 6,
+    Won = 7,
+    Lost = 8,
+}
+
+/// Collateral insurance status (from CollateralRegistry)
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CollateralInsuranceStatus {
+    Uninsured = 0,
+    Insured = 1,
+    PartiallyInsured = 2,
+    Expired = 3,
 }
 
 /// Covenant status
@@ -200,13 +429,14 @@ pub enum CovenantStatus {
 
 /// Loan fee structure
 #[contracttype]
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct LoanFees {
-    pub origination_fee: u32,    // basis points
+    pub origination_fee: u32,    // basis points of principal, charged at funding
     pub servicing_fee: u32,      // basis points
-    pub prepayment_penalty: u32, // basis points
-    pub late_payment_fee: u32,   // basis points
+    pub prepayment_penalty: u32, // basis points of the balance settled before maturity
+    pub late_payment_fee: u32,   // basis points of an installment when it goes overdue
     pub default_fee: u32,        // basis points
+    pub penalty_rate: u32,       // annual basis points accrued on overdue installments
 }
 
 /// Loan pricing
@@ -220,17 +450,25 @@ pub struct LoanPricing {
     pub total_rate: u32,
 }
 
-/// Loan insurance
+/// Credit protection policy on a loan
+///
+/// The insurer covers `coverage_rate` of the loss on default, up to
+/// `coverage_amount`, in exchange for a premium taken from each repayment.
+/// Claims are paid from the insurer's token allowance to the contract.
 #[contracttype]
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct LoanInsurance {
     pub id: u64,
     pub loan_id: u64,
     pub insurer: Address,
-    pub coverage_amount: i128,
-    pub premium_rate: u32,
+    pub coverage_rate: u32,    // basis points of the loss covered
+    pub coverage_amount: i128, // maximum payout
+    pub premium_rate: u32,     // basis points of each repayment
+    pub premiums_paid: i128,
+    pub claimed_amount: i128,
     pub status: InsuranceStatus,
     pub created_at: u64,
+    pub updated_at: u64,
 }
 
 /// Insurance status
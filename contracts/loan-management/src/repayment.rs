@@ -26,6 +26,9 @@ pub struct RepaymentSchedule {
 }
 
 /// Individual installment
+///
+/// Payments credited to an installment cover its fees first, then interest,
+/// then principal.
 #[contracttype]
 #[derive(Clone)]
 pub struct Installment {
@@ -35,8 +38,36 @@ pub struct Installment {
     pub paid_date: u64,
     pub principal: i128,
     pub interest: i128,
+    pub fees: i128,
+    pub paid_amount: i128,
+    pub status: InstallmentStatus,
+}
+
+/// Installment marker, refreshed whenever the schedule is read
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InstallmentStatus {
+    Upcoming = 0,
+    Due = 1,
+    Overdue = 2,
+    Paid = 3,
 }
 
+/// How principal is spread over the installments
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RepaymentProfile {
+    /// Level payments of principal and interest
+    EqualInstallments = 0,
+    /// Interest each period, principal as a balloon with the last installment
+    InterestOnly = 1,
+    /// Principal and interest in a single payment at maturity
+    Bullet = 2,
+}
+
+/// Spacing between scheduled installments (30 days)
+pub const INSTALLMENT_INTERVAL: u64 = 86400 * 30;
+
 /// Schedule status
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
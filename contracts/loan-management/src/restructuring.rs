@@ -1,6 +1,7 @@
 //! Loan restructuring module
 
-use soroban_sdk::{contracttype, Address, String};
+use crate::repayment::RepaymentProfile;
+use soroban_sdk::{contracttype, Address};
 
 /// Restructuring proposal
 #[contracttype]
@@ -12,19 +13,31 @@ pub struct RestructuringProposal {
     pub proposal_date: u64,
     pub new_terms: RestructuringTerms,
     pub status: ProposalStatus,
+    pub deadline: u64, // counterparty must respond before this time
 }
 
 /// Restructuring terms
+///
+/// The new term runs from acceptance; accrued interest is capitalized before
+/// `new_principal` or `forgiveness_amount` apply.
 #[contracttype]
 #[derive(Clone)]
 pub struct RestructuringTerms {
     pub new_principal: Option<i128>,
     pub new_interest_rate: Option<u32>,
     pub new_term: Option<u64>,
-    pub new_payment_schedule: Option<String>,
+    pub new_payment_schedule: ScheduleChange,
     pub forgiveness_amount: Option<i128>,
 }
 
+/// Change to the repayment profile requested by a proposal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ScheduleChange {
+    Keep,
+    Switch(RepaymentProfile),
+}
+
 /// Proposal status
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -41,6 +54,7 @@ pub enum ProposalStatus {
 #[derive(Clone)]
 pub struct RestructuringHistory {
     pub loan_id: u64,
+    pub borrower: Address,
     pub restructuring_count: u32,
     pub total_forgiveness: i128,
     pub last_restructure_date: u64,
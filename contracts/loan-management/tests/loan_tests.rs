@@ -2,11 +2,514 @@
 
 #[cfg(test)]
 mod tests {
+    use loan_management::{
+        analytics::PortfolioScope,
+        credit_line::{BasketValuation, CreditLineStatus, CreditSecurity},
+        default_handling::{
+            ForbearanceStatus, LiquidationStatus, RecoveryStatus, RecoveryStrategy, SeizureStatus,
+        },
+        interest::{Compounding, DayCountConvention},
+        loan::{
+            BreachAction, CollateralInsuranceStatus, CollateralType, CollateralValuation,
+            CovenantStatus, CovenantType, FxRate, InsuranceStatus, InterestType, LoanFees,
+            LoanStatus, MarginCallStatus, ReputationBehavior, ReputationOutcome,
+            RiskCollateralType, SecuritizationStatus, SyndicationStatus, TrancheClass,
+        },
+        repayment::{InstallmentStatus, RepaymentProfile, ScheduleStatus},
+        restructuring::{ProposalStatus, RestructuringTerms, ScheduleChange},
+        LoanContract, LoanContractClient,
+    };
     use soroban_sdk::{
-        testutils::{Address as _, Ledger},
-        Address, Env,
+        contract, contractimpl, symbol_short,
+        testutils::{Address as _, Events, Ledger},
+        token, Address, Env, IntoVal, String, Vec,
     };
 
+    /// Collateral registry stand-in with preset lendable values and lock tracking
+    #[contract]
+    pub struct MockCollateralRegistry;
+
+    #[contractimpl]
+    impl MockCollateralRegistry {
+        pub fn set_valuation(
+            env: Env,
+            collateral_id: u64,
+            lendable_value: i128,
+            valuation_timestamp: u64,
+            verified: bool,
+        ) {
+            let valuation = CollateralValuation {
+                collateral_id,
+                valuation: lendable_value,
+                lendable_value,
+                valuation_timestamp,
+                verified,
+            };
+            env.storage()
+                .instance()
+                .set(&(symbol_short!("value"), collateral_id), &valuation);
+        }
+
+        pub fn set_lendable_value(env: Env, collateral_id: u64, value: i128) {
+            let now = env.ledger().timestamp();
+            Self::set_valuation(env, collateral_id, value, now, true);
+        }
+
+        pub fn get_collateral_valuation(env: Env, collateral_id: u64) -> CollateralValuation {
+            env.storage()
+                .instance()
+                .get(&(symbol_short!("value"), collateral_id))
+                .expect("collateral not found")
+        }
+
+        pub fn lock_collateral_for(
+            env: Env,
+            collateral_id: u64,
+            _escrow_id: u64,
+            _secured_party: Address,
+        ) {
+            env.storage()
+                .instance()
+                .set(&(symbol_short!("locked"), collateral_id), &true);
+        }
+
+        pub fn unlock_collateral(env: Env, collateral_id: u64) {
+            env.storage()
+                .instance()
+                .set(&(symbol_short!("locked"), collateral_id), &false);
+        }
+
+        pub fn is_locked(env: Env, collateral_id: u64) -> bool {
+            env.storage()
+                .instance()
+                .get(&(symbol_short!("locked"), collateral_id))
+                .unwrap_or(false)
+        }
+
+        pub fn seize_collateral(env: Env, collateral_id: u64, recipient: Address) {
+            Self::unlock_collateral(env.clone(), collateral_id);
+            env.storage()
+                .instance()
+                .set(&(symbol_short!("seized"), collateral_id), &recipient);
+        }
+
+        pub fn liquidate_collateral(
+            env: Env,
+            collateral_id: u64,
+            liquidator: Address,
+            share_bps: u32,
+        ) -> u32 {
+            let total_key = (symbol_short!("liq_shr"), collateral_id);
+            let liquidated: u32 = env.storage().instance().get(&total_key).unwrap_or(0);
+            let share = share_bps.min(10000 - liquidated);
+            let claim_key = (symbol_short!("liq_clm"), collateral_id, liquidator);
+            let claim: u32 = env.storage().instance().get(&claim_key).unwrap_or(0);
+            env.storage().instance().set(&claim_key, &(claim + share));
+            env.storage()
+                .instance()
+                .set(&total_key, &(liquidated + share));
+            if liquidated + share == 10000 {
+                Self::unlock_collateral(env, collateral_id);
+            }
+            liquidated + share
+        }
+
+        pub fn get_liquidated_share(env: Env, collateral_id: u64) -> u32 {
+            env.storage()
+                .instance()
+                .get(&(symbol_short!("liq_shr"), collateral_id))
+                .unwrap_or(0)
+        }
+
+        pub fn liquidation_claim(env: Env, collateral_id: u64, holder: Address) -> u32 {
+            env.storage()
+                .instance()
+                .get(&(symbol_short!("liq_clm"), collateral_id, holder))
+                .unwrap_or(0)
+        }
+
+        pub fn seized_by(env: Env, collateral_id: u64) -> Option<Address> {
+            env.storage()
+                .instance()
+                .get(&(symbol_short!("seized"), collateral_id))
+        }
+
+        pub fn release_from_receipt(env: Env, collateral_id: u64, quantity: u64) {
+            let qty_key = (symbol_short!("rcpt_qty"), collateral_id);
+            let held: u64 = env.storage().instance().get(&qty_key).unwrap_or(1_000);
+            let remaining = held - quantity;
+            let mut valuation = Self::get_collateral_valuation(env.clone(), collateral_id);
+            valuation.lendable_value = valuation.lendable_value * remaining as i128 / held as i128;
+            valuation.valuation = valuation.lendable_value;
+            env.storage()
+                .instance()
+                .set(&(symbol_short!("value"), collateral_id), &valuation);
+            env.storage().instance().set(&qty_key, &remaining);
+        }
+
+        pub fn set_basket_value(env: Env, basket_id: u64, adjusted_value: i128) {
+            let timestamp = env.ledger().timestamp();
+            env.storage().instance().set(
+                &(symbol_short!("bsk_val"), basket_id),
+                &(adjusted_value, true, timestamp),
+            );
+        }
+
+        pub fn set_basket_unverified(env: Env, basket_id: u64) {
+            let (adjusted_value, _, timestamp): (i128, bool, u64) = env
+                .storage()
+                .instance()
+                .get(&(symbol_short!("bsk_val"), basket_id))
+                .expect("basket not found");
+            env.storage().instance().set(
+                &(symbol_short!("bsk_val"), basket_id),
+                &(adjusted_value, false, timestamp),
+            );
+        }
+
+        pub fn get_basket_valuation(env: Env, basket_id: u64) -> BasketValuation {
+            let (adjusted_value, verified, oldest_valuation): (i128, bool, u64) = env
+                .storage()
+                .instance()
+                .get(&(symbol_short!("bsk_val"), basket_id))
+                .expect("basket not found");
+            BasketValuation {
+                basket_id,
+                gross_value: adjusted_value,
+                adjusted_value,
+                verified,
+                oldest_valuation,
+            }
+        }
+
+        pub fn lock_basket_for(env: Env, basket_id: u64, _escrow_id: u64, _secured_party: Address) {
+            env.storage()
+                .instance()
+                .set(&(symbol_short!("bsk_lock"), basket_id), &true);
+        }
+
+        pub fn unlock_basket(env: Env, basket_id: u64) {
+            env.storage()
+                .instance()
+                .set(&(symbol_short!("bsk_lock"), basket_id), &false);
+        }
+
+        pub fn seize_basket(env: Env, basket_id: u64, recipient: Address) {
+            Self::unlock_basket(env.clone(), basket_id);
+            env.storage()
+                .instance()
+                .set(&(symbol_short!("bsk_seiz"), basket_id), &recipient);
+        }
+
+        pub fn basket_seized_by(env: Env, basket_id: u64) -> Option<Address> {
+            env.storage()
+                .instance()
+                .get(&(symbol_short!("bsk_seiz"), basket_id))
+        }
+
+        pub fn is_basket_locked(env: Env, basket_id: u64) -> bool {
+            env.storage()
+                .instance()
+                .get(&(symbol_short!("bsk_lock"), basket_id))
+                .unwrap_or(false)
+        }
+
+        pub fn set_asset_type(env: Env, collateral_id: u64, asset_type: CollateralType) {
+            env.storage()
+                .instance()
+                .set(&(symbol_short!("type"), collateral_id), &asset_type);
+        }
+
+        pub fn get_asset_type(env: Env, collateral_id: u64) -> CollateralType {
+            env.storage()
+                .instance()
+                .get(&(symbol_short!("type"), collateral_id))
+                .unwrap_or(CollateralType::Other)
+        }
+
+        pub fn set_insurance_status(
+            env: Env,
+            collateral_id: u64,
+            status: CollateralInsuranceStatus,
+        ) {
+            env.storage()
+                .instance()
+                .set(&(symbol_short!("insured"), collateral_id), &status);
+        }
+
+        pub fn get_insurance_status(env: Env, collateral_id: u64) -> CollateralInsuranceStatus {
+            env.storage()
+                .instance()
+                .get(&(symbol_short!("insured"), collateral_id))
+                .unwrap_or(CollateralInsuranceStatus::Uninsured)
+        }
+    }
+
+    /// Reputation registry stand-in with preset scores
+    #[contract]
+    pub struct MockReputationRegistry;
+
+    #[contractimpl]
+    impl MockReputationRegistry {
+        pub fn set_score(env: Env, user_address: Address, score: u32) {
+            env.storage().instance().set(&user_address, &score);
+        }
+
+        pub fn calculate_reputation_score(env: Env, user_address: Address) -> u32 {
+            env.storage().instance().get(&user_address).unwrap_or(0)
+        }
+
+        pub fn record_behavior(
+            env: Env,
+            caller: Address,
+            user_address: Address,
+            behavior_data: ReputationBehavior,
+        ) {
+            caller.require_auth();
+            env.storage().instance().set(
+                &(
+                    symbol_short!("behavior"),
+                    user_address,
+                    behavior_data.outcome,
+                ),
+                &behavior_data.volume,
+            );
+        }
+
+        pub fn get_volume(
+            env: Env,
+            user_address: Address,
+            outcome: ReputationOutcome,
+        ) -> Option<i128> {
+            env.storage()
+                .instance()
+                .get(&(symbol_short!("behavior"), user_address, outcome))
+        }
+    }
+
+    /// Risk assessment stand-in counting reported defaults
+    #[contract]
+    pub struct MockRiskAssessment;
+
+    #[contractimpl]
+    impl MockRiskAssessment {
+        pub fn record_default(env: Env, borrower: Address, _loan_id: u64, exposure: i128) {
+            let defaults = Self::get_defaults(env.clone(), borrower.clone());
+            env.storage().instance().set(
+                &(symbol_short!("defaults"), borrower.clone()),
+                &(defaults + 1),
+            );
+            env.storage()
+                .instance()
+                .set(&(symbol_short!("exposure"), borrower), &exposure);
+        }
+
+        pub fn get_exposure(env: Env, borrower: Address) -> i128 {
+            env.storage()
+                .instance()
+                .get(&(symbol_short!("exposure"), borrower))
+                .unwrap_or(0)
+        }
+
+        pub fn get_defaults(env: Env, borrower: Address) -> u32 {
+            env.storage()
+                .instance()
+                .get(&(symbol_short!("defaults"), borrower))
+                .unwrap_or(0)
+        }
+
+        pub fn set_credit_score(env: Env, borrower: Address, score: u32) {
+            env.storage()
+                .instance()
+                .set(&(symbol_short!("score"), borrower), &score);
+        }
+
+        pub fn calculate_credit_score(env: Env, wallet_address: Address) -> u32 {
+            env.storage()
+                .instance()
+                .get(&(symbol_short!("score"), wallet_address))
+                .unwrap_or(500)
+        }
+
+        /// Spread of one basis point per point below a 1000 score
+        pub fn calculate_interest_rate(
+            _env: Env,
+            credit_score: u32,
+            _collateral_type: RiskCollateralType,
+        ) -> u32 {
+            1000u32.saturating_sub(credit_score)
+        }
+    }
+
+    /// Oracle adapter stand-in with settable FX rates
+    #[contract]
+    pub struct MockOracleAdapter;
+
+    #[contractimpl]
+    impl MockOracleAdapter {
+        pub fn set_fx_rate(env: Env, base: String, quote: String, rate: i128) {
+            let fx_rate = FxRate {
+                base: base.clone(),
+                quote: quote.clone(),
+                rate,
+                oracle: env.current_contract_address(),
+                timestamp: env.ledger().timestamp(),
+            };
+            env.storage()
+                .instance()
+                .set(&(symbol_short!("fx"), base, quote), &fx_rate);
+        }
+
+        pub fn get_fx_rate(env: Env, base: String, quote: String) -> FxRate {
+            env.storage()
+                .instance()
+                .get(&(symbol_short!("fx"), base, quote))
+                .expect("fx rate not found")
+        }
+    }
+
+    /// Lending pool stand-in lending at a fixed rate and tracking principal
+    #[contract]
+    pub struct MockLendingPool;
+
+    #[contractimpl]
+    impl MockLendingPool {
+        pub fn set_asset(env: Env, asset: Address) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("asset"), &asset);
+        }
+
+        pub fn set_servicer(env: Env, servicer: Address) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("servicer"), &servicer);
+        }
+
+        pub fn get_servicer(env: Env) -> Option<Address> {
+            env.storage().instance().get(&symbol_short!("servicer"))
+        }
+
+        pub fn get_asset(env: Env) -> Address {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("asset"))
+                .unwrap()
+        }
+
+        pub fn draw(env: Env, _loan_id: u64, borrower: Address, amount: i128) -> u32 {
+            let asset: Address = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("asset"))
+                .unwrap();
+            token::Client::new(&env, &asset).transfer(
+                &env.current_contract_address(),
+                &borrower,
+                &amount,
+            );
+            750
+        }
+
+        pub fn repay(env: Env, _loan_id: u64, principal: i128) {
+            let repaid = Self::get_repaid(env.clone());
+            env.storage()
+                .instance()
+                .set(&symbol_short!("repaid"), &(repaid + principal));
+        }
+
+        pub fn write_off(env: Env, _loan_id: u64, amount: i128) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("written"), &amount);
+        }
+
+        pub fn get_repaid(env: Env) -> i128 {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("repaid"))
+                .unwrap_or(0)
+        }
+
+        pub fn get_written_off(env: Env) -> i128 {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("written"))
+                .unwrap_or(0)
+        }
+    }
+
+    /// Protocol treasury stand-in with a configurable fee
+    #[contract]
+    pub struct MockTreasury;
+
+    #[contractimpl]
+    impl MockTreasury {
+        pub fn set_fee_bps(env: Env, fee_bps: u32) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("fee_bps"), &fee_bps);
+        }
+
+        pub fn get_fee_bps(env: Env) -> u32 {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("fee_bps"))
+                .unwrap_or(0)
+        }
+
+        pub fn deposit_fee(env: Env, _asset: Address, amount: i128) {
+            let total: i128 = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("fees"))
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("fees"), &(total + amount));
+        }
+
+        pub fn get_fees(env: Env) -> i128 {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("fees"))
+                .unwrap_or(0)
+        }
+    }
+
+    struct LoanSetup<'a> {
+        loans: LoanContractClient<'a>,
+        registry: MockCollateralRegistryClient<'a>,
+        treasury: MockTreasuryClient<'a>,
+        asset: Address,
+    }
+
+    fn setup_loans(env: &Env) -> LoanSetup<'_> {
+        env.mock_all_auths();
+
+        let registry_id = env.register(MockCollateralRegistry, ());
+        let treasury_id = env.register(MockTreasury, ());
+
+        let contract_id = env.register(LoanContract, ());
+        let loans = LoanContractClient::new(env, &contract_id);
+        loans.initialize(&Address::generate(env), &treasury_id, &registry_id);
+
+        let asset = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+
+        LoanSetup {
+            loans,
+            registry: MockCollateralRegistryClient::new(env, &registry_id),
+            treasury: MockTreasuryClient::new(env, &treasury_id),
+            asset,
+        }
+    }
+
+    fn mint(env: &Env, asset: &Address, to: &Address, amount: i128) {
+        token::StellarAssetClient::new(env, asset).mint(to, &amount);
+    }
+
     #[test]
     fn test_initialize_contract() {
         let env = Env::default();
@@ -253,17 +756,67 @@ mod tests {
         // assert_eq!(loan.status, LoanStatus::Paid);
     }
 
+    fn set_loan_fees(setup: &LoanSetup, late_payment_fee: u32, penalty_rate: u32) {
+        setup.loans.set_loan_fees(&LoanFees {
+            origination_fee: 100,
+            servicing_fee: 0,
+            prepayment_penalty: 200,
+            late_payment_fee,
+            default_fee: 200,
+            penalty_rate,
+        });
+    }
+
     #[test]
     fn test_calculate_early_repayment() {
         let env = Env::default();
         env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        set_loan_fees(&setup, 0, 0);
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+
+        // The 1% origination fee is booked at funding
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.fees, 1_200);
+        let schedule = setup.loans.get_repayment_schedule(&loan_id);
+        assert_eq!(schedule.installments.get(0).unwrap().fees, 1_200);
+
+        // Principal + 10 days' interest + origination fee + 2% prepayment fee
+        env.ledger().set_timestamp(1_000_000 + 86400 * 10);
+        let payoff = setup.loans.calculate_early_repayment(&loan_id);
+        assert_eq!(
+            payoff,
+            120_000 + 120_000 * 1000 * 10 / 10000 / 365 + 1_200 + 2_400
+        );
+
+        mint(&env, &setup.asset, &borrower, payoff);
+        setup.loans.make_repayment(&loan_id, &payoff, &borrower);
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.status, LoanStatus::Paid);
+        assert_eq!(loan.fees, 0);
+        assert_eq!(loan.total_fees_paid, 3_600);
+    }
 
-        // let loan_id = 1u64;
-        // let prepayment_penalty_rate = 200; // 2%
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #25)")]
+    fn test_payoff_without_prepayment_fee_rejected() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
 
-        // Calculate early repayment
-        // let result = calculate_early_repayment(&env, loan_id, prepayment_penalty_rate);
-        // assert!(result.is_ok());
+        set_loan_fees(&setup, 0, 0);
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+
+        // Exactly the balance owed before the 2% prepayment fee
+        env.ledger().set_timestamp(1_000_000 + 86400 * 10);
+        let payoff = setup.loans.calculate_early_repayment(&loan_id) - 2_400;
+        mint(&env, &setup.asset, &borrower, payoff);
+        setup.loans.make_repayment(&loan_id, &payoff, &borrower);
     }
 
     #[test]
@@ -282,10 +835,73 @@ mod tests {
     fn test_get_portfolio_analytics() {
         let env = Env::default();
         env.ledger().set_timestamp(1_000_000);
-
-        // Get portfolio analytics
-        // let result = get_portfolio_analytics(&env, None);
-        // assert!(result.is_ok());
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let other_borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        setup
+            .registry
+            .set_asset_type(&1, &CollateralType::Equipment);
+        setup
+            .registry
+            .set_asset_type(&2, &CollateralType::RealEstate);
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+
+        setup.registry.set_lendable_value(&2, &100_000);
+        let other_loan_id = setup.loans.create_loan(
+            &other_borrower,
+            &2,
+            &setup.asset,
+            &60_000,
+            &500,
+            &(86400 * 365),
+            &InterestType::Simple,
+            &RepaymentProfile::EqualInstallments,
+        );
+        let pending = setup.loans.get_portfolio_aggregate(&PortfolioScope::All);
+        assert_eq!(pending.pending_loans, 1);
+        assert_eq!(pending.total_outstanding, 120_000);
+
+        mint(&env, &setup.asset, &lender, 60_000);
+        setup.loans.fund_loan(&other_loan_id, &lender);
+
+        // Rate weighted by outstanding balance, LTV over pledged collateral
+        let analytics = setup.loans.get_portfolio_analytics(&None);
+        assert_eq!(analytics.total_loans, 2);
+        assert_eq!(analytics.total_principal, 180_000);
+        assert_eq!(analytics.total_outstanding, 180_000);
+        assert_eq!(analytics.active_loans, 2);
+        assert_eq!(analytics.average_interest_rate, 833);
+        assert_eq!(analytics.portfolio_ltv, 6000);
+
+        setup.loans.make_repayment(&loan_id, &20_000, &borrower);
+
+        let analytics = setup.loans.get_portfolio_analytics(&Some(borrower.clone()));
+        assert_eq!(analytics.total_loans, 1);
+        assert_eq!(analytics.total_principal, 120_000);
+        assert_eq!(analytics.total_outstanding, 100_000);
+
+        let analytics = setup
+            .loans
+            .get_collateral_type_analytics(&CollateralType::RealEstate);
+        assert_eq!(analytics.total_loans, 1);
+        assert_eq!(analytics.total_outstanding, 60_000);
+        assert_eq!(analytics.average_interest_rate, 500);
+
+        let performance = setup.loans.get_performance_metrics(&loan_id);
+        assert_eq!(performance.on_time_payments, 1);
+        assert_eq!(performance.payment_history_score, 10000);
+
+        // Paying off moves the loan out of the outstanding totals
+        setup
+            .loans
+            .make_repayment(&other_loan_id, &60_000, &other_borrower);
+        let analytics = setup.loans.get_portfolio_analytics(&None);
+        assert_eq!(analytics.total_outstanding, 100_000);
+        assert_eq!(analytics.active_loans, 1);
+        assert_eq!(analytics.paid_loans, 1);
+        assert_eq!(analytics.average_interest_rate, 1000);
     }
 
     #[test]
@@ -353,27 +969,2725 @@ mod tests {
         // assert!(result.unwrap());
     }
 
+    /// Fund a 100,000 USDC loan against collateral valued at 20,000,000 KES,
+    /// worth 155,000 USDC at 0.00775
+    fn create_cross_currency_loan(
+        env: &Env,
+        setup: &LoanSetup,
+        borrower: &Address,
+        lender: &Address,
+    ) -> (u64, MockOracleAdapterClient<'static>) {
+        let oracle_id = env.register(MockOracleAdapter, ());
+        let oracle = MockOracleAdapterClient::new(env, &oracle_id);
+        setup.loans.set_oracle_adapter(&oracle_id);
+        oracle.set_fx_rate(
+            &String::from_str(env, "KES"),
+            &String::from_str(env, "USDC"),
+            &77_500,
+        );
+
+        setup.registry.set_lendable_value(&1, &20_000_000);
+        let loan_id = setup.loans.create_cross_currency_loan(
+            borrower,
+            &1,
+            &setup.asset,
+            &100_000,
+            &1000,
+            &(86400 * 365),
+            &InterestType::Simple,
+            &RepaymentProfile::Bullet,
+            &String::from_str(env, "KES"),
+            &String::from_str(env, "USDC"),
+        );
+
+        mint(env, &setup.asset, lender, 100_000);
+        setup.loans.fund_loan(&loan_id, lender);
+        (loan_id, oracle)
+    }
+
+    fn set_kes_rate(env: &Env, oracle: &MockOracleAdapterClient, rate: i128) {
+        oracle.set_fx_rate(
+            &String::from_str(env, "KES"),
+            &String::from_str(env, "USDC"),
+            &rate,
+        );
+    }
+
     #[test]
     fn test_cross_currency_loan() {
         let env = Env::default();
         env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let (loan_id, oracle) = create_cross_currency_loan(&env, &setup, &borrower, &lender);
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.collateral_value, 155_000);
+        assert_eq!(loan.ltv, 6451);
+        let cross_currency = setup.loans.get_cross_currency_loan(&loan_id).unwrap();
+        assert_eq!(cross_currency.exchange_rate, 77_500);
+
+        // The shilling weakens to 0.006, still inside the 85% threshold
+        set_kes_rate(&env, &oracle, 60_000);
+        assert_eq!(setup.loans.check_fx_coverage(&loan_id), 8333);
+        assert!(setup.loans.get_margin_call(&loan_id).is_none());
+
+        // At 0.0055 the collateral covers 110,000: 15,000 short of 80% LTV
+        set_kes_rate(&env, &oracle, 55_000);
+        assert_eq!(setup.loans.check_fx_coverage(&loan_id), 9090);
+        assert_eq!(setup.loans.evaluate_ltv(&loan_id), 9090);
+        let call = setup.loans.get_margin_call(&loan_id).unwrap();
+        assert_eq!(call.status, MarginCallStatus::Open);
+        assert_eq!(call.shortfall, 15_000);
+        assert_eq!(call.deadline, 1_000_000 + 86400 * 3);
+
+        // Topping up the collateral meets the call
+        env.ledger().set_timestamp(1_000_000 + 86400);
+        setup.registry.set_lendable_value(&1, &24_000_000);
+        assert_eq!(setup.loans.check_fx_coverage(&loan_id), 7575);
+        let call = setup.loans.get_margin_call(&loan_id).unwrap();
+        assert_eq!(call.status, MarginCallStatus::Met);
+        assert!(!setup.loans.get_loan_status(&loan_id).callable);
+    }
+
+    #[test]
+    fn test_unmet_fx_margin_call_makes_loan_callable() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let (loan_id, oracle) = create_cross_currency_loan(&env, &setup, &borrower, &lender);
+        set_kes_rate(&env, &oracle, 55_000);
+        setup.loans.check_fx_coverage(&loan_id);
+
+        // Still open before the deadline
+        env.ledger().set_timestamp(1_000_000 + 86400 * 3);
+        set_kes_rate(&env, &oracle, 55_000);
+        setup.loans.check_fx_coverage(&loan_id);
+        assert_eq!(
+            setup.loans.get_margin_call(&loan_id).unwrap().status,
+            MarginCallStatus::Open
+        );
+
+        env.ledger().set_timestamp(1_000_000 + 86400 * 3 + 1);
+        setup.loans.check_fx_coverage(&loan_id);
+        assert_eq!(
+            setup.loans.get_margin_call(&loan_id).unwrap().status,
+            MarginCallStatus::Expired
+        );
+        assert!(setup.loans.get_loan_status(&loan_id).callable);
+
+        setup.loans.call_loan(&loan_id);
+        assert_eq!(
+            setup.loans.get_loan_status(&loan_id).maturity_date,
+            1_000_000 + 86400 * 3 + 1
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #29)")]
+    fn test_fx_coverage_rejects_stale_rate() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let (loan_id, _oracle) = create_cross_currency_loan(&env, &setup, &borrower, &lender);
+        setup.loans.set_max_fx_age(&3600);
+
+        env.ledger().set_timestamp(1_000_000 + 3601);
+        setup.loans.check_fx_coverage(&loan_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #29)")]
+    fn test_cross_currency_origination_rejects_stale_rate() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
 
-        // Test cross-currency loan creation and management
+        let oracle_id = env.register(MockOracleAdapter, ());
+        let oracle = MockOracleAdapterClient::new(&env, &oracle_id);
+        setup.loans.set_oracle_adapter(&oracle_id);
+        set_kes_rate(&env, &oracle, 77_500);
+
+        env.ledger().set_timestamp(1_000_000 + 86400 + 1);
+        setup.registry.set_lendable_value(&1, &20_000_000);
+        setup.loans.create_cross_currency_loan(
+            &borrower,
+            &1,
+            &setup.asset,
+            &100_000,
+            &1000,
+            &(86400 * 365),
+            &InterestType::Simple,
+            &RepaymentProfile::Bullet,
+            &String::from_str(&env, "KES"),
+            &String::from_str(&env, "USDC"),
+        );
     }
 
     #[test]
     fn test_loan_syndication() {
         let env = Env::default();
         env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let arranger = Address::generate(&env);
+        let participant = Address::generate(&env);
+
+        let loan_id = create_syndicated_loan(&env, &setup, &borrower, &arranger, &participant);
+
+        // 180,000 committed for 120,000 principal draws two thirds of each
+        let token = token::Client::new(&env, &setup.asset);
+        assert_eq!(token.balance(&borrower), 120_000);
+        assert_eq!(token.balance(&arranger), 20_000);
+        assert_eq!(token.balance(&participant), 40_000);
+        assert_eq!(
+            setup
+                .loans
+                .get_syndicate_share(&loan_id, &participant)
+                .funded,
+            80_000
+        );
+
+        let syndication = setup.loans.get_syndication(&loan_id);
+        assert_eq!(syndication.status, SyndicationStatus::Active);
+        assert_eq!(syndication.total_amount, 120_000);
+
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.status, LoanStatus::Active);
+        assert_eq!(loan.lender, Some(arranger));
+    }
 
-        // Test loan syndication
+    /// Pool the lender's loans into a securitization and fill both tranches
+    fn create_securitization(
+        env: &Env,
+        setup: &LoanSetup,
+        lender: &Address,
+        loan_ids: &[u64],
+        senior: (&Address, i128),
+        junior: (&Address, i128),
+    ) -> u64 {
+        let securitization_id = setup.loans.create_securitization(
+            lender,
+            &Vec::from_slice(env, loan_ids),
+            &senior.1,
+            &500,
+        );
+        for (class, (investor, amount)) in [
+            (TrancheClass::Senior, senior),
+            (TrancheClass::Junior, junior),
+        ] {
+            mint(env, &setup.asset, investor, amount);
+            setup
+                .loans
+                .subscribe_tranche(&securitization_id, &class, investor, &amount);
+        }
+        securitization_id
     }
 
     #[test]
     fn test_loan_securitization() {
         let env = Env::default();
         env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let senior = Address::generate(&env);
+        let junior = Address::generate(&env);
+        let buyer = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        let securitization_id = create_securitization(
+            &env,
+            &setup,
+            &lender,
+            &[loan_id],
+            (&senior, 90_000),
+            (&junior, 30_000),
+        );
+
+        // Full subscription pays the issuer for the pool
+        let token = token::Client::new(&env, &setup.asset);
+        assert_eq!(token.balance(&lender), 120_000);
+        assert_eq!(
+            setup.loans.get_securitization(&securitization_id).status,
+            SecuritizationStatus::Active
+        );
+
+        // 30,000 collected: 2,250 senior coupon, then 27,750 senior principal
+        env.ledger().set_timestamp(1_000_000 + 86400 * 365 / 2);
+        setup.loans.make_repayment(&loan_id, &30_000, &borrower);
+        assert_eq!(token.balance(&lender), 120_000);
+
+        let tranche = setup
+            .loans
+            .get_tranche(&securitization_id, &TrancheClass::Senior);
+        assert_eq!(tranche.balance, 62_250);
+        assert_eq!(tranche.distributed, 30_000);
+        assert_eq!(
+            setup
+                .loans
+                .get_tranche(&securitization_id, &TrancheClass::Junior)
+                .distributed,
+            0
+        );
+
+        // Half the junior tranche changes hands before the payoff
+        setup.loans.transfer_tranche(
+            &securitization_id,
+            &TrancheClass::Junior,
+            &junior,
+            &buyer,
+            &15_000,
+        );
+
+        // Payoff retires the senior tranche and leaves the excess to junior
+        let payoff = setup.loans.calculate_early_repayment(&loan_id);
+        mint(&env, &setup.asset, &borrower, payoff - 90_000);
+        setup.loans.make_repayment(&loan_id, &payoff, &borrower);
+        assert_eq!(
+            setup.loans.get_securitization(&securitization_id).status,
+            SecuritizationStatus::Completed
+        );
+        assert_eq!(
+            setup
+                .loans
+                .get_tranche_position(&securitization_id, &TrancheClass::Senior, &senior)
+                .claimable,
+            92_249
+        );
+
+        // Holders pull their payouts; per-unit rounding leaves dust behind
+        assert_eq!(
+            setup
+                .loans
+                .claim_tranche(&securitization_id, &TrancheClass::Senior, &senior),
+            92_249
+        );
+        assert_eq!(
+            setup
+                .loans
+                .claim_tranche(&securitization_id, &TrancheClass::Junior, &junior),
+            (payoff - 62_250) / 2
+        );
+        assert_eq!(
+            setup
+                .loans
+                .claim_tranche(&securitization_id, &TrancheClass::Junior, &buyer),
+            (payoff - 62_250) / 2
+        );
+        assert_eq!(token.balance(&senior), 92_249);
+        assert!(token.balance(&setup.loans.address) <= 2);
+
+        // Nothing left to claim
+        assert_eq!(
+            setup
+                .loans
+                .claim_tranche(&securitization_id, &TrancheClass::Senior, &senior),
+            0
+        );
+    }
+
+    #[test]
+    fn test_securitization_losses_hit_junior_first() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let lender = Address::generate(&env);
+        let senior = Address::generate(&env);
+        let junior = Address::generate(&env);
+
+        let first = create_funded_loan(&env, &setup, &Address::generate(&env), &lender);
+        let second = create_funded_loan(&env, &setup, &Address::generate(&env), &lender);
+        let securitization_id = create_securitization(
+            &env,
+            &setup,
+            &lender,
+            &[first, second],
+            (&senior, 100_000),
+            (&junior, 140_000),
+        );
+
+        // The 120,000 write-off is absorbed by the junior tranche alone
+        env.ledger().set_timestamp(1_000_000 + 86400 * 36);
+        assert!(setup.loans.check_default(&first));
+
+        let junior_tranche = setup
+            .loans
+            .get_tranche(&securitization_id, &TrancheClass::Junior);
+        assert_eq!(junior_tranche.losses, 120_000);
+        assert_eq!(junior_tranche.balance, 20_000);
+
+        let senior_tranche = setup
+            .loans
+            .get_tranche(&securitization_id, &TrancheClass::Senior);
+        assert_eq!(senior_tranche.losses, 0);
+        assert_eq!(senior_tranche.balance, 100_000);
+        assert!(senior_tranche.interest_due > 0);
+
+        let securitization = setup.loans.get_securitization(&securitization_id);
+        assert_eq!(securitization.status, SecuritizationStatus::Active);
+        assert_eq!(
+            setup
+                .loans
+                .get_loan_portfolio(&securitization.portfolio_id)
+                .unwrap()
+                .total_outstanding,
+            120_000
+        );
+    }
+
+    #[test]
+    fn test_securitization_reprices_at_activation() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let senior = Address::generate(&env);
+        let junior = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        let securitization_id = setup.loans.create_securitization(
+            &lender,
+            &Vec::from_array(&env, [loan_id]),
+            &90_000,
+            &500,
+        );
+        mint(&env, &setup.asset, &senior, 90_000);
+        setup
+            .loans
+            .subscribe_tranche(&securitization_id, &TrancheClass::Senior, &senior, &90_000);
+
+        // The issuer still owns the loan's collections while subscriptions are open
+        env.ledger().set_timestamp(1_000_000 + 86400 * 10);
+        mint(&env, &setup.asset, &borrower, 20_000);
+        setup.loans.make_repayment(&loan_id, &20_000, &borrower);
+        let token = token::Client::new(&env, &setup.asset);
+        assert_eq!(token.balance(&lender), 20_000);
+        let outstanding = setup.loans.get_loan_status(&loan_id).outstanding_balance;
+
+        mint(&env, &setup.asset, &junior, 30_000);
+        setup
+            .loans
+            .subscribe_tranche(&securitization_id, &TrancheClass::Junior, &junior, &30_000);
+
+        // The issuer is paid the live balance and junior gets the difference back
+        let securitization = setup.loans.get_securitization(&securitization_id);
+        assert_eq!(securitization.status, SecuritizationStatus::Active);
+        assert_eq!(securitization.total_value, outstanding);
+        assert_eq!(token.balance(&lender), 20_000 + outstanding);
+
+        let shortfall = 120_000 - outstanding;
+        let junior_tranche = setup
+            .loans
+            .get_tranche(&securitization_id, &TrancheClass::Junior);
+        assert_eq!(junior_tranche.balance, 30_000 - shortfall);
+        let refund = setup
+            .loans
+            .claim_tranche(&securitization_id, &TrancheClass::Junior, &junior);
+        assert!(refund <= shortfall && refund >= shortfall - 1);
+    }
+
+    #[test]
+    fn test_cancelled_securitization_refunds_subscribers() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let senior = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        let securitization_id = setup.loans.create_securitization(
+            &lender,
+            &Vec::from_array(&env, [loan_id]),
+            &90_000,
+            &500,
+        );
+        mint(&env, &setup.asset, &senior, 90_000);
+        setup
+            .loans
+            .subscribe_tranche(&securitization_id, &TrancheClass::Senior, &senior, &90_000);
+
+        setup
+            .loans
+            .cancel_securitization(&securitization_id, &lender);
+        assert_eq!(
+            setup.loans.get_securitization(&securitization_id).status,
+            SecuritizationStatus::Cancelled
+        );
+
+        assert_eq!(
+            setup
+                .loans
+                .claim_tranche(&securitization_id, &TrancheClass::Senior, &senior),
+            90_000
+        );
+        let token = token::Client::new(&env, &setup.asset);
+        assert_eq!(token.balance(&senior), 90_000);
+
+        // The loan is free to be pooled again
+        setup.loans.create_securitization(
+            &lender,
+            &Vec::from_array(&env, [loan_id]),
+            &90_000,
+            &500,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #1)")]
+    fn test_only_issuer_cancels_before_subscription_deadline() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let investor = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        let securitization_id = setup.loans.create_securitization(
+            &lender,
+            &Vec::from_array(&env, [loan_id]),
+            &90_000,
+            &500,
+        );
+
+        // Anyone may cancel once the deadline has passed
+        env.ledger().set_timestamp(1_000_000 + 86400 * 30 + 1);
+        let other_id = setup.loans.create_securitization(
+            &lender,
+            &Vec::from_array(&env, [create_funded_loan(&env, &setup, &borrower, &lender)]),
+            &90_000,
+            &500,
+        );
+        setup
+            .loans
+            .cancel_securitization(&securitization_id, &investor);
+        assert_eq!(
+            setup.loans.get_securitization(&securitization_id).status,
+            SecuritizationStatus::Cancelled
+        );
+
+        setup.loans.cancel_securitization(&other_id, &investor);
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #3)")]
+    fn test_tranche_subscription_capped_at_size() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let investor = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        let securitization_id = setup.loans.create_securitization(
+            &lender,
+            &Vec::from_array(&env, [loan_id]),
+            &90_000,
+            &500,
+        );
+
+        mint(&env, &setup.asset, &investor, 40_000);
+        setup.loans.subscribe_tranche(
+            &securitization_id,
+            &TrancheClass::Junior,
+            &investor,
+            &40_000,
+        );
+    }
+
+    #[test]
+    fn test_create_loan_uses_lendable_value() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+
+        setup.registry.set_lendable_value(&1, &200_000);
+        let loan_id = setup.loans.create_loan(
+            &borrower,
+            &1,
+            &setup.asset,
+            &140_000,
+            &500,
+            &(86400 * 90),
+            &InterestType::Simple,
+            &RepaymentProfile::EqualInstallments,
+        );
+
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.collateral_value, 200_000);
+        assert_eq!(loan.ltv, 7000);
+        assert_eq!(loan.status, LoanStatus::Pending);
+        assert!(setup.registry.is_locked(&1));
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #6)")]
+    fn test_create_loan_rejects_ltv_above_lendable_value() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+
+        // Lendable value already reflects the haircut, so 90% of it exceeds max LTV
+        setup.registry.set_lendable_value(&1, &100_000);
+        setup.loans.create_loan(
+            &borrower,
+            &1,
+            &setup.asset,
+            &90_000,
+            &500,
+            &(86400 * 90),
+            &InterestType::Simple,
+            &RepaymentProfile::EqualInstallments,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #20)")]
+    fn test_create_loan_rejects_unvalued_collateral() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+
+        setup.loans.create_loan(
+            &borrower,
+            &99,
+            &setup.asset,
+            &10_000,
+            &500,
+            &(86400 * 90),
+            &InterestType::Simple,
+            &RepaymentProfile::EqualInstallments,
+        );
+    }
+
+    fn create_funded_loan(
+        env: &Env,
+        setup: &LoanSetup,
+        borrower: &Address,
+        lender: &Address,
+    ) -> u64 {
+        setup.registry.set_lendable_value(&1, &200_000);
+        let loan_id = setup.loans.create_loan(
+            borrower,
+            &1,
+            &setup.asset,
+            &120_000,
+            &1000,
+            &(86400 * 365),
+            &InterestType::Simple,
+            &RepaymentProfile::EqualInstallments,
+        );
+
+        mint(env, &setup.asset, lender, 120_000);
+        setup.loans.fund_loan(&loan_id, lender);
+        loan_id
+    }
+
+    #[test]
+    fn test_fund_loan_disburses_principal() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+
+        let token = token::Client::new(&env, &setup.asset);
+        assert_eq!(token.balance(&borrower), 120_000);
+        assert_eq!(token.balance(&lender), 0);
+
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.status, LoanStatus::Active);
+        assert_eq!(loan.lender, Some(lender));
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #26)")]
+    fn test_fund_loan_twice_rejected() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        setup.loans.fund_loan(&loan_id, &lender);
+    }
+
+    #[test]
+    fn test_repayment_routes_to_lender_and_treasury() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+        setup.treasury.set_fee_bps(&1000); // 10% of interest
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+
+        // Half a year at 10% simple interest on 120,000 = 6,000 interest
+        env.ledger().set_timestamp(1_000_000 + 86400 * 365 / 2);
+        mint(&env, &setup.asset, &borrower, 10_000);
+        setup.loans.make_repayment(&loan_id, &26_000, &borrower);
+
+        let token = token::Client::new(&env, &setup.asset);
+        assert_eq!(token.balance(&lender), 25_400);
+        assert_eq!(token.balance(&setup.treasury.address), 600);
+        assert_eq!(setup.treasury.get_fees(), 600);
+        assert_eq!(token.balance(&borrower), 104_000);
+
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.outstanding_balance, 100_000);
+        assert!(setup.registry.is_locked(&1));
+    }
+
+    #[test]
+    fn test_full_repayment_releases_collateral() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        setup.loans.make_repayment(&loan_id, &120_000, &borrower);
+
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.status, LoanStatus::Paid);
+        assert!(!setup.registry.is_locked(&1));
+        assert_eq!(
+            token::Client::new(&env, &setup.asset).balance(&lender),
+            120_000
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #27)")]
+    fn test_repayment_on_unfunded_loan_rejected() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+
+        setup.registry.set_lendable_value(&1, &200_000);
+        let loan_id = setup.loans.create_loan(
+            &borrower,
+            &1,
+            &setup.asset,
+            &120_000,
+            &1000,
+            &(86400 * 365),
+            &InterestType::Simple,
+            &RepaymentProfile::EqualInstallments,
+        );
+        setup.loans.make_repayment(&loan_id, &1_000, &borrower);
+    }
+
+    #[test]
+    fn test_cancel_pending_loan_releases_collateral() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+
+        setup.registry.set_lendable_value(&1, &200_000);
+        let loan_id = setup.loans.create_loan(
+            &borrower,
+            &1,
+            &setup.asset,
+            &120_000,
+            &1000,
+            &(86400 * 365),
+            &InterestType::Simple,
+            &RepaymentProfile::EqualInstallments,
+        );
+        setup.loans.cancel_loan(&loan_id);
+
+        assert_eq!(
+            setup.loans.get_loan_status(&loan_id).status,
+            LoanStatus::Closed
+        );
+        assert!(!setup.registry.is_locked(&1));
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #28)")]
+    fn test_create_loan_rejects_unverified_collateral() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+
+        setup
+            .registry
+            .set_valuation(&1, &200_000, &1_000_000, &false);
+        setup.loans.create_loan(
+            &borrower,
+            &1,
+            &setup.asset,
+            &120_000,
+            &500,
+            &(86400 * 90),
+            &InterestType::Simple,
+            &RepaymentProfile::EqualInstallments,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #29)")]
+    fn test_create_loan_rejects_stale_valuation() {
+        let env = Env::default();
+        env.ledger().set_timestamp(10_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+
+        // Valued 31 days ago, past the 30-day default
+        setup
+            .registry
+            .set_valuation(&1, &200_000, &(10_000_000 - 86400 * 31), &true);
+        setup.loans.create_loan(
+            &borrower,
+            &1,
+            &setup.asset,
+            &120_000,
+            &500,
+            &(86400 * 90),
+            &InterestType::Simple,
+            &RepaymentProfile::EqualInstallments,
+        );
+    }
+
+    #[test]
+    fn test_evaluate_ltv_raises_margin_call_when_value_drops() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        assert_eq!(setup.loans.evaluate_ltv(&loan_id), 6000);
+
+        // Collateral revalued down: 120,000 / 130,000 = 92.3% > 85% margin call LTV
+        setup.registry.set_lendable_value(&1, &130_000);
+        let ltv = setup.loans.evaluate_ltv(&loan_id);
+        assert_eq!(ltv, 9230);
+        let (_, topics, _) = env.events().all().last().unwrap();
+        assert_eq!(topics, (symbol_short!("margin"),).into_val(&env));
+
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.ltv, 9230);
+        assert_eq!(loan.collateral_value, 130_000);
+    }
+
+    #[test]
+    fn test_release_receipt_collateral_within_max_ltv() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+
+        // Release 20% of the receipt: 120,000 / 160,000 = 75% <= 80% max LTV
+        let ltv = setup.loans.release_receipt_collateral(&loan_id, &200);
+        assert_eq!(ltv, 7500);
+
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.collateral_value, 160_000);
+        assert_eq!(loan.ltv, 7500);
+        assert!(setup.registry.is_locked(&1));
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #6)")]
+    fn test_release_receipt_collateral_rejects_ltv_breach() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+
+        // 120,000 / 140,000 = 85.7% > 80% max LTV
+        setup.loans.release_receipt_collateral(&loan_id, &300);
+    }
+
+    fn create_pending_loan(setup: &LoanSetup, interest_type: InterestType) -> u64 {
+        setup.registry.set_lendable_value(&1, &200_000);
+        setup.loans.create_loan(
+            &Address::generate(&setup.loans.env),
+            &1,
+            &setup.asset,
+            &120_000,
+            &1000,
+            &(86400 * 365),
+            &interest_type,
+            &RepaymentProfile::EqualInstallments,
+        )
+    }
+
+    #[test]
+    fn test_compounding_frequencies_over_one_year() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let year = 86400 * 365;
+
+        let simple_id = create_pending_loan(&setup, InterestType::Simple);
+        assert_eq!(setup.loans.calculate_interest(&simple_id, &year), 12_000);
+
+        // Daily is the default: 120,000 * ((1 + 0.1/365)^365 - 1)
+        let daily_id = create_pending_loan(&setup, InterestType::Compound);
+        assert_eq!(setup.loans.calculate_interest(&daily_id, &year), 12_618);
+
+        // 120,000 * ((1 + 0.1/12)^12 - 1)
+        let monthly_id = create_pending_loan(&setup, InterestType::Compound);
+        setup.loans.set_interest_conventions(
+            &monthly_id,
+            &Compounding::Monthly,
+            &DayCountConvention::Act365,
+        );
+        assert_eq!(setup.loans.calculate_interest(&monthly_id, &year), 12_565);
+
+        // 120,000 * (e^0.1 - 1)
+        let continuous_id = create_pending_loan(&setup, InterestType::Compound);
+        setup.loans.set_interest_conventions(
+            &continuous_id,
+            &Compounding::Continuous,
+            &DayCountConvention::Act365,
+        );
+        assert_eq!(
+            setup.loans.calculate_interest(&continuous_id, &year),
+            12_620
+        );
+    }
+
+    #[test]
+    fn test_day_count_conventions() {
+        let env = Env::default();
+        // 1970-01-12; 31 actual days later is 1970-02-12
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let period = 86400 * 31;
+
+        let act365_id = create_pending_loan(&setup, InterestType::Simple);
+        assert_eq!(setup.loans.calculate_interest(&act365_id, &period), 1_019);
+
+        let act360_id = create_pending_loan(&setup, InterestType::Simple);
+        setup.loans.set_interest_conventions(
+            &act360_id,
+            &Compounding::Daily,
+            &DayCountConvention::Act360,
+        );
+        assert_eq!(setup.loans.calculate_interest(&act360_id, &period), 1_033);
+
+        // 30/360 counts Jan 12 -> Feb 12 as 30 days
+        let thirty_id = create_pending_loan(&setup, InterestType::Simple);
+        setup.loans.set_interest_conventions(
+            &thirty_id,
+            &Compounding::Daily,
+            &DayCountConvention::Thirty360,
+        );
+        assert_eq!(setup.loans.calculate_interest(&thirty_id, &period), 1_000);
+
+        let loan = setup.loans.get_loan_status(&thirty_id);
+        assert_eq!(loan.day_count, DayCountConvention::Thirty360);
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #26)")]
+    fn test_set_interest_conventions_after_funding_rejected() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        setup.loans.set_interest_conventions(
+            &loan_id,
+            &Compounding::Continuous,
+            &DayCountConvention::Act360,
+        );
+    }
+
+    fn create_profile_loan(
+        setup: &LoanSetup,
+        borrower: &Address,
+        profile: RepaymentProfile,
+    ) -> u64 {
+        setup.registry.set_lendable_value(&1, &200_000);
+        setup.loans.create_loan(
+            borrower,
+            &1,
+            &setup.asset,
+            &120_000,
+            &1000,
+            &(86400 * 365),
+            &InterestType::Simple,
+            &profile,
+        )
+    }
+
+    #[test]
+    fn test_equal_installment_schedule() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let loan_id = create_profile_loan(
+            &setup,
+            &Address::generate(&env),
+            RepaymentProfile::EqualInstallments,
+        );
+
+        let schedule = setup.loans.get_repayment_schedule(&loan_id);
+        assert_eq!(schedule.installments.len(), 13);
+
+        // 30 days at 10% on 120,000
+        let first = schedule.installments.get(0).unwrap();
+        assert_eq!(first.interest, 986);
+        assert_eq!(first.due_date, 1_000_000 + 86400 * 30);
+        assert_eq!(first.status, InstallmentStatus::Due);
+
+        // Level payments, with principal amortizing and interest falling
+        let second = schedule.installments.get(1).unwrap();
+        assert_eq!(first.amount, second.amount);
+        assert!(second.interest < first.interest);
+        assert!(second.principal > first.principal);
+
+        let principal: i128 = schedule.installments.iter().map(|i| i.principal).sum();
+        assert_eq!(principal, 120_000);
+        let last = schedule.installments.get(12).unwrap();
+        assert_eq!(last.due_date, 1_000_000 + 86400 * 365);
+
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.next_payment_date, first.due_date);
+    }
+
+    #[test]
+    fn test_interest_only_schedule_has_balloon() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let loan_id = create_profile_loan(
+            &setup,
+            &Address::generate(&env),
+            RepaymentProfile::InterestOnly,
+        );
+
+        let schedule = setup.loans.get_repayment_schedule(&loan_id);
+        assert_eq!(schedule.installments.len(), 13);
+        for installment in schedule.installments.iter().take(12) {
+            assert_eq!(installment.principal, 0);
+            assert_eq!(installment.interest, 986);
+        }
+        let balloon = schedule.installments.get(12).unwrap();
+        assert_eq!(balloon.principal, 120_000);
+        // Final 5-day stub period
+        assert_eq!(balloon.interest, 164);
+    }
+
+    #[test]
+    fn test_bullet_schedule_single_payment_at_maturity() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let loan_id =
+            create_profile_loan(&setup, &Address::generate(&env), RepaymentProfile::Bullet);
+
+        let schedule = setup.loans.get_repayment_schedule(&loan_id);
+        assert_eq!(schedule.installments.len(), 1);
+        assert_eq!(schedule.total_amount, 132_000);
+
+        let installment = schedule.installments.get(0).unwrap();
+        assert_eq!(installment.due_date, 1_000_000 + 86400 * 365);
+        assert_eq!(installment.principal, 120_000);
+        assert_eq!(installment.interest, 12_000);
+
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.next_payment_date, installment.due_date);
+    }
+
+    #[test]
+    fn test_repayment_marks_installments_paid_due_and_overdue() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        let installment = setup
+            .loans
+            .get_repayment_schedule(&loan_id)
+            .installments
+            .get(0)
+            .unwrap();
+
+        // Pay the first installment on its due date
+        env.ledger().set_timestamp(1_000_000 + 86400 * 30);
+        setup
+            .loans
+            .make_repayment(&loan_id, &installment.amount, &borrower);
+
+        let schedule = setup.loans.get_repayment_schedule(&loan_id);
+        assert_eq!(schedule.paid_amount, installment.amount);
+        let first = schedule.installments.get(0).unwrap();
+        assert!(first.paid);
+        assert_eq!(first.status, InstallmentStatus::Paid);
+        assert_eq!(
+            schedule.installments.get(1).unwrap().status,
+            InstallmentStatus::Due
+        );
+        assert_eq!(
+            schedule.installments.get(2).unwrap().status,
+            InstallmentStatus::Upcoming
+        );
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.next_payment_date, 1_000_000 + 86400 * 60);
+
+        // Second installment missed
+        env.ledger().set_timestamp(1_000_000 + 86400 * 61);
+        let schedule = setup.loans.get_repayment_schedule(&loan_id);
+        assert_eq!(
+            schedule.installments.get(1).unwrap().status,
+            InstallmentStatus::Overdue
+        );
+        assert_eq!(
+            schedule.installments.get(2).unwrap().status,
+            InstallmentStatus::Due
+        );
+    }
+
+    #[test]
+    fn test_partial_payment_stays_on_current_installment() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        setup.loans.make_repayment(&loan_id, &1_000, &borrower);
+
+        let schedule = setup.loans.get_repayment_schedule(&loan_id);
+        let first = schedule.installments.get(0).unwrap();
+        assert!(!first.paid);
+        assert_eq!(first.paid_amount, 1_000);
+        assert_eq!(first.status, InstallmentStatus::Due);
+
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.next_payment_date, first.due_date);
+    }
+
+    #[test]
+    fn test_full_repayment_completes_schedule() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        setup.loans.make_repayment(&loan_id, &120_000, &borrower);
+
+        let schedule = setup.loans.get_repayment_schedule(&loan_id);
+        assert_eq!(schedule.status, ScheduleStatus::Completed);
+        assert!(schedule.installments.iter().all(|i| i.paid));
+    }
+
+    /// Fund a loan and let it default on its first installment
+    fn create_defaulted_loan(
+        env: &Env,
+        setup: &LoanSetup,
+        borrower: &Address,
+        lender: &Address,
+    ) -> u64 {
+        let loan_id = create_funded_loan(env, setup, borrower, lender);
+        env.ledger().set_timestamp(1_000_000 + 86400 * 36);
+        assert!(setup.loans.check_default(&loan_id));
+        loan_id
+    }
+
+    #[test]
+    fn test_default_charges_fee() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_defaulted_loan(&env, &setup, &borrower, &lender);
+
+        // 2% of the 120,000 outstanding
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.status, LoanStatus::Defaulted);
+        assert_eq!(loan.default_fees, 2_400);
+        assert_eq!(
+            setup.loans.get_default_fee(&loan_id).unwrap().fee_amount,
+            2_400
+        );
+
+        let default = setup.loans.get_default_event(&loan_id).unwrap();
+        assert_eq!(default.outstanding_balance, 120_000);
+        assert_eq!(default.accrued_interest, 1_183);
+    }
+
+    #[test]
+    fn test_forbearance_defers_schedule_and_blocks_default() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        setup
+            .loans
+            .grant_forbearance(&loan_id, &(86400 * 60), &10000);
+
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.next_payment_date, 1_000_000 + 86400 * 90);
+        assert_eq!(loan.maturity_date, 1_000_000 + 86400 * 425);
+        let schedule = setup.loans.get_repayment_schedule(&loan_id);
+        assert_eq!(
+            schedule.installments.get(0).unwrap().due_date,
+            1_000_000 + 86400 * 90
+        );
+
+        // Past the original first due date, still no default
+        env.ledger().set_timestamp(1_000_000 + 86400 * 40);
+        assert!(!setup.loans.check_default(&loan_id));
+
+        // Interest keeps accruing through the holiday
+        assert_eq!(
+            setup.loans.calculate_interest(&loan_id, &(86400 * 40)),
+            1_315
+        );
+    }
+
+    #[test]
+    fn test_forbearance_cures_default() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_defaulted_loan(&env, &setup, &borrower, &lender);
+        setup
+            .loans
+            .grant_forbearance(&loan_id, &(86400 * 30), &5000);
+
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.status, LoanStatus::Active);
+        let forbearance = setup.loans.get_forbearance(&loan_id).unwrap();
+        assert_eq!(forbearance.status, ForbearanceStatus::Active);
+        assert_eq!(forbearance.payment_reduction, 5000);
+
+        // Holiday over with the deferred installment still unpaid
+        env.ledger().set_timestamp(1_000_000 + 86400 * 72);
+        assert!(setup.loans.check_default(&loan_id));
+        assert_eq!(
+            setup.loans.get_forbearance(&loan_id).unwrap().status,
+            ForbearanceStatus::Completed
+        );
+    }
+
+    #[test]
+    fn test_recovery_plan_settles_defaulted_loan() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_defaulted_loan(&env, &setup, &borrower, &lender);
+        let deadline = env.ledger().timestamp() + 86400 * 30;
+        setup.loans.propose_recovery_plan(
+            &loan_id,
+            &RecoveryStrategy::Settlement,
+            &100_000,
+            &deadline,
+        );
+        setup.loans.accept_recovery_plan(&loan_id);
+
+        setup.loans.make_repayment(&loan_id, &60_000, &borrower);
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.status, LoanStatus::Defaulted);
+        assert_eq!(loan.default_fees, 0);
+        assert_eq!(loan.recovered_amount, 60_000);
+
+        setup.loans.make_repayment(&loan_id, &40_000, &borrower);
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.status, LoanStatus::Closed);
+        assert_eq!(loan.recovered_amount, 100_000);
+        assert_eq!(
+            setup.loans.get_recovery_plan(&loan_id).unwrap().status,
+            RecoveryStatus::Successful
+        );
+        assert!(!setup.registry.is_locked(&1));
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #24)")]
+    fn test_repayment_in_default_requires_recovery_plan() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_defaulted_loan(&env, &setup, &borrower, &lender);
+        setup.loans.make_repayment(&loan_id, &10_000, &borrower);
+    }
+
+    #[test]
+    fn test_seize_collateral_reports_default_and_records_sale() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let risk_id = env.register(MockRiskAssessment, ());
+        let risk = MockRiskAssessmentClient::new(&env, &risk_id);
+        setup.loans.set_risk_assessment(&risk_id);
+
+        let loan_id = create_defaulted_loan(&env, &setup, &borrower, &lender);
+        setup.loans.seize_collateral(&loan_id);
+
+        assert_eq!(setup.registry.seized_by(&1), Some(lender.clone()));
+        assert!(!setup.registry.is_locked(&1));
+        assert_eq!(risk.get_defaults(&borrower), 1);
+        let seizure = setup.loans.get_collateral_seizure(&loan_id).unwrap();
+        assert_eq!(seizure.status, SeizureStatus::InProgress);
+
+        setup.loans.record_seizure_sale(&loan_id, &90_000);
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.status, LoanStatus::Closed);
+        assert_eq!(loan.recovered_amount, 90_000);
+        let seizure = setup.loans.get_collateral_seizure(&loan_id).unwrap();
+        assert_eq!(seizure.status, SeizureStatus::Sold);
+        assert_eq!(seizure.sale_amount, 90_000);
+    }
+
+    #[test]
+    fn test_insured_loan_pays_premiums_and_claim_reduces_reported_loss() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let insurer = Address::generate(&env);
+        let risk = MockRiskAssessmentClient::new(&env, &env.register(MockRiskAssessment, ()));
+        let reputation =
+            MockReputationRegistryClient::new(&env, &env.register(MockReputationRegistry, ()));
+        setup.loans.set_risk_assessment(&risk.address);
+        setup.loans.set_reputation_registry(&reputation.address);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        setup.loans.insure_loan(&loan_id, &insurer, &8000, &500);
+
+        let policy = setup.loans.get_loan_insurance(&loan_id).unwrap();
+        assert_eq!(policy.status, InsuranceStatus::Active);
+        assert_eq!(policy.coverage_amount, 96_000);
+
+        // 5% of the repayment goes to the insurer
+        let token = token::Client::new(&env, &setup.asset);
+        env.ledger().set_timestamp(1_000_000 + 86400 * 10);
+        setup.loans.make_repayment(&loan_id, &12_000, &borrower);
+        assert_eq!(token.balance(&insurer), 600);
+        assert_eq!(token.balance(&lender), 11_400);
+        assert_eq!(
+            setup
+                .loans
+                .get_loan_insurance(&loan_id)
+                .unwrap()
+                .premiums_paid,
+            600
+        );
+
+        // The borrower stops paying and the lender claims 80% of the loss
+        env.ledger().set_timestamp(1_000_000 + 86400 * 100);
+        assert!(setup.loans.check_default(&loan_id));
+        let loan = setup.loans.get_loan_status(&loan_id);
+        let loss = loan.outstanding_balance + loan.default_fees;
+
+        mint(&env, &setup.asset, &insurer, 100_000);
+        token.approve(&insurer, &setup.loans.address, &100_000, &1000);
+        let payout = setup.loans.file_insurance_claim(&loan_id);
+        assert_eq!(payout, loss * 8000 / 10000);
+        assert_eq!(token.balance(&lender), 11_400 + payout);
+
+        let policy = setup.loans.get_loan_insurance(&loan_id).unwrap();
+        assert_eq!(policy.status, InsuranceStatus::Claimed);
+        assert_eq!(policy.claimed_amount, payout);
+        assert_eq!(
+            setup.loans.get_loan_status(&loan_id).recovered_amount,
+            payout
+        );
+
+        // Only the uninsured remainder is reported against the borrower
+        setup.loans.seize_collateral(&loan_id);
+        assert_eq!(risk.get_exposure(&borrower), loss - payout);
+        assert_eq!(
+            reputation.get_volume(&borrower, &ReputationOutcome::Default),
+            Some(loss - payout)
+        );
+    }
+
+    #[test]
+    fn test_liquidation_settles_loan_and_reports_outcome() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let risk = env.register(MockRiskAssessment, ());
+        let reputation =
+            MockReputationRegistryClient::new(&env, &env.register(MockReputationRegistry, ()));
+        setup.loans.set_risk_assessment(&risk);
+        setup.loans.set_reputation_registry(&reputation.address);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        let liquidator = Address::generate(&env);
+        let token = token::Client::new(&env, &setup.asset);
+        let lender_start = token.balance(&lender);
+
+        // A partial liquidation covers interest first, then principal
+        env.ledger().set_timestamp(1_000_000 + 86400 * 10);
+        assert_eq!(setup.loans.get_liquidation_payoff(&loan_id), 120_000 + 328);
+        mint(&env, &setup.asset, &setup.loans.address, 40_000);
+        let liquidation = setup
+            .loans
+            .settle_liquidation(&loan_id, &liquidator, &40_000, &3000);
+        assert_eq!(token.balance(&lender), lender_start + 40_000);
+        assert_eq!(liquidation.status, LiquidationStatus::Partial);
+        assert_eq!(liquidation.debt_covered, 40_000);
+        assert_eq!(setup.registry.liquidation_claim(&1, &liquidator), 3000);
+        assert!(setup.registry.is_locked(&1));
+
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.status, LoanStatus::Active);
+        assert_eq!(loan.outstanding_balance, 120_000 - (40_000 - 328));
+        assert_eq!(loan.recovered_amount, 40_000);
+        assert_eq!(
+            setup.loans.get_liquidation_payoff(&loan_id),
+            120_000 + 328 - 40_000
+        );
+        assert_eq!(
+            reputation.get_volume(&borrower, &ReputationOutcome::Late),
+            Some(40_000)
+        );
+
+        // Covering more than is owed closes the loan; the borrower keeps the
+        // share nobody liquidated as a claim alongside the liquidator's
+        mint(&env, &setup.asset, &setup.loans.address, 100_000);
+        let liquidation = setup
+            .loans
+            .settle_liquidation(&loan_id, &liquidator, &100_000, &5000);
+        assert_eq!(liquidation.status, LiquidationStatus::Closed);
+        assert_eq!(setup.registry.liquidation_claim(&1, &liquidator), 8000);
+        assert_eq!(setup.registry.liquidation_claim(&1, &borrower), 2000);
+        assert!(!setup.registry.is_locked(&1));
+        assert_eq!(liquidation.liquidation_count, 2);
+        assert_eq!(liquidation.outstanding_balance, 0);
+        assert_eq!(liquidation.debt_covered, 120_000 + 328);
+        // The excess over what was owed goes back to the liquidator
+        assert_eq!(token.balance(&liquidator), 140_000 - 120_000 - 328);
+        assert_eq!(token.balance(&lender), lender_start + 120_000 + 328);
+        assert_eq!(token.balance(&setup.loans.address), 0);
+
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.status, LoanStatus::Closed);
+        assert_eq!(
+            reputation.get_volume(&borrower, &ReputationOutcome::Default),
+            Some(120_000 + 328 - 40_000)
+        );
+        assert_eq!(setup.loans.get_liquidation(&loan_id), Some(liquidation));
+    }
+
+    #[test]
+    fn test_repaid_loan_keeps_partial_liquidation_claims() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+        setup
+            .loans
+            .set_risk_assessment(&env.register(MockRiskAssessment, ()));
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        let liquidator = Address::generate(&env);
+        mint(&env, &setup.asset, &setup.loans.address, 40_000);
+        setup
+            .loans
+            .settle_liquidation(&loan_id, &liquidator, &40_000, &3000);
+
+        // Repaying the rest doesn't hand the liquidated share back
+        setup.loans.make_repayment(&loan_id, &80_000, &borrower);
+        assert_eq!(
+            setup.loans.get_loan_status(&loan_id).status,
+            LoanStatus::Paid
+        );
+        assert!(!setup.registry.is_locked(&1));
+        assert_eq!(setup.registry.liquidation_claim(&1, &liquidator), 3000);
+        assert_eq!(setup.registry.liquidation_claim(&1, &borrower), 7000);
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #27)")]
+    fn test_closed_loan_cannot_be_liquidated_again() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+        setup
+            .loans
+            .set_risk_assessment(&env.register(MockRiskAssessment, ()));
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        let liquidator = Address::generate(&env);
+        mint(&env, &setup.asset, &setup.loans.address, 121_000);
+        setup
+            .loans
+            .settle_liquidation(&loan_id, &liquidator, &120_000, &10000);
+        setup
+            .loans
+            .settle_liquidation(&loan_id, &liquidator, &1_000, &10000);
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #30)")]
+    fn test_insurance_claim_requires_default() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+        let insurer = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        setup.loans.insure_loan(&loan_id, &insurer, &8000, &500);
+
+        setup.loans.file_insurance_claim(&loan_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #32)")]
+    fn test_seize_blocked_while_recovery_plan_runs() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_defaulted_loan(&env, &setup, &borrower, &lender);
+        let deadline = env.ledger().timestamp() + 86400 * 30;
+        setup.loans.propose_recovery_plan(
+            &loan_id,
+            &RecoveryStrategy::Settlement,
+            &100_000,
+            &deadline,
+        );
+        setup.loans.accept_recovery_plan(&loan_id);
+
+        setup.loans.seize_collateral(&loan_id);
+    }
+
+    fn no_changes() -> RestructuringTerms {
+        RestructuringTerms {
+            new_principal: None,
+            new_interest_rate: None,
+            new_term: None,
+            new_payment_schedule: ScheduleChange::Keep,
+            forgiveness_amount: None,
+        }
+    }
+
+    #[test]
+    fn test_accepted_restructuring_regenerates_schedule() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+
+        env.ledger().set_timestamp(1_000_000 + 86400 * 10);
+        let terms = RestructuringTerms {
+            new_interest_rate: Some(500),
+            new_term: Some(86400 * 180),
+            new_payment_schedule: ScheduleChange::Switch(RepaymentProfile::InterestOnly),
+            forgiveness_amount: Some(10_000),
+            ..no_changes()
+        };
+        let deadline = env.ledger().timestamp() + 86400 * 7;
+        let proposal_id = setup
+            .loans
+            .propose_restructuring(&loan_id, &lender, &terms, &deadline);
+        setup.loans.accept_restructuring(&proposal_id);
+
+        // 10 days of interest (328) capitalized, then 10,000 forgiven
+        let now = 1_000_000 + 86400 * 10;
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.status, LoanStatus::Restructured);
+        assert_eq!(loan.outstanding_balance, 110_328);
+        assert_eq!(loan.interest_rate, 500);
+        assert_eq!(loan.maturity_date, now + 86400 * 180);
+        assert_eq!(loan.repayment_profile, RepaymentProfile::InterestOnly);
+        assert_eq!(loan.next_payment_date, now + 86400 * 30);
+
+        let schedule = setup.loans.get_repayment_schedule(&loan_id);
+        assert_eq!(schedule.installments.len(), 6);
+        let balloon = schedule.installments.get(5).unwrap();
+        assert_eq!(balloon.principal, 110_328);
+        assert_eq!(balloon.due_date, now + 86400 * 180);
+
+        let proposal = setup.loans.get_restructuring_proposal(&proposal_id);
+        assert_eq!(proposal.status, ProposalStatus::Implemented);
+
+        let history = setup.loans.get_restructuring_history(&loan_id);
+        assert_eq!(history.restructuring_count, 1);
+        assert_eq!(history.total_forgiveness, 10_000);
+        assert_eq!(history.last_restructure_date, now);
+        assert_eq!(history.borrower, borrower);
+    }
+
+    #[test]
+    fn test_rejected_restructuring_leaves_loan_unchanged() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        let terms = RestructuringTerms {
+            forgiveness_amount: Some(50_000),
+            ..no_changes()
+        };
+        let proposal_id =
+            setup
+                .loans
+                .propose_restructuring(&loan_id, &borrower, &terms, &(1_000_000 + 86400));
+        setup.loans.reject_restructuring(&proposal_id);
+
+        let proposal = setup.loans.get_restructuring_proposal(&proposal_id);
+        assert_eq!(proposal.status, ProposalStatus::Rejected);
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.status, LoanStatus::Active);
+        assert_eq!(loan.outstanding_balance, 120_000);
+        assert_eq!(
+            setup
+                .loans
+                .get_restructuring_history(&loan_id)
+                .restructuring_count,
+            0
+        );
+
+        // A new proposal can follow the rejected one
+        setup
+            .loans
+            .propose_restructuring(&loan_id, &lender, &no_changes(), &(1_000_000 + 86400));
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #37)")]
+    fn test_restructuring_cannot_be_accepted_after_deadline() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        let proposal_id = setup.loans.propose_restructuring(
+            &loan_id,
+            &borrower,
+            &no_changes(),
+            &(1_000_000 + 86400),
+        );
+
+        env.ledger().set_timestamp(1_000_000 + 86400 * 2);
+        setup.loans.accept_restructuring(&proposal_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #38)")]
+    fn test_only_one_open_restructuring_proposal() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        setup
+            .loans
+            .propose_restructuring(&loan_id, &borrower, &no_changes(), &(1_000_000 + 86400));
+        setup
+            .loans
+            .propose_restructuring(&loan_id, &lender, &no_changes(), &(1_000_000 + 86400));
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #1)")]
+    fn test_restructuring_proposer_must_be_a_party() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        setup.loans.propose_restructuring(
+            &loan_id,
+            &Address::generate(&env),
+            &no_changes(),
+            &(1_000_000 + 86400),
+        );
+    }
+    /// Syndicate a 120,000 loan: the arranger commits 60,000 and the
+    /// participant 120,000, both approving the loan contract
+    fn create_syndicated_loan(
+        env: &Env,
+        setup: &LoanSetup,
+        borrower: &Address,
+        arranger: &Address,
+        participant: &Address,
+    ) -> u64 {
+        setup.registry.set_lendable_value(&1, &200_000);
+        let loan_id = setup.loans.create_loan(
+            borrower,
+            &1,
+            &setup.asset,
+            &120_000,
+            &1000,
+            &(86400 * 365),
+            &InterestType::Simple,
+            &RepaymentProfile::EqualInstallments,
+        );
+
+        setup.loans.create_syndication(&loan_id, arranger);
+        let token = token::Client::new(env, &setup.asset);
+        for (member, commitment) in [(arranger, 60_000), (participant, 120_000)] {
+            setup
+                .loans
+                .invite_participant(&loan_id, member, &commitment);
+            mint(env, &setup.asset, member, commitment);
+            token.approve(member, &setup.loans.address, &commitment, &1000);
+            setup.loans.accept_participation(&loan_id, member);
+        }
+
+        setup.loans.fund_syndicated_loan(&loan_id);
+        loan_id
+    }
+
+    #[test]
+    fn test_syndicated_repayment_split_pro_rata() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let arranger = Address::generate(&env);
+        let participant = Address::generate(&env);
+        setup.treasury.set_fee_bps(&1000);
+
+        let loan_id = create_syndicated_loan(&env, &setup, &borrower, &arranger, &participant);
+
+        // 6,000 interest, 600 protocol fee, 30,000 split one third / two thirds
+        env.ledger().set_timestamp(1_000_000 + 86400 * 365 / 2);
+        setup.loans.make_repayment(&loan_id, &30_600, &borrower);
+
+        let token = token::Client::new(&env, &setup.asset);
+        assert_eq!(token.balance(&arranger), 20_000 + 10_000);
+        assert_eq!(token.balance(&participant), 40_000 + 20_000);
+        assert_eq!(token.balance(&setup.treasury.address), 600);
+
+        // Paying off completes the syndicate
+        mint(&env, &setup.asset, &borrower, 6_000);
+        setup.loans.make_repayment(&loan_id, &95_400, &borrower);
+        assert_eq!(
+            setup.loans.get_syndication(&loan_id).status,
+            SyndicationStatus::Completed
+        );
+        assert_eq!(token.balance(&arranger), 61_800);
+        assert_eq!(token.balance(&participant), 123_600);
+    }
+
+    #[test]
+    fn test_syndicated_liquidation_split_pro_rata() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let arranger = Address::generate(&env);
+        let participant = Address::generate(&env);
+        let liquidator = Address::generate(&env);
+        setup.treasury.set_fee_bps(&1000);
+        setup
+            .loans
+            .set_risk_assessment(&env.register(MockRiskAssessment, ()));
+
+        let loan_id = create_syndicated_loan(&env, &setup, &borrower, &arranger, &participant);
+        mint(&env, &setup.asset, &setup.loans.address, 30_600);
+
+        // Liquidation proceeds take the repayment split, not a single lender
+        env.ledger().set_timestamp(1_000_000 + 86400 * 365 / 2);
+        setup
+            .loans
+            .settle_liquidation(&loan_id, &liquidator, &30_600, &3000);
+
+        let token = token::Client::new(&env, &setup.asset);
+        assert_eq!(token.balance(&setup.loans.address), 0);
+        assert_eq!(token.balance(&arranger), 20_000 + 10_000);
+        assert_eq!(token.balance(&participant), 40_000 + 20_000);
+        assert_eq!(token.balance(&setup.treasury.address), 600);
+    }
+
+    #[test]
+    fn test_sell_participation_moves_share() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let arranger = Address::generate(&env);
+        let participant = Address::generate(&env);
+        let buyer = Address::generate(&env);
+
+        let loan_id = create_syndicated_loan(&env, &setup, &borrower, &arranger, &participant);
+
+        mint(&env, &setup.asset, &buyer, 39_000);
+        setup
+            .loans
+            .sell_participation(&loan_id, &participant, &buyer, &40_000, &39_000);
+
+        let token = token::Client::new(&env, &setup.asset);
+        assert_eq!(token.balance(&participant), 40_000 + 39_000);
+        assert_eq!(token.balance(&buyer), 0);
+        assert_eq!(
+            setup
+                .loans
+                .get_syndicate_share(&loan_id, &participant)
+                .funded,
+            40_000
+        );
+        assert_eq!(
+            setup.loans.get_syndicate_share(&loan_id, &buyer).funded,
+            40_000
+        );
+        assert!(setup
+            .loans
+            .get_syndication(&loan_id)
+            .participants
+            .contains(&buyer));
+
+        // Repayments now follow the new split
+        setup.loans.make_repayment(&loan_id, &12_000, &borrower);
+        assert_eq!(token.balance(&arranger), 20_000 + 4_000);
+        assert_eq!(token.balance(&participant), 79_000 + 4_000);
+        assert_eq!(token.balance(&buyer), 4_000);
+    }
+
+    #[test]
+    fn test_pool_funded_loan_repays_and_writes_off_to_pool() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let pool = MockLendingPoolClient::new(&env, &env.register(MockLendingPool, ()));
+        pool.set_asset(&setup.asset);
+        mint(&env, &setup.asset, &pool.address, 500_000);
+        setup.loans.set_lending_pool(&pool.address);
+
+        setup.registry.set_lendable_value(&1, &200_000);
+        let loan_id = setup.loans.create_loan(
+            &borrower,
+            &1,
+            &setup.asset,
+            &120_000,
+            &1000,
+            &(86400 * 365),
+            &InterestType::Simple,
+            &RepaymentProfile::EqualInstallments,
+        );
+        setup.loans.fund_loan_from_pool(&loan_id);
+
+        // The pool lends at its own rate
+        let token = token::Client::new(&env, &setup.asset);
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.status, LoanStatus::Active);
+        assert_eq!(loan.lender, Some(pool.address.clone()));
+        assert_eq!(loan.interest_rate, 750);
+        assert_eq!(token.balance(&borrower), 120_000);
+
+        // Repayments return to the pool with their principal reported
+        env.ledger().set_timestamp(1_000_000 + 86400 * 10);
+        setup.loans.make_repayment(&loan_id, &12_000, &borrower);
+        assert_eq!(token.balance(&pool.address), 380_000 + 12_000);
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(pool.get_repaid(), 120_000 - loan.outstanding_balance);
+
+        // Defaulted principal is written off against the pool
+        env.ledger().set_timestamp(1_000_000 + 86400 * 100);
+        assert!(setup.loans.check_default(&loan_id));
+        assert_eq!(pool.get_written_off(), loan.outstanding_balance);
+    }
+
+    fn create_pool_funded_default<'a>(
+        env: &Env,
+        setup: &LoanSetup<'a>,
+        borrower: &Address,
+    ) -> (MockLendingPoolClient<'a>, u64) {
+        let pool = MockLendingPoolClient::new(env, &env.register(MockLendingPool, ()));
+        pool.set_asset(&setup.asset);
+        mint(env, &setup.asset, &pool.address, 500_000);
+        setup.loans.set_lending_pool(&pool.address);
+
+        setup.registry.set_lendable_value(&1, &200_000);
+        let loan_id = setup.loans.create_loan(
+            borrower,
+            &1,
+            &setup.asset,
+            &120_000,
+            &1000,
+            &(86400 * 365),
+            &InterestType::Simple,
+            &RepaymentProfile::EqualInstallments,
+        );
+        setup.loans.fund_loan_from_pool(&loan_id);
+        env.ledger().set_timestamp(1_000_000 + 86400 * 100);
+        assert!(setup.loans.check_default(&loan_id));
+        (pool, loan_id)
+    }
+
+    #[test]
+    fn test_pool_servicer_seizes_for_pool() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let servicer = Address::generate(&env);
+        let (pool, loan_id) = create_pool_funded_default(&env, &setup, &borrower);
+        pool.set_servicer(&servicer);
+
+        setup.loans.seize_collateral(&loan_id);
+
+        // The servicer signs; the collateral goes to the pool
+        assert_eq!(env.auths()[0].0, servicer);
+        assert_eq!(setup.registry.seized_by(&1), Some(pool.address.clone()));
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #1)")]
+    fn test_pool_loan_without_servicer_cannot_be_seized() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let (_, loan_id) = create_pool_funded_default(&env, &setup, &borrower);
+
+        setup.loans.seize_collateral(&loan_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #18)")]
+    fn test_pool_rejects_loan_in_other_asset() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let pool = MockLendingPoolClient::new(&env, &env.register(MockLendingPool, ()));
+        let pool_asset = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        pool.set_asset(&pool_asset);
+        mint(&env, &pool_asset, &pool.address, 500_000);
+        setup.loans.set_lending_pool(&pool.address);
+
+        setup.registry.set_lendable_value(&1, &200_000);
+        let loan_id = setup.loans.create_loan(
+            &borrower,
+            &1,
+            &setup.asset,
+            &120_000,
+            &1000,
+            &(86400 * 365),
+            &InterestType::Simple,
+            &RepaymentProfile::EqualInstallments,
+        );
+        setup.loans.fund_loan_from_pool(&loan_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #41)")]
+    fn test_syndication_requires_full_commitment() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let arranger = Address::generate(&env);
+
+        setup.registry.set_lendable_value(&1, &200_000);
+        let loan_id = setup.loans.create_loan(
+            &borrower,
+            &1,
+            &setup.asset,
+            &120_000,
+            &1000,
+            &(86400 * 365),
+            &InterestType::Simple,
+            &RepaymentProfile::EqualInstallments,
+        );
+        setup.loans.create_syndication(&loan_id, &arranger);
+        setup
+            .loans
+            .invite_participant(&loan_id, &arranger, &100_000);
+        setup.loans.accept_participation(&loan_id, &arranger);
+        setup.loans.fund_syndicated_loan(&loan_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #40)")]
+    fn test_syndicated_loan_rejects_direct_funding() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        setup.registry.set_lendable_value(&1, &200_000);
+        let loan_id = setup.loans.create_loan(
+            &borrower,
+            &1,
+            &setup.asset,
+            &120_000,
+            &1000,
+            &(86400 * 365),
+            &InterestType::Simple,
+            &RepaymentProfile::EqualInstallments,
+        );
+        setup.loans.create_syndication(&loan_id, &lender);
+        mint(&env, &setup.asset, &lender, 120_000);
+        setup.loans.fund_loan(&loan_id, &lender);
+    }
+    #[test]
+    fn test_covenant_breach_raises_rate_until_cured() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        let covenant_id = setup.loans.add_covenant(
+            &loan_id,
+            &CovenantType::MinCollateralCoverage,
+            &15_000,
+            &BreachAction::RateIncrease(500),
+        );
+        assert_eq!(setup.loans.check_covenants(&loan_id), 0);
+
+        // Coverage drops to 150,000 / 120,000 = 125%
+        env.ledger().set_timestamp(1_000_000 + 86400);
+        setup.registry.set_lendable_value(&1, &150_000);
+        assert_eq!(setup.loans.check_covenants(&loan_id), 1);
+
+        let covenant = setup.loans.get_covenants(&loan_id).get(0).unwrap();
+        assert_eq!(covenant.id, covenant_id);
+        assert_eq!(covenant.status, CovenantStatus::Breached);
+        assert_eq!(covenant.last_value, 12_500);
+        assert_eq!(covenant.breached_at, 1_000_000 + 86400);
+
+        // A day of interest at the old rate is capitalized before the step-up
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.interest_rate, 1_500);
+        assert_eq!(
+            loan.outstanding_balance,
+            120_000 + 120_000 * 1000 / 10000 / 365
+        );
+
+        // Topping up collateral cures the breach and restores the rate
+        setup.registry.set_lendable_value(&1, &200_000);
+        assert_eq!(setup.loans.check_covenants(&loan_id), 0);
+        assert_eq!(setup.loans.get_loan_status(&loan_id).interest_rate, 1_000);
+        assert_eq!(
+            setup.loans.get_covenants(&loan_id).get(0).unwrap().status,
+            CovenantStatus::Compliant
+        );
+    }
+
+    #[test]
+    fn test_past_due_breach_makes_loan_callable() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        setup.loans.add_covenant(
+            &loan_id,
+            &CovenantType::MaxDaysPastDue,
+            &2,
+            &BreachAction::Callable,
+        );
+
+        // First installment fell due three days ago
+        let now = 1_000_000 + 86400 * 33;
+        env.ledger().set_timestamp(now);
+        assert_eq!(setup.loans.check_covenants(&loan_id), 1);
+        assert!(setup.loans.get_loan_status(&loan_id).callable);
+
+        setup.loans.call_loan(&loan_id);
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert!(!loan.callable);
+        assert_eq!(loan.maturity_date, now);
+        assert_eq!(loan.next_payment_date, now + 1);
+
+        // Unpaid after the grace period, the called loan defaults
+        env.ledger().set_timestamp(now + 1 + loan.grace_period + 1);
+        assert!(setup.loans.check_default(&loan_id));
+    }
+
+    #[test]
+    fn test_reputation_and_insurance_covenants() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let reputation =
+            MockReputationRegistryClient::new(&env, &env.register(MockReputationRegistry, ()));
+        setup.loans.set_reputation_registry(&reputation.address);
+        reputation.set_score(&borrower, &700);
+        setup
+            .registry
+            .set_insurance_status(&1, &CollateralInsuranceStatus::Insured);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        setup.loans.add_covenant(
+            &loan_id,
+            &CovenantType::MinReputationScore,
+            &600,
+            &BreachAction::Notify,
+        );
+        setup.loans.add_covenant(
+            &loan_id,
+            &CovenantType::RequiredInsurance,
+            &0,
+            &BreachAction::Notify,
+        );
+        assert_eq!(setup.loans.check_covenants(&loan_id), 0);
+
+        reputation.set_score(&borrower, &550);
+        setup
+            .registry
+            .set_insurance_status(&1, &CollateralInsuranceStatus::Expired);
+        assert_eq!(setup.loans.check_covenants(&loan_id), 2);
+
+        let breach_events = env
+            .events()
+            .all()
+            .iter()
+            .filter(|(_, topics, _)| {
+                topics
+                    .get(0)
+                    .unwrap()
+                    .shallow_eq(&symbol_short!("cov_brch").to_val())
+            })
+            .count();
+        assert_eq!(breach_events, 2);
+
+        // A waived covenant is no longer evaluated
+        let covenants = setup.loans.get_covenants(&loan_id);
+        setup.loans.waive_covenant(&covenants.get(1).unwrap().id);
+        assert_eq!(setup.loans.check_covenants(&loan_id), 1);
+        assert_eq!(
+            setup.loans.get_covenants(&loan_id).get(1).unwrap().status,
+            CovenantStatus::Waived
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #44)")]
+    fn test_call_loan_requires_breach() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        setup.loans.call_loan(&loan_id);
+    }
+    #[test]
+    fn test_risk_metrics_track_defaults_and_recoveries() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let loan_id = create_defaulted_loan(&env, &setup, &borrower, &lender);
+
+        let risk = setup.loans.get_risk_metrics(&None);
+        assert_eq!(risk.default_probability, 10000);
+        assert_eq!(risk.loss_given_default, 10000);
+        assert_eq!(risk.value_at_risk, 120_000);
+        assert_eq!(risk.expected_loss, 120_000);
+
+        let performance = setup.loans.get_performance_metrics(&loan_id);
+        assert_eq!(performance.missed_payments, 1);
+        assert_eq!(performance.payment_history_score, 0);
+        assert_eq!(performance.delinquency_rate, 10000);
+        assert_eq!(performance.days_past_due, 6);
+
+        // Half the defaulted exposure is recovered from the collateral sale
+        setup.loans.seize_collateral(&loan_id);
+        setup.loans.record_seizure_sale(&loan_id, &60_000);
+
+        let risk = setup.loans.get_risk_metrics(&Some(borrower));
+        assert_eq!(risk.loss_given_default, 5000);
+        assert_eq!(risk.portfolio_risk_score, 5000);
+        assert_eq!(risk.value_at_risk, 0);
+
+        let aggregate = setup.loans.get_portfolio_aggregate(&PortfolioScope::All);
+        assert_eq!(aggregate.closed_loans, 1);
+        assert_eq!(aggregate.defaulted_loans, 0);
+        assert_eq!(aggregate.recovered_amount, 60_000);
+    }
+    /// Open and commit a 100,000 line at 10% with a 0.5% commitment fee
+    fn create_credit_line(
+        env: &Env,
+        setup: &LoanSetup,
+        borrower: &Address,
+        lender: &Address,
+        security: CreditSecurity,
+    ) -> u64 {
+        let line_id = setup.loans.open_credit_line(
+            borrower,
+            &security,
+            &setup.asset,
+            &100_000,
+            &1000,
+            &50,
+            &(86400 * 365),
+        );
+        mint(env, &setup.asset, lender, 100_000);
+        setup.loans.commit_credit_line(&line_id, lender);
+        line_id
+    }
+
+    #[test]
+    fn test_credit_line_revolves_with_commitment_fee() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        setup.registry.set_lendable_value(&1, &200_000);
+        let line_id = create_credit_line(
+            &env,
+            &setup,
+            &borrower,
+            &lender,
+            CreditSecurity::Collateral(1),
+        );
+        assert!(setup.registry.is_locked(&1));
+
+        setup.loans.draw_credit_line(&line_id, &40_000);
+        let token = token::Client::new(&env, &setup.asset);
+        assert_eq!(token.balance(&borrower), 40_000);
+
+        // Half a year: 10% on 40,000 drawn and 0.5% on 60,000 undrawn
+        env.ledger().set_timestamp(1_000_000 + 86400 * 365 / 2);
+        let line = setup.loans.get_credit_line(&line_id);
+        assert_eq!(line.accrued_interest, 2_000);
+        assert_eq!(line.accrued_fees, 150);
+
+        mint(&env, &setup.asset, &borrower, 2_150);
+        setup.loans.repay_credit_line(&line_id, &42_150, &borrower);
+        assert_eq!(token.balance(&lender), 2_150);
+        assert_eq!(setup.loans.get_credit_line(&line_id).drawn, 0);
+
+        // Repaid principal can be drawn again up to the limit, against a
+        // fresh valuation
+        setup.registry.set_lendable_value(&1, &200_000);
+        setup.loans.draw_credit_line(&line_id, &100_000);
+        setup.loans.repay_credit_line(&line_id, &100_000, &borrower);
+
+        setup.loans.close_credit_line(&line_id, &borrower);
+        assert_eq!(token.balance(&lender), 102_150);
+        assert!(!setup.registry.is_locked(&1));
+        assert_eq!(
+            setup.loans.get_credit_line(&line_id).status,
+            CreditLineStatus::Closed
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #47)")]
+    fn test_credit_line_draw_above_limit_rejected() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        setup.registry.set_lendable_value(&1, &200_000);
+        let line_id = create_credit_line(
+            &env,
+            &setup,
+            &borrower,
+            &lender,
+            CreditSecurity::Collateral(1),
+        );
+        setup.loans.draw_credit_line(&line_id, &60_000);
+        setup.loans.draw_credit_line(&line_id, &40_001);
+    }
+
+    #[test]
+    fn test_credit_line_freezes_on_coverage_and_covenant_failure() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let reputation =
+            MockReputationRegistryClient::new(&env, &env.register(MockReputationRegistry, ()));
+        setup.loans.set_reputation_registry(&reputation.address);
+        reputation.set_score(&borrower, &700);
+
+        setup.registry.set_basket_value(&3, &200_000);
+        let line_id =
+            create_credit_line(&env, &setup, &borrower, &lender, CreditSecurity::Basket(3));
+        assert!(setup.registry.is_basket_locked(&3));
+        setup
+            .loans
+            .add_credit_line_covenant(&line_id, &CovenantType::MinReputationScore, &600);
+        setup.loans.draw_credit_line(&line_id, &90_000);
+        assert_eq!(
+            setup.loans.check_credit_line(&line_id),
+            CreditLineStatus::Open
+        );
+
+        // Borrowing base falls to 80% of 100,000, below the drawn balance
+        setup.registry.set_basket_value(&3, &100_000);
+        assert_eq!(
+            setup.loans.check_credit_line(&line_id),
+            CreditLineStatus::Frozen
+        );
+
+        setup.registry.set_basket_value(&3, &200_000);
+        assert_eq!(
+            setup.loans.check_credit_line(&line_id),
+            CreditLineStatus::Open
+        );
+
+        reputation.set_score(&borrower, &500);
+        assert_eq!(
+            setup.loans.check_credit_line(&line_id),
+            CreditLineStatus::Frozen
+        );
+        assert_eq!(
+            setup
+                .loans
+                .get_credit_line_covenants(&line_id)
+                .get(0)
+                .unwrap()
+                .status,
+            CovenantStatus::Breached
+        );
+    }
+
+    #[test]
+    fn test_credit_line_defaults_after_maturity_and_security_is_seized() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        setup.registry.set_lendable_value(&1, &200_000);
+        let line_id = create_credit_line(
+            &env,
+            &setup,
+            &borrower,
+            &lender,
+            CreditSecurity::Collateral(1),
+        );
+        setup.loans.draw_credit_line(&line_id, &40_000);
+        assert_eq!(
+            setup.loans.get_credit_line(&line_id).grace_period,
+            86400 * 5
+        );
+
+        // Still inside the grace period after maturity
+        env.ledger().set_timestamp(1_000_000 + 86400 * 368);
+        setup.registry.set_lendable_value(&1, &200_000);
+        assert_eq!(
+            setup.loans.check_credit_line(&line_id),
+            CreditLineStatus::Open
+        );
+
+        env.ledger().set_timestamp(1_000_000 + 86400 * 371);
+        assert_eq!(
+            setup.loans.check_credit_line(&line_id),
+            CreditLineStatus::Defaulted
+        );
+
+        // 40,000 drawn, 10% for 371 days, plus a year's fee on 60,000 undrawn
+        let owed = setup.loans.seize_credit_security(&line_id);
+        assert_eq!(owed, 40_000 + 4_065 + 300);
+        assert_eq!(setup.registry.seized_by(&1), Some(lender.clone()));
+
+        // The undrawn commitment goes back to the lender
+        let token = token::Client::new(&env, &setup.asset);
+        assert_eq!(token.balance(&lender), 60_000);
+        assert_eq!(
+            setup.loans.get_credit_line(&line_id).status,
+            CreditLineStatus::Closed
+        );
+    }
+
+    #[test]
+    fn test_defaulted_basket_line_seizes_basket() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        setup.registry.set_basket_value(&3, &200_000);
+        let line_id =
+            create_credit_line(&env, &setup, &borrower, &lender, CreditSecurity::Basket(3));
+        setup.loans.draw_credit_line(&line_id, &10_000);
+
+        env.ledger().set_timestamp(1_000_000 + 86400 * 371);
+        setup.loans.check_credit_line(&line_id);
+        setup.loans.seize_credit_security(&line_id);
+
+        assert_eq!(setup.registry.basket_seized_by(&3), Some(lender));
+        assert!(!setup.registry.is_basket_locked(&3));
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #20)")]
+    fn test_basket_line_rejects_unverified_member() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        setup.registry.set_basket_value(&3, &200_000);
+        setup.registry.set_basket_unverified(&3);
+        create_credit_line(&env, &setup, &borrower, &lender, CreditSecurity::Basket(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #20)")]
+    fn test_basket_line_rejects_stale_member() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        setup.registry.set_basket_value(&3, &200_000);
+        env.ledger().set_timestamp(1_000_000 + 86400 * 31);
+        create_credit_line(&env, &setup, &borrower, &lender, CreditSecurity::Basket(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #30)")]
+    fn test_seize_credit_security_requires_default() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        setup.registry.set_lendable_value(&1, &200_000);
+        let line_id = create_credit_line(
+            &env,
+            &setup,
+            &borrower,
+            &lender,
+            CreditSecurity::Collateral(1),
+        );
+        setup.loans.draw_credit_line(&line_id, &40_000);
+        setup.loans.seize_credit_security(&line_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #46)")]
+    fn test_frozen_credit_line_rejects_draws() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        setup.registry.set_lendable_value(&1, &200_000);
+        let line_id = create_credit_line(
+            &env,
+            &setup,
+            &borrower,
+            &lender,
+            CreditSecurity::Collateral(1),
+        );
+        setup.loans.draw_credit_line(&line_id, &90_000);
+
+        setup.registry.set_lendable_value(&1, &100_000);
+        setup.loans.check_credit_line(&line_id);
+        setup.loans.draw_credit_line(&line_id, &1);
+    }
+
+    #[test]
+    fn test_overdue_installment_accrues_late_fee_and_penalty_interest() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        // 5% late fee and 36.5% penalty interest, 0.1% a day
+        set_loan_fees(&setup, 500, 3650);
+        let loan_id = create_funded_loan(&env, &setup, &borrower, &lender);
+        let first = setup
+            .loans
+            .get_repayment_schedule(&loan_id)
+            .installments
+            .get(0)
+            .unwrap();
+
+        // Three days past the first due date, still inside the grace period
+        env.ledger().set_timestamp(first.due_date + 86400 * 3);
+        assert!(!setup.loans.check_default(&loan_id));
+
+        let late_fee = first.amount * 500 / 10000;
+        let penalty = first.amount * 3650 * 3 / 10000 / 365;
+        let overdue = setup
+            .loans
+            .get_repayment_schedule(&loan_id)
+            .installments
+            .get(0)
+            .unwrap();
+        assert_eq!(overdue.status, InstallmentStatus::Overdue);
+        assert_eq!(overdue.fees, first.fees + late_fee + penalty);
+        assert_eq!(overdue.amount, first.amount + late_fee + penalty);
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.fees, 1_200 + late_fee + penalty);
+
+        // The late fee is charged once; penalty interest keeps accruing
+        env.ledger().set_timestamp(first.due_date + 86400 * 4);
+        setup.loans.check_default(&loan_id);
+        let fees = setup.loans.get_loan_status(&loan_id).fees;
+        let unpaid = first.amount + late_fee + penalty;
+        assert_eq!(fees, loan.fees + unpaid * 3650 / 10000 / 365);
+
+        // Paying the installment clears the fee bucket before interest
+        mint(&env, &setup.asset, &borrower, unpaid * 2);
+        setup
+            .loans
+            .make_repayment(&loan_id, &(unpaid * 2), &borrower);
+        let loan = setup.loans.get_loan_status(&loan_id);
+        assert_eq!(loan.fees, 0);
+        assert_eq!(loan.total_fees_paid, fees);
+    }
+
+    fn create_floating_loan(
+        env: &Env,
+        setup: &LoanSetup,
+        borrower: &Address,
+        lender: &Address,
+    ) -> (u64, MockRiskAssessmentClient<'static>) {
+        let risk_id = env.register(MockRiskAssessment, ());
+        let risk = MockRiskAssessmentClient::new(env, &risk_id);
+        setup.loans.set_risk_assessment(&risk_id);
+        setup.loans.set_governance(&Address::generate(env));
+        setup.loans.set_base_rate(&300);
+        risk.set_credit_score(borrower, &800);
+
+        setup.registry.set_lendable_value(&1, &200_000);
+        let loan_id = setup.loans.create_loan(
+            borrower,
+            &1,
+            &setup.asset,
+            &120_000,
+            &1000,
+            &(86400 * 365),
+            &InterestType::Floating,
+            &RepaymentProfile::Bullet,
+        );
+        setup
+            .loans
+            .set_floating_rate_terms(&loan_id, &400, &900, &(86400 * 10));
+
+        mint(env, &setup.asset, lender, 120_000);
+        setup.loans.fund_loan(&loan_id, lender);
+        (loan_id, risk)
+    }
+
+    #[test]
+    fn test_floating_rate_resets_within_cap_and_floor() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        // Funding prices the loan at 3% base + 2% spread
+        let (loan_id, risk) = create_floating_loan(&env, &setup, &borrower, &lender);
+        assert_eq!(setup.loans.get_loan_status(&loan_id).interest_rate, 500);
+        let terms = setup.loans.get_floating_rate_terms(&loan_id).unwrap();
+        assert_eq!(terms.next_reset, 1_000_000 + 86400 * 10);
+
+        // A rising base rate is capped
+        env.ledger().set_timestamp(1_000_000 + 86400 * 10);
+        setup.loans.set_base_rate(&1_000);
+        assert_eq!(setup.loans.reset_floating_rate(&loan_id), 900);
+
+        // An improved score and falling base rate hit the floor
+        env.ledger().set_timestamp(1_000_000 + 86400 * 25);
+        setup.loans.set_base_rate(&0);
+        risk.set_credit_score(&borrower, &950);
+        assert_eq!(setup.loans.reset_floating_rate(&loan_id), 400);
+        let terms = setup.loans.get_floating_rate_terms(&loan_id).unwrap();
+        assert_eq!(terms.next_reset, 1_000_000 + 86400 * 30);
+
+        let history = setup.loans.get_rate_history(&loan_id);
+        assert_eq!(history.len(), 3);
+        let reset = history.get(1).unwrap();
+        assert_eq!(reset.old_rate, 500);
+        assert_eq!(reset.new_rate, 900);
+        assert_eq!(reset.effective_date, 1_000_000 + 86400 * 10);
+    }
+
+    #[test]
+    fn test_floating_interest_accrues_at_each_rate() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let (loan_id, _) = create_floating_loan(&env, &setup, &borrower, &lender);
+
+        env.ledger().set_timestamp(1_000_000 + 86400 * 10);
+        setup.loans.set_base_rate(&500);
+        assert_eq!(setup.loans.reset_floating_rate(&loan_id), 700);
+
+        // Ten days at 5% then ten days at 7%
+        assert_eq!(
+            setup.loans.calculate_interest(&loan_id, &(86400 * 20)),
+            120_000 * 500 * 10 / 10000 / 365 + 120_000 * 700 * 10 / 10000 / 365
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "HostError: Error(Contract, #50)")]
+    fn test_floating_rate_reset_before_period_rejected() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000_000);
+        let setup = setup_loans(&env);
+        let borrower = Address::generate(&env);
+        let lender = Address::generate(&env);
+
+        let (loan_id, _) = create_floating_loan(&env, &setup, &borrower, &lender);
+
+        env.ledger().set_timestamp(1_000_000 + 86400 * 9);
+        setup.loans.reset_floating_rate(&loan_id);
+    }
+}
+
+mod interest_properties {
+    use loan_management::interest::{
+        compound_interest, growth_factor, simple_interest, Compounding, SCALE,
+    };
+    use proptest::prelude::*;
+
+    const YEAR: u64 = 86400 * 365;
+
+    fn compounding() -> impl Strategy<Value = Compounding> {
+        prop_oneof![
+            Just(Compounding::Daily),
+            Just(Compounding::Monthly),
+            Just(Compounding::Continuous),
+        ]
+    }
+
+    // Property: compound interest never falls below simple interest
+    proptest! {
+        #[test]
+        fn prop_compound_at_least_simple(
+            principal in 1i128..1_000_000_000_000_000,
+            rate in 1u32..100_000,
+            elapsed in 1u64..(YEAR * 5),
+            compounding in compounding(),
+        ) {
+            let simple = simple_interest(principal, rate, elapsed, YEAR).unwrap();
+            let compound = compound_interest(principal, rate, elapsed, YEAR, compounding).unwrap();
+            prop_assert!(compound >= simple);
+        }
+    }
+
+    // Property: the unclamped growth factor is never below simple growth,
+    // allowing one unit of fixed-point truncation per elapsed day
+    proptest! {
+        #[test]
+        fn prop_growth_factor_at_least_simple(
+            rate in 1u32..100_000,
+            elapsed in 1u64..(YEAR * 5),
+            compounding in compounding(),
+        ) {
+            let simple = SCALE * rate as i128 * elapsed as i128 / (10000 * YEAR as i128);
+            let factor = growth_factor(rate, elapsed, YEAR, compounding).unwrap();
+            let tolerance = (elapsed / 86400) as i128 + 2;
+            prop_assert!(factor - SCALE + tolerance >= simple);
+        }
+    }
 
-        // Test loan securitization
+    // Property: once interest compounds at least twice, compound strictly exceeds simple
+    proptest! {
+        #[test]
+        fn prop_compound_exceeds_simple(
+            principal in 10_000_000i128..1_000_000_000_000_000,
+            rate in 100u32..100_000,
+            elapsed in (86400 * 62)..(YEAR * 5),
+            compounding in compounding(),
+        ) {
+            let simple = simple_interest(principal, rate, elapsed, YEAR).unwrap();
+            let compound = compound_interest(principal, rate, elapsed, YEAR, compounding).unwrap();
+            prop_assert!(compound > simple);
+        }
     }
 }
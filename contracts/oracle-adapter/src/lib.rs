@@ -3,12 +3,14 @@
 //! This contract manages oracle providers and verifies off-chain events
 //! such as shipment confirmations, delivery status, and quality inspections.
 //! It serves as the bridge between on-chain escrow operations and trusted oracles.
+//! Registered oracles also publish FX rates used to value collateral across
+//! currencies.
 
 #![no_std]
 
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env,
-    Symbol, Vec,
+    String, Symbol, Vec,
 };
 
 #[contracterror]
@@ -25,6 +27,8 @@ pub enum ContractError {
     ConsensusNotMet = 9,
     InvalidThreshold = 10,
     NoPendingAdmin = 11,
+    FxRateNotFound = 12,
+    InvalidFxRate = 13,
 }
 
 /// Event types for oracle confirmations
@@ -50,6 +54,42 @@ pub struct ConfirmationData {
     pub verified: bool,
 }
 
+/// Fixed-point scale of FX rates (7 decimals)
+pub const FX_RATE_SCALE: i128 = 10_000_000;
+
+/// Latest FX rate for a currency pair
+///
+/// `rate` is the amount of `quote` currency per unit of `base` currency,
+/// scaled by `FX_RATE_SCALE`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FxRate {
+    pub base: String,
+    pub quote: String,
+    pub rate: i128,
+    pub oracle: Address,
+    pub timestamp: u64,
+}
+
+/// One oracle's report in an open FX round
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FxSubmission {
+    pub oracle: Address,
+    pub rate: i128,
+}
+
+/// Reports collected for a currency pair until the FX threshold is reached
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FxRound {
+    pub submissions: Vec<FxSubmission>,
+    pub opened_at: u64,
+}
+
+/// Seconds an FX round stays open before its reports are discarded
+pub const FX_ROUND_WINDOW: u64 = 3600;
+
 /// Contract data structure for storage
 #[contracttype]
 #[derive(Clone)]
@@ -65,6 +105,10 @@ const ORACLE_REMOVED: Symbol = symbol_short!("orc_rem");
 const ORACLE_CONFIRMED: Symbol = symbol_short!("confirmed");
 const INITIALIZED: Symbol = symbol_short!("init");
 const CONFIRMING_ORACLES: Symbol = symbol_short!("conf_orc");
+const FX_RATE: Symbol = symbol_short!("fx_rate");
+const FX_ROUND: Symbol = symbol_short!("fx_round");
+const FX_SUBMITTED: Symbol = symbol_short!("fx_subm");
+const FX_THRESHOLD: Symbol = symbol_short!("fx_thrsh");
 
 /// Main contract for oracle adapter operations
 #[contract]
@@ -415,6 +459,140 @@ impl OracleAdapter {
         Ok(unique_oracle_count >= threshold)
     }
 
+    /// Report the FX rate for a currency pair (registered oracles only)
+    ///
+    /// Reports are collected in a round until `threshold` distinct oracles
+    /// have reported within `FX_ROUND_WINDOW`; the median report is then
+    /// published. A later report from the same oracle replaces its earlier
+    /// one, and a round older than the window starts over.
+    ///
+    /// # Arguments
+    /// * `oracle` - The reporting oracle
+    /// * `base` - Currency being priced, e.g. "KES"
+    /// * `quote` - Currency the price is expressed in, e.g. "USDC"
+    /// * `rate` - Units of `quote` per unit of `base`, scaled by `FX_RATE_SCALE`
+    ///
+    /// # Events
+    /// Emits `FX_RATE` event when the rate is published, otherwise
+    /// `FX_SUBMITTED`
+    pub fn submit_fx_rate(
+        env: Env,
+        oracle: Address,
+        base: String,
+        quote: String,
+        rate: i128,
+    ) -> Result<(), ContractError> {
+        let contract_data = Self::get_contract_data(&env)?;
+
+        if !Self::is_oracle_registered(&contract_data, &oracle) {
+            return Err(ContractError::OracleNotRegistered);
+        }
+        oracle.require_auth();
+
+        if rate <= 0 || base == quote {
+            return Err(ContractError::InvalidFxRate);
+        }
+
+        let now = env.ledger().timestamp();
+        let round_key = (FX_ROUND, base.clone(), quote.clone());
+        let (previous, opened_at) = match env.storage().persistent().get::<_, FxRound>(&round_key) {
+            Some(round) if now <= round.opened_at + FX_ROUND_WINDOW => {
+                (round.submissions, round.opened_at)
+            }
+            _ => (Vec::new(&env), now),
+        };
+
+        // Keep reports from registered oracles sorted by rate, one per oracle
+        let mut submissions = Vec::new(&env);
+        for submission in previous.iter() {
+            if submission.oracle != oracle
+                && Self::is_oracle_registered(&contract_data, &submission.oracle)
+            {
+                submissions.push_back(submission);
+            }
+        }
+        let mut index = 0;
+        while index < submissions.len() && submissions.get(index).unwrap().rate <= rate {
+            index += 1;
+        }
+        submissions.insert(
+            index,
+            FxSubmission {
+                oracle: oracle.clone(),
+                rate,
+            },
+        );
+
+        if submissions.len() < Self::get_fx_threshold(env.clone()) {
+            env.storage().persistent().set(
+                &round_key,
+                &FxRound {
+                    submissions: submissions.clone(),
+                    opened_at,
+                },
+            );
+            env.events().publish(
+                (FX_SUBMITTED,),
+                (base, quote, rate, oracle, submissions.len()),
+            );
+            return Ok(());
+        }
+
+        let median = submissions.get((submissions.len() - 1) / 2).unwrap();
+        let fx_rate = FxRate {
+            base: base.clone(),
+            quote: quote.clone(),
+            rate: median.rate,
+            oracle: median.oracle.clone(),
+            timestamp: now,
+        };
+        env.storage()
+            .persistent()
+            .set(&(FX_RATE, base.clone(), quote.clone()), &fx_rate);
+        env.storage().persistent().remove(&round_key);
+
+        env.events()
+            .publish((FX_RATE,), (base, quote, median.rate, median.oracle));
+
+        Ok(())
+    }
+
+    /// Set how many oracles must report before an FX rate is published
+    /// (admin only)
+    ///
+    /// # Arguments
+    /// * `threshold` - Number of distinct registered oracles, at most the
+    ///   number registered
+    pub fn set_fx_threshold(env: Env, threshold: u32) -> Result<(), ContractError> {
+        Self::check_admin(&env)?;
+
+        let contract_data = Self::get_contract_data(&env)?;
+        if threshold == 0 || threshold > contract_data.oracles.len() {
+            return Err(ContractError::InvalidThreshold);
+        }
+
+        env.storage().instance().set(&FX_THRESHOLD, &threshold);
+
+        Ok(())
+    }
+
+    /// Get how many oracles must report before an FX rate is published
+    pub fn get_fx_threshold(env: Env) -> u32 {
+        env.storage().instance().get(&FX_THRESHOLD).unwrap_or(1)
+    }
+
+    /// Get the latest FX rate for a currency pair
+    ///
+    /// # Arguments
+    /// * `base` - Currency being priced
+    /// * `quote` - Currency the price is expressed in
+    pub fn get_fx_rate(env: Env, base: String, quote: String) -> Result<FxRate, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&(FX_RATE, base, quote))
+            .ok_or(ContractError::FxRateNotFound)
+    }
+
     // Helper functions
 
     fn is_initialized(env: &Env) -> bool {
@@ -508,7 +686,7 @@ impl OracleAdapter {
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::testutils::{Address as _, Ledger};
     use soroban_sdk::{
         testutils::MockAuth, testutils::MockAuthInvoke, Address, Bytes, Env, IntoVal,
     };
@@ -696,6 +874,92 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_fx_rate_submission() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(OracleAdapter, ());
+        let client = OracleAdapterClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let oracle = Address::generate(&env);
+        let unregistered = Address::generate(&env);
+        client.initialize(&admin);
+        client.add_oracle(&oracle);
+
+        let kes = String::from_str(&env, "KES");
+        let usdc = String::from_str(&env, "USDC");
+        assert_eq!(
+            client.try_get_fx_rate(&kes, &usdc),
+            Err(Ok(ContractError::FxRateNotFound))
+        );
+        assert_eq!(
+            client.try_submit_fx_rate(&unregistered, &kes, &usdc, &77_500),
+            Err(Ok(ContractError::OracleNotRegistered))
+        );
+        assert_eq!(
+            client.try_submit_fx_rate(&oracle, &kes, &usdc, &0),
+            Err(Ok(ContractError::InvalidFxRate))
+        );
+
+        // 1 KES = 0.00775 USDC
+        client.submit_fx_rate(&oracle, &kes, &usdc, &77_500);
+        let fx_rate = client.get_fx_rate(&kes, &usdc);
+        assert_eq!(fx_rate.rate, 77_500);
+        assert_eq!(fx_rate.oracle, oracle);
+        assert_eq!(
+            client.try_get_fx_rate(&usdc, &kes),
+            Err(Ok(ContractError::FxRateNotFound))
+        );
+    }
+
+    #[test]
+    fn test_fx_rate_requires_threshold_and_publishes_median() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(OracleAdapter, ());
+        let client = OracleAdapterClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+        let third = Address::generate(&env);
+        client.initialize(&admin);
+        client.add_oracle(&first);
+        client.add_oracle(&second);
+        client.add_oracle(&third);
+
+        assert_eq!(
+            client.try_set_fx_threshold(&4),
+            Err(Ok(ContractError::InvalidThreshold))
+        );
+        client.set_fx_threshold(&3);
+
+        let kes = String::from_str(&env, "KES");
+        let usdc = String::from_str(&env, "USDC");
+        client.submit_fx_rate(&first, &kes, &usdc, &80_000);
+        client.submit_fx_rate(&second, &kes, &usdc, &77_000);
+        // A repeat report replaces the oracle's earlier one
+        client.submit_fx_rate(&first, &kes, &usdc, &78_000);
+        assert_eq!(
+            client.try_get_fx_rate(&kes, &usdc),
+            Err(Ok(ContractError::FxRateNotFound))
+        );
+
+        client.submit_fx_rate(&third, &kes, &usdc, &90_000);
+        let fx_rate = client.get_fx_rate(&kes, &usdc);
+        assert_eq!(fx_rate.rate, 78_000);
+        assert_eq!(fx_rate.oracle, first);
+
+        // Reports older than the round window are discarded
+        client.submit_fx_rate(&first, &kes, &usdc, &70_000);
+        client.submit_fx_rate(&second, &kes, &usdc, &70_000);
+        env.ledger()
+            .with_mut(|li| li.timestamp += FX_ROUND_WINDOW + 1);
+        client.submit_fx_rate(&third, &kes, &usdc, &70_000);
+        assert_eq!(client.get_fx_rate(&kes, &usdc).rate, 78_000);
+    }
+
     #[test]
     fn test_replay_attack_prevention() {
         let env = Env::default();